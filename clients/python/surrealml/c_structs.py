@@ -64,6 +64,8 @@ class Vecf32Return(Structure):
         capacity: the capacity of the vector
         is_error: 1 if error, 0 if not
         error_message: the error message (only present if error)
+        shape: the shape of the output (only present if successful)
+        shape_length: the number of dimensions in the shape
     """
     _fields_ = [
         ("data", POINTER(c_float)),  # Pointer to f32 array
@@ -71,6 +73,8 @@ class Vecf32Return(Structure):
         ("capacity", c_size_t),            # Capacity of the array
         ("is_error", c_int),               # Indicates if it's an error
         ("error_message", c_char_p),       # Optional error message
+        ("shape", POINTER(c_size_t)),      # Pointer to the shape of the output
+        ("shape_length", c_size_t),        # Number of dimensions in the shape
     ]
 
 
@@ -117,8 +117,100 @@ class LibLoader(metaclass=Singleton):
         ]
         self.lib.add_output.restype = EmptyReturn
 
+        # encoders expanding string columns into numeric columns
+        self.lib.add_one_hot_encoder.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # column_name
+            ctypes.POINTER(ctypes.c_char_p),  # vocabulary
+            ctypes.c_size_t,  # vocabulary_length
+        ]
+        self.lib.add_one_hot_encoder.restype = EmptyReturn
+        self.lib.add_ordinal_encoder.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # column_name
+            ctypes.POINTER(ctypes.c_char_p),  # categories
+            ctypes.c_size_t,  # categories_length
+            ctypes.c_char_p,  # unknown
+        ]
+        self.lib.add_ordinal_encoder.restype = EmptyReturn
+        self.lib.add_hashing_encoder.argtypes = [ctypes.c_char_p, ctypes.c_char_p, ctypes.c_uint32]
+        self.lib.add_hashing_encoder.restype = EmptyReturn
+        self.lib.add_target_encoder.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # column_name
+            ctypes.POINTER(ctypes.c_char_p),  # categories
+            ctypes.POINTER(ctypes.c_float),  # targets
+            ctypes.c_size_t,  # length
+            ctypes.c_char_p,  # default
+        ]
+        self.lib.add_target_encoder.restype = EmptyReturn
+
+        self.lib.add_normaliser_step.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # column_name
+            ctypes.c_char_p,  # normaliser_type
+            ctypes.c_float,  # one
+            ctypes.c_float,  # two
+        ]
+        self.lib.add_normaliser_step.restype = EmptyReturn
+        self.lib.add_output_normaliser_step.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # normaliser_type
+            ctypes.c_float,  # one
+            ctypes.c_float,  # two
+        ]
+        self.lib.add_output_normaliser_step.restype = EmptyReturn
+        self.lib.add_normaliser_definition.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # column_name
+            ctypes.c_char_p,  # definition
+        ]
+        self.lib.add_normaliser_definition.restype = EmptyReturn
+        self.lib.add_output_normaliser_definition.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # definition
+        ]
+        self.lib.add_output_normaliser_definition.restype = EmptyReturn
+        self.lib.add_provenance.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # run_id
+            ctypes.c_char_p,  # commit
+            ctypes.c_char_p,  # parent_digest
+            ctypes.c_char_p,  # trained_at
+        ]
+        self.lib.add_provenance.restype = EmptyReturn
+        self.lib.add_exporter.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # name
+            ctypes.c_char_p,  # version
+            ctypes.c_char_p,  # opset
+        ]
+        self.lib.add_exporter.restype = EmptyReturn
+        self.lib.set_metadata.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # key
+            ctypes.c_char_p,  # value as JSON
+        ]
+        self.lib.set_metadata.restype = EmptyReturn
+        self.lib.get_metadata.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # path
+        ]
+        self.lib.get_metadata.restype = StringReturn
+        self.lib.list_metadata.argtypes = [ctypes.c_char_p]
+        self.lib.list_metadata.restype = StringReturn
+        self.lib.remove_metadata.argtypes = [
+            ctypes.c_char_p,  # file_id
+            ctypes.c_char_p,  # key
+        ]
+        self.lib.remove_metadata.restype = EmptyReturn
+
         self.lib.load_model.restype = FileInfo
         self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.load_header.restype = FileInfo
+        self.lib.load_header.argtypes = [ctypes.c_char_p]
+        self.lib.unload_model.restype = EmptyReturn
+        self.lib.unload_model.argtypes = [ctypes.c_char_p]
         self.lib.load_cached_raw_model.restype = StringReturn
         self.lib.load_cached_raw_model.argtypes = [ctypes.c_char_p]
         self.lib.to_bytes.argtypes = [ctypes.c_char_p]
@@ -147,6 +239,13 @@ class LibLoader(metaclass=Singleton):
             ctypes.c_int  # string_count -> c_int
         ]
         self.lib.buffered_compute.restype = Vecf32Return
+        self.lib.buffered_compute_values.argtypes = [
+            ctypes.c_char_p,  # file_id_ptr -> *const c_char
+            ctypes.POINTER(ctypes.c_char_p),  # keys -> *const *const c_char
+            ctypes.POINTER(ctypes.c_char_p),  # values -> *const *const c_char
+            ctypes.c_size_t  # length -> usize
+        ]
+        self.lib.buffered_compute_values.restype = Vecf32Return
 
         # Define free alloc functions
         self.lib.free_string_return.argtypes = [StringReturn]
@@ -2,10 +2,11 @@
 The adapter to interact with the Rust module compiled to a C dynamic library
 """
 import ctypes
+import json
 import platform
 import warnings
 from pathlib import Path
-from typing import List, Tuple
+from typing import Any, List, Tuple
 from typing import Optional
 
 from surrealml.c_structs import EmptyReturn, StringReturn, Vecf32Return, FileInfo, VecU8Return
@@ -18,6 +19,7 @@ class RustAdapter:
     def __init__(self, file_id: str, engine: Engine) -> None:
         self.file_id: str = file_id
         self.engine: Engine = engine
+        self.origin: str = "local"
         self.loader = LibLoader()
 
     @staticmethod
@@ -141,6 +143,274 @@ class RustAdapter:
             raise RuntimeError(outcome.error_message.decode("utf-8"))
         self.loader.lib.free_empty_return(outcome)
 
+    def add_normaliser_step(self, column_name: str, normaliser_type: str, one: float, two: float) -> None:
+        """
+        Appends a normaliser to the pipeline of a column so it is applied after the normalisers already on the column.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param normaliser_type: the type of normaliser to append.
+        :param one: the first parameter of the normaliser.
+        :param two: the second parameter of the normaliser.
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.add_normaliser_step(
+            self.file_id.encode("utf-8"),
+            column_name.encode("utf-8"),
+            normaliser_type.encode("utf-8"),
+            one,
+            two,
+        )
+        self._check_empty_return(outcome)
+
+    def add_output_normaliser_step(self, normaliser_type: str, one: float, two: float) -> None:
+        """
+        Appends a normaliser to the pipeline of the output, the outputs are passed through the inverse of the
+        pipeline in reverse order.
+
+        :param normaliser_type: the type of normaliser to append.
+        :param one: the first parameter of the normaliser.
+        :param two: the second parameter of the normaliser.
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.add_output_normaliser_step(
+            self.file_id.encode("utf-8"),
+            normaliser_type.encode("utf-8"),
+            one,
+            two,
+        )
+        self._check_empty_return(outcome)
+
+    def add_normaliser_definition(self, column_name: str, definition: str) -> None:
+        """
+        Sets the normaliser of a column from its definition where parameters can be named such as
+        "clipping(min=0)" and pipeline steps are separated by "|".
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param definition: the definition of the normaliser such as "robust_scaling(median=3,iqr=2)".
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.add_normaliser_definition(
+            self.file_id.encode("utf-8"),
+            column_name.encode("utf-8"),
+            definition.encode("utf-8"),
+        )
+        self._check_empty_return(outcome)
+
+    def add_output_normaliser_definition(self, definition: str) -> None:
+        """
+        Sets the normaliser of the output from its definition.
+
+        :param definition: the definition of the normaliser such as "yeo_johnson(lambda=0.5)|z_score(1,2)".
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.add_output_normaliser_definition(
+            self.file_id.encode("utf-8"),
+            definition.encode("utf-8"),
+        )
+        self._check_empty_return(outcome)
+
+    def set_metadata(self, key: str, value: Any) -> None:
+        """
+        Sets a metadata value of the model such as a metric, license or tags.
+
+        :param key: the name of the value.
+        :param value: a string, number, bool, list or dict that can be serialised to JSON.
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.set_metadata(
+            self.file_id.encode("utf-8"),
+            key.encode("utf-8"),
+            json.dumps(value, allow_nan=False).encode("utf-8"),
+        )
+        self._check_empty_return(outcome)
+
+    def get_metadata(self, path: str) -> Any:
+        """
+        Gets a metadata value of the model.
+
+        :param path: the key of the value or a dot separated path into nested values such as "metrics.rmse".
+        :return: the value or None if there is no value at the path.
+        """
+        outcome: StringReturn = self.loader.lib.get_metadata(
+            self.file_id.encode("utf-8"),
+            path.encode("utf-8"),
+        )
+        return json.loads(self._take_string_return(outcome))
+
+    def list_metadata(self) -> dict:
+        """
+        Gets all of the metadata of the model.
+
+        :return: the metadata keyed by name.
+        """
+        outcome: StringReturn = self.loader.lib.list_metadata(self.file_id.encode("utf-8"))
+        return json.loads(self._take_string_return(outcome))
+
+    def remove_metadata(self, key: str) -> None:
+        """
+        Removes a metadata value of the model.
+
+        :param key: the name of the value.
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.remove_metadata(
+            self.file_id.encode("utf-8"),
+            key.encode("utf-8"),
+        )
+        self._check_empty_return(outcome)
+
+    def add_one_hot_encoder(self, column_name: str, vocabulary: List[str]) -> None:
+        """
+        Adds a one-hot encoder to a column so a string value is expanded into one column per category.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param vocabulary: the categories in the order of their columns.
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.add_one_hot_encoder(
+            self.file_id.encode("utf-8"),
+            column_name.encode("utf-8"),
+            self._string_array(vocabulary),
+            len(vocabulary),
+        )
+        self._check_empty_return(outcome)
+
+    def add_ordinal_encoder(self, column_name: str, categories: List[str], unknown: Optional[float] = None) -> None:
+        """
+        Adds an ordinal encoder to a column so a string value is mapped to the index of its category.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param categories: the categories in the order of their index.
+        :param unknown: the value for an unknown category, an unknown category raises an error without it.
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.add_ordinal_encoder(
+            self.file_id.encode("utf-8"),
+            column_name.encode("utf-8"),
+            self._string_array(categories),
+            len(categories),
+            None if unknown is None else str(unknown).encode("utf-8"),
+        )
+        self._check_empty_return(outcome)
+
+    def add_hashing_encoder(self, column_name: str, buckets: int) -> None:
+        """
+        Adds a hashing trick encoder to a column so a string value is hashed into one of a number of columns.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param buckets: the number of buckets the values are hashed into.
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.add_hashing_encoder(
+            self.file_id.encode("utf-8"),
+            column_name.encode("utf-8"),
+            buckets,
+        )
+        self._check_empty_return(outcome)
+
+    def add_target_encoder(self, column_name: str, table: dict, default: Optional[float] = None) -> None:
+        """
+        Adds a target encoder to a column so a string value is replaced with its value in a lookup table.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param table: the value of each category.
+        :param default: the value for an unknown category, an unknown category raises an error without it.
+        :return: None
+        """
+        categories = list(table.keys())
+        targets = (ctypes.c_float * len(categories))(*[table[category] for category in categories])
+        outcome: EmptyReturn = self.loader.lib.add_target_encoder(
+            self.file_id.encode("utf-8"),
+            column_name.encode("utf-8"),
+            self._string_array(categories),
+            targets,
+            len(categories),
+            None if default is None else str(default).encode("utf-8"),
+        )
+        self._check_empty_return(outcome)
+
+    @staticmethod
+    def _string_array(strings: List[str]):
+        """
+        Converts a list of strings into a C array of strings.
+
+        :param strings: the strings to convert.
+        :return: the C array.
+        """
+        return (ctypes.c_char_p * len(strings))(*[string.encode("utf-8") for string in strings])
+
+    def _take_string_return(self, outcome: StringReturn) -> str:
+        """
+        Raises the error of an outcome if there is one, otherwise frees the outcome and returns its string.
+
+        :param outcome: the outcome returned from the C lib.
+        :return: the string of the outcome.
+        """
+        if outcome.is_error == 1:
+            raise RuntimeError(outcome.error_message.decode("utf-8"))
+        string = outcome.string.decode("utf-8")
+        self.loader.lib.free_string_return(outcome)
+        return string
+
+    def _check_empty_return(self, outcome: EmptyReturn) -> None:
+        """
+        Raises the error of an outcome if there is one and frees the outcome.
+
+        :param outcome: the outcome returned from the C lib.
+        :return: None
+        """
+        if outcome.is_error == 1:
+            raise RuntimeError(outcome.error_message.decode("utf-8"))
+        self.loader.lib.free_empty_return(outcome)
+
+    def add_provenance(
+            self,
+            source: Optional[str] = None,
+            run_id: Optional[str] = None,
+            commit: Optional[str] = None,
+            parent_digest: Optional[str] = None,
+            trained_at: Optional[str] = None,
+    ) -> None:
+        """
+        Records how the model was trained so it can be linked to the models it was trained from.
+
+        :param source: the URI of the system the model was trained in such as "mlflow://runs/42".
+        :param run_id: the identifier of the training run.
+        :param commit: the git commit of the training code.
+        :param parent_digest: the hex encoded SHA-256 digest of the model this model was trained from.
+        :param trained_at: when the model was trained as an RFC 3339 timestamp.
+        :return: None
+        """
+        if source is not None:
+            outcome: EmptyReturn = self.loader.lib.add_origin(
+                self.file_id.encode("utf-8"),
+                source.encode("utf-8"),
+            )
+            self._check_empty_return(outcome)
+            self.origin = source
+        outcome: EmptyReturn = self.loader.lib.add_provenance(
+            self.file_id.encode("utf-8"),
+            *[None if value is None else value.encode("utf-8") for value in (run_id, commit, parent_digest, trained_at)],
+        )
+        self._check_empty_return(outcome)
+
+    def add_exporter(self, name: str, version: Optional[str] = None, opset: Optional[int] = None) -> None:
+        """
+        Records the tool that exported the model to ONNX.
+
+        :param name: the name of the exporter such as "skl2onnx".
+        :param version: the version of the exporter.
+        :param opset: the ONNX opset the model was exported with.
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.add_exporter(
+            self.file_id.encode("utf-8"),
+            name.encode("utf-8"),
+            None if version is None else version.encode("utf-8"),
+            None if opset is None else str(opset).encode("utf-8"),
+        )
+        self._check_empty_return(outcome)
+
     def add_author(self, author: str) -> None:
         """
         Adds an author to the model to the metadata.
@@ -174,7 +444,7 @@ class RustAdapter:
         self.loader.lib.free_empty_return(outcome)
         outcome: EmptyReturn = self.loader.lib.add_origin(
             self.file_id.encode("utf-8"),
-            "local".encode("utf-8"),
+            self.origin.encode("utf-8"),
         )
         if outcome.is_error == 1:
             raise RuntimeError(outcome.error_message.decode("utf-8"))
@@ -237,6 +507,43 @@ class RustAdapter:
         loader.lib.free_file_info(outcome)
         return package
 
+    @staticmethod
+    def load_header(path) -> Tuple[str, str, str, str]:
+        """
+        Loads only the header of a model from a file without reading the model bytes.
+
+        :param path: the path to load the header from.
+        :return: the id of the header being loaded with the name, description and version of the model.
+        """
+        loader = LibLoader()
+        outcome: FileInfo = loader.lib.load_header(
+            path.encode("utf-8"),
+        )
+        if outcome.is_error == 1:
+            raise RuntimeError(outcome.error_message.decode("utf-8"))
+        package = (
+            outcome.file_id.decode("utf-8"),
+            outcome.name.decode("utf-8"),
+            outcome.description.decode("utf-8"),
+            outcome.version.decode("utf-8"),
+        )
+        loader.lib.free_file_info(outcome)
+        return package
+
+    def unload(self) -> None:
+        """
+        Removes the loaded model or header from the rust library so its memory is freed. The file id cannot be used
+        after this call.
+
+        :return: None
+        """
+        outcome: EmptyReturn = self.loader.lib.unload_model(
+            self.file_id.encode("utf-8"),
+        )
+        if outcome.is_error == 1:
+            raise RuntimeError(outcome.error_message.decode("utf-8"))
+        self.loader.lib.free_empty_return(outcome)
+
     @staticmethod
     def upload(
             path: str,
@@ -282,6 +589,16 @@ class RustAdapter:
         :param dims: the dimensions of the input vector to be sliced into
         :return: the output of the model.
         """
+        return self.raw_compute_with_shape(input_vector, dims)[0]
+
+    def raw_compute_with_shape(self, input_vector, dims=None) -> Tuple[List[float], List[int]]:
+        """
+        Calculates an output from the model given an input vector keeping the shape of the output.
+
+        :param input_vector: a 1D vector of inputs to the model.
+        :param dims: the dimensions of the input vector to be sliced into
+        :return: the flattened output of the model and the shape to rebuild it with.
+        """
         array_type = ctypes.c_float * len(input_vector)
         input_data = array_type(*input_vector)
         outcome: Vecf32Return = self.loader.lib.raw_compute(
@@ -289,11 +606,7 @@ class RustAdapter:
             input_data,
             len(input_data),
         )
-        if outcome.is_error == 1:
-            raise RuntimeError(outcome.error_message.decode("utf-8"))
-        package = [outcome.data[i] for i in range(outcome.length)]
-        self.loader.lib.free_vecf32_return(outcome)
-        return package
+        return self._unpack_vec_f32(outcome)
 
     def buffered_compute(self, value_map: dict) -> List[float]:
         """
@@ -302,6 +615,15 @@ class RustAdapter:
         :param value_map: a dictionary of inputs to the model with the column names as keys and floats as values.
         :return: the output of the model.
         """
+        return self.buffered_compute_with_shape(value_map)[0]
+
+    def buffered_compute_with_shape(self, value_map: dict) -> Tuple[List[float], List[int]]:
+        """
+        Calculates an output from the model given a value map keeping the shape of the output.
+
+        :param value_map: a dictionary of inputs to the model with the column names as keys and floats as values.
+        :return: the flattened output of the model and the shape to rebuild it with.
+        """
         string_buffer = []
         data_buffer = []
         for key, value in value_map.items():
@@ -323,8 +645,37 @@ class RustAdapter:
             string_array,
             string_count
         )
+        return self._unpack_vec_f32(outcome)
+
+    def buffered_compute_values(self, value_map: dict) -> List[float]:
+        """
+        Calculates an output from the model given a value map where the values can be strings for encoded and
+        categorical columns, booleans, or numbers.
+
+        :param value_map: a dictionary of inputs to the model with the column names as keys.
+        :return: the output of the model.
+        """
+        keys = list(value_map.keys())
+        values = [str(value_map[key]).lower() if isinstance(value_map[key], bool) else str(value_map[key])
+                  for key in keys]
+        outcome = self.loader.lib.buffered_compute_values(
+            self.file_id.encode("utf-8"),
+            self._string_array(keys),
+            self._string_array(values),
+            len(keys),
+        )
+        return self._unpack_vec_f32(outcome)[0]
+
+    def _unpack_vec_f32(self, outcome: Vecf32Return) -> Tuple[List[float], List[int]]:
+        """
+        Copies the data and shape out of a computation outcome and frees the outcome.
+
+        :param outcome: the outcome returned from the C lib.
+        :return: the flattened output of the model and its shape.
+        """
         if outcome.is_error == 1:
             raise RuntimeError(outcome.error_message.decode("utf-8"))
-        return_data = [outcome.data[i] for i in range(outcome.length)]
+        data = [outcome.data[i] for i in range(outcome.length)]
+        shape = [outcome.shape[i] for i in range(outcome.shape_length)]
         self.loader.lib.free_vecf32_return(outcome)
-        return return_data
+        return data, shape
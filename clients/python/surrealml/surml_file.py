@@ -127,6 +127,152 @@ class SurMlFile:
         """
         self.rust_adapter.add_normaliser(column_name, normaliser_type, one, two)
 
+    def add_normaliser_step(self, column_name, normaliser_type, one, two):
+        """
+        Appends a normaliser to the pipeline of a column so it is applied after the normalisers already on the column.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param normaliser_type: the type of normaliser to append.
+        :param one: the first parameter of the normaliser.
+        :param two: the second parameter of the normaliser.
+        :return: None
+        """
+        self.rust_adapter.add_normaliser_step(column_name, normaliser_type, one, two)
+
+    def add_output_normaliser_step(self, normaliser_type, one, two):
+        """
+        Appends a normaliser to the pipeline of the output, the outputs are passed through the inverse of the
+        pipeline in reverse order.
+
+        :param normaliser_type: the type of normaliser to append.
+        :param one: the first parameter of the normaliser.
+        :param two: the second parameter of the normaliser.
+        :return: None
+        """
+        self.rust_adapter.add_output_normaliser_step(normaliser_type, one, two)
+
+    def add_normaliser_definition(self, column_name, definition):
+        """
+        Sets the normaliser of a column from its definition where parameters can be named such as
+        "clipping(min=0)" and pipeline steps are separated by "|".
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param definition: the definition of the normaliser such as "robust_scaling(median=3,iqr=2)".
+        :return: None
+        """
+        self.rust_adapter.add_normaliser_definition(column_name, definition)
+
+    def add_output_normaliser_definition(self, definition):
+        """
+        Sets the normaliser of the output from its definition.
+
+        :param definition: the definition of the normaliser such as "yeo_johnson(lambda=0.5)|z_score(1,2)".
+        :return: None
+        """
+        self.rust_adapter.add_output_normaliser_definition(definition)
+
+    def set_metadata(self, key, value):
+        """
+        Sets a metadata value of the model such as a metric, license or tags.
+
+        :param key: the name of the value.
+        :param value: a string, number, bool, list or dict that can be serialised to JSON.
+        :return: None
+        """
+        self.rust_adapter.set_metadata(key, value)
+
+    def get_metadata(self, path):
+        """
+        Gets a metadata value of the model.
+
+        :param path: the key of the value or a dot separated path into nested values such as "metrics.rmse".
+        :return: the value or None if there is no value at the path.
+        """
+        return self.rust_adapter.get_metadata(path)
+
+    def list_metadata(self):
+        """
+        Gets all of the metadata of the model.
+
+        :return: the metadata keyed by name.
+        """
+        return self.rust_adapter.list_metadata()
+
+    def remove_metadata(self, key):
+        """
+        Removes a metadata value of the model.
+
+        :param key: the name of the value.
+        :return: None
+        """
+        self.rust_adapter.remove_metadata(key)
+
+    def add_one_hot_encoder(self, column_name, vocabulary):
+        """
+        Adds a one-hot encoder to a column so a string value is expanded into one column per category.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param vocabulary: the categories in the order of their columns.
+        :return: None
+        """
+        self.rust_adapter.add_one_hot_encoder(column_name, vocabulary)
+
+    def add_ordinal_encoder(self, column_name, categories, unknown=None):
+        """
+        Adds an ordinal encoder to a column so a string value is mapped to the index of its category.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param categories: the categories in the order of their index.
+        :param unknown: the value for an unknown category, an unknown category raises an error without it.
+        :return: None
+        """
+        self.rust_adapter.add_ordinal_encoder(column_name, categories, unknown)
+
+    def add_hashing_encoder(self, column_name, buckets):
+        """
+        Adds a hashing trick encoder to a column so a string value is hashed into one of a number of columns.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param buckets: the number of buckets the values are hashed into.
+        :return: None
+        """
+        self.rust_adapter.add_hashing_encoder(column_name, buckets)
+
+    def add_target_encoder(self, column_name, table, default=None):
+        """
+        Adds a target encoder to a column so a string value is replaced with its value in a lookup table.
+
+        :param column_name: the name of the column (column already needs to be in the metadata).
+        :param table: a dictionary of the value of each category.
+        :param default: the value for an unknown category, an unknown category raises an error without it.
+        :return: None
+        """
+        self.rust_adapter.add_target_encoder(column_name, table, default)
+
+    def add_provenance(self, source=None, run_id=None, commit=None, parent_digest=None, trained_at=None):
+        """
+        Records how the model was trained so it can be linked to the models it was trained from.
+
+        :param source: the URI of the system the model was trained in such as "mlflow://runs/42".
+        :param run_id: the identifier of the training run.
+        :param commit: the git commit of the training code.
+        :param parent_digest: the hex encoded SHA-256 digest of the model this model was trained from.
+        :param trained_at: when the model was trained as an RFC 3339 timestamp.
+        :return: None
+        """
+        self.rust_adapter.add_provenance(source, run_id, commit, parent_digest, trained_at)
+
+    def add_exporter(self, name, version=None, opset=None):
+        """
+        Records the tool that exported the model to ONNX.
+
+        :param name: the name of the exporter such as "skl2onnx".
+        :param version: the version of the exporter.
+        :param opset: the ONNX opset the model was exported with.
+        :return: None
+        """
+        self.rust_adapter.add_exporter(name, version, opset)
+
     def add_author(self, author):
         """
         Adds an author to the model to the metadata.
@@ -170,6 +316,36 @@ class SurMlFile:
         self.engine = engine
         self.rust_adapter = RustAdapter(self.file_id, self.engine)
         return self
+
+    @staticmethod
+    def load_header(path):
+        """
+        Loads only the header of a model from a file so the metadata of many models can be listed cheaply. The
+        model bytes are not read so the loaded file cannot be used for compute operations. Call `unload`, or use the
+        returned file in a `with` block, once the header is no longer needed so the rust library frees it.
+
+        :param path: the path to load the header from.
+
+        :return: The SurMlFile with the loaded header.
+        """
+        self = SurMlFile()
+        self.file_id, self.name, self.description, self.version = RustAdapter.load_header(path)
+        self.rust_adapter = RustAdapter(self.file_id, self.engine)
+        return self
+
+    def unload(self):
+        """
+        Frees the loaded model or header in the rust library. The file cannot be used after this call.
+
+        :return: None
+        """
+        self.rust_adapter.unload()
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.unload()
     
     @staticmethod
     def upload(
@@ -214,6 +390,16 @@ class SurMlFile:
         """
         return self.rust_adapter.raw_compute(input_vector, dims)
 
+    def raw_compute_with_shape(self, input_vector, dims=None):
+        """
+        Calculates an output from the model given an input vector keeping the shape of the output.
+
+        :param input_vector: a 1D vector of inputs to the model.
+        :param dims: the dimensions of the input vector to be sliced into
+        :return: the flattened output of the model and the shape to rebuild it with.
+        """
+        return self.rust_adapter.raw_compute_with_shape(input_vector, dims)
+
     def buffered_compute(self, value_map):
         """
         Calculates an output from the model given a value map.
@@ -222,3 +408,22 @@ class SurMlFile:
         :return: the output of the model.
         """
         return self.rust_adapter.buffered_compute(value_map)
+
+    def buffered_compute_values(self, value_map):
+        """
+        Calculates an output from the model given a value map where the values can be strings for encoded and
+        categorical columns, booleans, or numbers.
+
+        :param value_map: a dictionary of inputs to the model with the column names as keys.
+        :return: the output of the model.
+        """
+        return self.rust_adapter.buffered_compute_values(value_map)
+
+    def buffered_compute_with_shape(self, value_map):
+        """
+        Calculates an output from the model given a value map keeping the shape of the output.
+
+        :param value_map: a dictionary of inputs to the model with the column names as keys and floats as values.
+        :return: the flattened output of the model and the shape to rebuild it with.
+        """
+        return self.rust_adapter.buffered_compute_with_shape(value_map)
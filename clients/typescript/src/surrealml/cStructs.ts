@@ -67,13 +67,17 @@ const FileInfo = koffi.struct('FileInfo', {
  *     capacity: the capacity of the array
  *     is_error: 1 if error, 0 if not
  *     error_message: the error message (only present if error)
+ *     shape: pointer to the shape of the output (only present if successful)
+ *     shape_length: the number of dimensions in the shape
  */
 const Vecf32Return = koffi.struct('Vecf32Return', {
 	data: FloatPtr,
 	length: 'size_t',
 	capacity: 'size_t',
 	is_error: 'int',
-	error_message: HeapStr
+	error_message: HeapStr,
+	shape: 'size_t *',
+	shape_length: 'size_t'
 });
 
 /**
@@ -131,6 +135,8 @@ export interface StringReturnType {
 	capacity: number;
 	is_error: number;
 	error_message: string | null;
+	shape: unknown;
+	shape_length: number;
   }
   
   export interface VecU8ReturnType {
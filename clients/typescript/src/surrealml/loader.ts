@@ -88,6 +88,8 @@ export class LibLoader {
 		);
 
 		this.lib.load_model = this.lib.func('load_model', FileInfo, ['char *']);
+		this.lib.load_header = this.lib.func('load_header', FileInfo, ['char *']);
+		this.lib.unload_model = this.lib.func('unload_model', EmptyReturn, ['char *']);
 		this.lib.load_cached_raw_model = this.lib.func('load_cached_raw_model', StringReturn, ['char *']);
 		this.lib.to_bytes = this.lib.func('to_bytes', VecU8Return, ['char *']);
 		this.lib.save_model = this.lib.func('save_model', EmptyReturn, ['char *', 'char *']);
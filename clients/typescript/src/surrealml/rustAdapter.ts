@@ -5,6 +5,13 @@ import { LibLoader } from './loader.js';
 import { Buffer } from 'buffer';
 import koffi from 'koffi';
 
+/**
+ * The flattened output of a computation with the shape to rebuild it with.
+ */
+export interface ShapedOutput {
+	data: number[];
+	shape: number[];
+}
 
 /**
  * The adapter to interact with the Rust module compiled to a C dynamic library
@@ -62,6 +69,34 @@ export class RustAdapter {
 		return ret;
 	}
 
+	/**
+	 * Loads only the header of a model from a file without reading the model bytes.
+	 *
+	 * path: the path to load the header from.
+	 *
+	 * returns: [fileId, name, description, version]
+	 */
+	static loadHeader(path: string): [string, string, string, string] {
+		const loader = LibLoader.getInstance();
+		const out = loader.lib.load_header(path) as FileInfoType;
+		if (out.is_error == 1) throw new Error(out.error_message || 'Unknown error whilst loading header.');
+
+		return [out.file_id, out.name, out.description, out.version];
+	}
+
+	/**
+	 * Removes the loaded model or header from the Rust library so its memory is freed. The file id cannot be used
+	 * after this call.
+	 *
+	 * returns: none
+	 */
+	unload(): void {
+		const out = this.loader.lib.unload_model(this.fileId) as EmptyReturnType;
+		if (out.is_error === 1) {
+			throw new Error(out.error_message || 'Unknown error whilst unloading model');
+		}
+	}
+
 	/**
 	 * Uploads a model to a remote server.
 	 *
@@ -308,11 +343,21 @@ export class RustAdapter {
 	 * Calculates an output from the model given an input vector.
 	 *
 	 * inputVector: a 1D vector of inputs to the model.
-	 * dims: the dimensions of the input vector to be sliced into.
 	 *
 	 * returns: the output of the model.
 	 */
 	rawCompute(inputVector: number[]): number[] {
+		return this.rawComputeWithShape(inputVector).data;
+	}
+
+	/**
+	 * Calculates an output from the model given an input vector keeping the shape of the output.
+	 *
+	 * inputVector: a 1D vector of inputs to the model.
+	 *
+	 * returns: the flattened output of the model and the shape to rebuild it with.
+	 */
+	rawComputeWithShape(inputVector: number[]): ShapedOutput {
 		const out = this.loader.lib.raw_compute(
 			this.fileId,
 			new Float32Array(inputVector),
@@ -322,13 +367,7 @@ export class RustAdapter {
 		if (out.is_error === 1) {
 			throw new Error(out.error_message || 'Unknown error whilst computing model.');
 		}
-	
-		// length in bytes = number of floats * 4
-		const byteLen = out.length * Float32Array.BYTES_PER_ELEMENT; 
-		const buf = koffi.view(out.data, byteLen);              // now an ArrayBuffer of N*4 bytes
-		const floats = new Float32Array(buf);                      // a Float32Array of length `out.length`
-
-		return Array.from(floats);                                 // [f0, f1, …]
+		return RustAdapter.unpackVecf32(out);
 	}
 
 	/**
@@ -339,6 +378,17 @@ export class RustAdapter {
 	 * returns: the output of the model.
 	 */
 	bufferedCompute(valueMap: Record<string, number>): number[] {
+		return this.bufferedComputeWithShape(valueMap).data;
+	}
+
+	/**
+	 * Calculates an output from the model given a value map keeping the shape of the output.
+	 *
+	 * valueMap: a dictionary of inputs to the model with the column names as keys and floats as values.
+	 *
+	 * returns: the flattened output of the model and the shape to rebuild it with.
+	 */
+	bufferedComputeWithShape(valueMap: Record<string, number>): ShapedOutput {
 		const keys = Object.keys(valueMap);
 		const values = keys.map(k => {
 			const v = valueMap[k];
@@ -359,12 +409,24 @@ export class RustAdapter {
 		if (out.is_error === 1) {
 		  	throw new Error(out.error_message ?? 'buffered_compute failed');
 		};
-	  
+		return RustAdapter.unpackVecf32(out);
+	}
+
+	/**
+	 * Copies the data and shape out of a computation outcome.
+	 *
+	 * out: the outcome returned from the C lib.
+	 *
+	 * returns: the flattened output of the model and its shape.
+	 */
+	private static unpackVecf32(out: Vecf32ReturnType): ShapedOutput {
 		// length in bytes = number of floats * 4
 		const byteLen = out.length * Float32Array.BYTES_PER_ELEMENT; 
-		const buf = koffi.view(out.data, byteLen);             
-		const floats = new Float32Array(buf);                      
-
-		return Array.from(floats);                               
-	}	  
+		const buf = koffi.view(out.data, byteLen);              // now an ArrayBuffer of N*4 bytes
+		const floats = new Float32Array(buf);                      // a Float32Array of length `out.length`
+		const shape = out.shape_length > 0
+			? (koffi.decode(out.shape, 'size_t', out.shape_length) as number[]).map(Number)
+			: [];
+		return { data: Array.from(floats), shape };
+	}
 }
\ No newline at end of file
@@ -5,6 +5,7 @@
 import { Engine } from './engine/engine.js';
 import { OnnxAdapter } from './engine/onnx.js';
 import { RustAdapter } from './rustAdapter.js';
+import type { ShapedOutput } from './rustAdapter.js';
 
 export class SurMlFile {
 	model: any;
@@ -175,6 +176,33 @@ export class SurMlFile {
 		return instance;
 	}
 
+	/**
+	 * Loads only the header of a model from a file so the metadata of many models can be listed cheaply. The model
+	 * bytes are not read so the loaded file cannot be used for compute operations. Call `unload` once the header is
+	 * no longer needed so the Rust library frees it.
+	 *
+	 * path: the path to load the header from
+	 *
+	 * returns: a new SurMlFile with the loaded header
+	 */
+	static loadHeader(path: string): SurMlFile {
+		const instance = new SurMlFile();
+		const [fileId, name, description, version] = RustAdapter.loadHeader(path);
+		instance.fileId = fileId;
+		instance.name = name;
+		instance.description = description;
+		instance.version = version;
+		instance.rustAdapter = new RustAdapter(fileId, instance.engine!);
+		return instance;
+	}
+
+	/**
+	 * Frees the loaded model or header in the Rust library. The file cannot be used after this call.
+	 */
+	unload(): void {
+		this.rustAdapter.unload();
+	}
+
 	/**
 	 * Uploads a model to a remote server.
 	 *
@@ -219,4 +247,26 @@ export class SurMlFile {
 	bufferedCompute(valueMap: Record<string, number>): number[] {
 		return this.rustAdapter.bufferedCompute(valueMap);
 	}
+
+	/**
+	 * Calculates an output from the model given an input vector keeping the shape of the output.
+	 *
+	 * inputVector: a 1D vector of inputs to the model
+	 *
+	 * returns: the flattened output of the model and the shape to rebuild it with
+	 */
+	rawComputeWithShape(inputVector: number[]): ShapedOutput {
+		return this.rustAdapter.rawComputeWithShape(inputVector);
+	}
+
+	/**
+	 * Calculates an output from the model given a value map keeping the shape of the output.
+	 *
+	 * valueMap: a dictionary of inputs to the model with the column names as keys and floats as values
+	 *
+	 * returns: the flattened output of the model and the shape to rebuild it with
+	 */
+	bufferedComputeWithShape(valueMap: Record<string, number>): ShapedOutput {
+		return this.rustAdapter.bufferedComputeWithShape(valueMap);
+	}
 }
@@ -1,8 +1,9 @@
 //! This module contains the buffered_compute function that is called from the C API to compute the model.
+use crate::api::execution::compiled_model;
 use crate::state::STATE;
-use crate::utils::Vecf32Return;
+use crate::utils::{strings_from_array, Vecf32Return};
 use std::collections::HashMap;
-use std::ffi::{c_char, c_float, c_int, CStr, CString};
+use std::ffi::{c_char, c_float, c_int, CStr};
 use surrealml_core::execution::compute::ModelComputation;
 
 /// Computes the model with the given data.
@@ -15,7 +16,7 @@ use surrealml_core::execution::compute::ModelComputation;
 /// * `string_count` - The number of strings in the array.
 ///
 /// # Returns
-/// A Vecf32Return object containing the outcome of the computation.
+/// A Vecf32Return object containing the outcome of the computation and its shape.
 #[no_mangle]
 pub extern "C" fn buffered_compute(
     file_id_ptr: *const c_char,
@@ -25,47 +26,19 @@ pub extern "C" fn buffered_compute(
     string_count: c_int,
 ) -> Vecf32Return {
     if file_id_ptr.is_null() {
-        return Vecf32Return {
-            data: std::ptr::null_mut(),
-            length: 0,
-            capacity: 0,
-            is_error: 1,
-            error_message: CString::new("File id is null").unwrap().into_raw(),
-        };
+        return Vecf32Return::error("File id is null".to_string());
     }
     if data_ptr.is_null() {
-        return Vecf32Return {
-            data: std::ptr::null_mut(),
-            length: 0,
-            capacity: 0,
-            is_error: 1,
-            error_message: CString::new("Data is null").unwrap().into_raw(),
-        };
+        return Vecf32Return::error("Data is null".to_string());
     }
 
     let file_id = match unsafe { CStr::from_ptr(file_id_ptr) }.to_str() {
         Ok(file_id) => file_id.to_owned(),
-        Err(error) => {
-            return Vecf32Return {
-                data: std::ptr::null_mut(),
-                length: 0,
-                capacity: 0,
-                is_error: 1,
-                error_message: CString::new(format!("Error getting file id: {}", error))
-                    .unwrap()
-                    .into_raw(),
-            }
-        }
+        Err(error) => return Vecf32Return::error(format!("Error getting file id: {}", error)),
     };
 
     if strings.is_null() {
-        return Vecf32Return {
-            data: std::ptr::null_mut(),
-            length: 0,
-            capacity: 0,
-            is_error: 1,
-            error_message: CString::new("string pointer is null").unwrap().into_raw(),
-        };
+        return Vecf32Return::error("string pointer is null".to_string());
     }
 
     // extract the list of strings from the C array
@@ -83,30 +56,14 @@ pub extern "C" fn buffered_compute(
         .collect();
     for i in rust_strings.iter() {
         if i.is_empty() {
-            return Vecf32Return {
-                data: std::ptr::null_mut(),
-                length: 0,
-                capacity: 0,
-                is_error: 1,
-                error_message: CString::new("null string passed in as key")
-                    .unwrap()
-                    .into_raw(),
-            };
+            return Vecf32Return::error("null string passed in as key".to_string());
         }
     }
 
     let data_slice = unsafe { std::slice::from_raw_parts(data_ptr, data_length) };
 
     if rust_strings.len() != data_slice.len() {
-        return Vecf32Return {
-            data: std::ptr::null_mut(),
-            length: 0,
-            capacity: 0,
-            is_error: 1,
-            error_message: CString::new("String count does not match data length")
-                .unwrap()
-                .into_raw(),
-        };
+        return Vecf32Return::error("String count does not match data length".to_string());
     }
 
     // stitch the strings and data together
@@ -117,59 +74,81 @@ pub extern "C" fn buffered_compute(
 
     let mut state = match STATE.lock() {
         Ok(state) => state,
-        Err(error) => {
-            return Vecf32Return {
-                data: std::ptr::null_mut(),
-                length: 0,
-                capacity: 0,
-                is_error: 1,
-                error_message: CString::new(format!("Error getting state: {}", error))
-                    .unwrap()
-                    .into_raw(),
-            }
-        }
+        Err(error) => return Vecf32Return::error(format!("Error getting state: {}", error)),
     };
     let file = match state.get_mut(&file_id) {
         Some(file) => file,
         None => {
-            return Vecf32Return {
-                data: std::ptr::null_mut(),
-                length: 0,
-                capacity: 0,
-                is_error: 1,
-                error_message: CString::new(format!(
-                    "File not found for id: {}, here is the state: {:?}",
-                    file_id,
-                    state.keys()
-                ))
-                .unwrap()
-                .into_raw(),
-            }
+            return Vecf32Return::error(format!(
+                "File not found for id: {}, here is the state: {:?}",
+                file_id,
+                state.keys()
+            ))
         }
     };
-    let compute_unit = ModelComputation { surml_file: file };
-    match compute_unit.buffered_compute(&mut input_map) {
-        Ok(mut output) => {
-            let output_len = output.len();
-            let output_capacity = output.capacity();
-            let output_ptr = output.as_mut_ptr();
-            std::mem::forget(output);
-            Vecf32Return {
-                data: output_ptr,
-                length: output_len,
-                capacity: output_capacity,
-                is_error: 0,
-                error_message: std::ptr::null_mut(),
-            }
-        }
-        Err(error) => Vecf32Return {
-            data: std::ptr::null_mut(),
-            length: 0,
-            capacity: 0,
-            is_error: 1,
-            error_message: CString::new(format!("Error computing model: {}", error))
-                .unwrap()
-                .into_raw(),
-        },
+    let compiled = match compiled_model(&file_id, file) {
+        Ok(compiled) => compiled,
+        Err(error) => return Vecf32Return::error(format!("Error computing model: {}", error)),
+    };
+    let compute_unit = ModelComputation::from_compiled(&compiled);
+    match compute_unit.buffered_compute_shaped(&mut input_map) {
+        Ok(output) => Vecf32Return::success(output),
+        Err(error) => Vecf32Return::error(format!("Error computing model: {}", error)),
+    }
+}
+
+/// Computes the model with input values passed as strings so encoded and typed columns such as categories and
+/// timestamps can be passed. Each value is parsed by the header as the type of its column.
+///
+/// # Arguments
+/// * `file_id_ptr` - A pointer to the unique identifier for the loaded model.
+/// * `keys` - A pointer to an array of strings to use as keys for the values.
+/// * `values` - A pointer to an array of the values as strings.
+/// * `length` - The number of keys and values.
+///
+/// # Returns
+/// A Vecf32Return object containing the outcome of the computation.
+#[no_mangle]
+pub extern "C" fn buffered_compute_values(
+    file_id_ptr: *const c_char,
+    keys: *const *const c_char,
+    values: *const *const c_char,
+    length: usize,
+) -> Vecf32Return {
+    if file_id_ptr.is_null() {
+        return Vecf32Return::error("File id is null".to_string());
+    }
+    let file_id = unsafe { CStr::from_ptr(file_id_ptr) }
+        .to_string_lossy()
+        .into_owned();
+    let keys = match strings_from_array(keys, length, "keys") {
+        Ok(keys) => keys,
+        Err(error) => return Vecf32Return::error(error),
+    };
+    let values = match strings_from_array(values, length, "values") {
+        Ok(values) => values,
+        Err(error) => return Vecf32Return::error(error),
+    };
+
+    let mut state = STATE.lock().unwrap();
+    let file = match state.get_mut(&file_id) {
+        Some(file) => file,
+        None => return Vecf32Return::error(format!("File not found for id: {}", file_id)),
+    };
+    let mut input_map = HashMap::new();
+    for (key, value) in keys.into_iter().zip(values) {
+        match file.header.parse_value(&key, &value) {
+            Ok(value) => input_map.insert(key, value),
+            Err(error) => return Vecf32Return::error(error.to_string()),
+        };
+    }
+    let compiled = match compiled_model(&file_id, file) {
+        Ok(compiled) => compiled,
+        Err(error) => return Vecf32Return::error(format!("Error computing model: {}", error)),
+    };
+    let compute_unit = ModelComputation::from_compiled(&compiled);
+    match compute_unit.buffered_compute_values(&input_map) {
+        Ok(output) => Vecf32Return::success(ndarray::arr1(&output).into_dyn()),
+        Err(error) => Vecf32Return::error(format!("Error computing model: {}", error)),
     }
 }
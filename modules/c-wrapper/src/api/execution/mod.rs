@@ -1,3 +1,42 @@
 //! The C API for executing ML models.
 pub mod buffered_compute;
 pub mod raw_compute;
+
+// Standard library imports
+use std::sync::Arc;
+
+// External crate imports
+use surrealml_core::errors::error::SurrealError;
+use surrealml_core::execution::compiled::CompiledModel;
+use surrealml_core::storage::surml_file::SurMlFile;
+
+// Local module imports
+use crate::state::{check_model_loaded, COMPILED, TRUSTED_KEYS};
+
+/// Gets the compiled model of a loaded model for computation, checking its signature first if a signature is
+/// required for it. The session is built on the first computation and reused until the model is changed or unloaded.
+/// Files loaded with `load_header` are refused as their model was never read.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the loaded model.
+/// * `file` - The loaded model.
+///
+/// # Returns
+/// The compiled model, an error with a `BadRequest` status if only the header was loaded, or an error with an
+/// `Unauthorized` status if a required signature is missing or not valid.
+pub(crate) fn compiled_model(
+    file_id: &str,
+    file: &SurMlFile,
+) -> Result<Arc<CompiledModel>, SurrealError> {
+    check_model_loaded(file_id)?;
+    if let Some(trusted_keys) = TRUSTED_KEYS.lock().unwrap().get(file_id) {
+        file.verify(trusted_keys)?;
+    }
+    let mut compiled = COMPILED.lock().unwrap();
+    if let Some(model) = compiled.get(file_id) {
+        return Ok(model.clone());
+    }
+    let model = Arc::new(CompiledModel::from_file(file)?);
+    compiled.insert(file_id.to_string(), model.clone());
+    Ok(model)
+}
@@ -0,0 +1,175 @@
+//! Defines the C API interface for adding encoders that expand string columns of a SurML file into numeric columns.
+// Standard library imports
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_float};
+
+// External crate imports
+use surrealml_core::storage::header::encoders::{
+    hashing::Hashing, one_hot::OneHot, ordinal::Ordinal, target::TargetEncoding,
+    wrapper::EncoderType,
+};
+
+// Local module imports
+use crate::state::{invalidate_compiled, STATE};
+use crate::utils::{strings_from_array, EmptyReturn};
+use crate::{empty_return_safe_eject, process_string_for_empty_return};
+
+/// Adds an encoder to a column of a SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column which must already have been added.
+/// * `encoder` - The encoder to be applied to the column.
+fn add_encoder(file_id: String, column_name: String, encoder: EncoderType) -> EmptyReturn {
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(wrapped_file.header.add_encoder(column_name, encoder));
+    EmptyReturn::success()
+}
+
+/// Parses an optional number passed as a string.
+///
+/// # Arguments
+/// * `value` - The pointer to the number which can be null.
+/// * `var_name` - The name of the number to include in the error message.
+///
+/// # Returns
+/// The number if one is passed, or an error message if it is not a number.
+fn optional_number(value: *const c_char, var_name: &str) -> Result<Option<f32>, String> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    let value = unsafe { CStr::from_ptr(value) }.to_string_lossy();
+    match value.parse::<f32>() {
+        Ok(number) => Ok(Some(number)),
+        Err(_) => Err(format!("Invalid number for {}: {}", var_name, value)),
+    }
+}
+
+/// Adds a one-hot encoder to a column of the SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column which must already have been added.
+/// * `vocabulary` - The categories in the order of their columns.
+/// * `vocabulary_length` - The number of categories.
+#[no_mangle]
+pub extern "C" fn add_one_hot_encoder(
+    file_id: *const c_char,
+    column_name: *const c_char,
+    vocabulary: *const *const c_char,
+    vocabulary_length: usize,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let column_name = process_string_for_empty_return!(column_name, "column name");
+    let vocabulary = empty_return_safe_eject!(strings_from_array(
+        vocabulary,
+        vocabulary_length,
+        "vocabulary"
+    ));
+    add_encoder(
+        file_id,
+        column_name,
+        EncoderType::OneHot(OneHot { vocabulary }),
+    )
+}
+
+/// Adds an ordinal encoder to a column of the SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column which must already have been added.
+/// * `categories` - The categories in the order of their index.
+/// * `categories_length` - The number of categories.
+/// * `unknown` (Optional) - The value for an unknown category. An unknown category is an error without it.
+#[no_mangle]
+pub extern "C" fn add_ordinal_encoder(
+    file_id: *const c_char,
+    column_name: *const c_char,
+    categories: *const *const c_char,
+    categories_length: usize,
+    unknown: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let column_name = process_string_for_empty_return!(column_name, "column name");
+    let categories = empty_return_safe_eject!(strings_from_array(
+        categories,
+        categories_length,
+        "categories"
+    ));
+    let unknown = empty_return_safe_eject!(optional_number(unknown, "unknown"));
+    add_encoder(
+        file_id,
+        column_name,
+        EncoderType::Ordinal(Ordinal {
+            categories,
+            unknown,
+        }),
+    )
+}
+
+/// Adds a hashing trick encoder to a column of the SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column which must already have been added.
+/// * `buckets` - The number of buckets the categories are hashed into.
+#[no_mangle]
+pub extern "C" fn add_hashing_encoder(
+    file_id: *const c_char,
+    column_name: *const c_char,
+    buckets: u32,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let column_name = process_string_for_empty_return!(column_name, "column name");
+    add_encoder(
+        file_id,
+        column_name,
+        EncoderType::Hashing(Hashing { buckets }),
+    )
+}
+
+/// Adds a target encoder to a column of the SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column which must already have been added.
+/// * `categories` - The categories of the lookup table.
+/// * `targets` - The value of each category in the lookup table.
+/// * `length` - The number of categories and values.
+/// * `default` (Optional) - The value for an unknown category. An unknown category is an error without it.
+#[no_mangle]
+pub extern "C" fn add_target_encoder(
+    file_id: *const c_char,
+    column_name: *const c_char,
+    categories: *const *const c_char,
+    targets: *const c_float,
+    length: usize,
+    default: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let column_name = process_string_for_empty_return!(column_name, "column name");
+    let categories = empty_return_safe_eject!(strings_from_array(categories, length, "categories"));
+    if targets.is_null() {
+        return EmptyReturn {
+            is_error: 1,
+            error_message: CString::new("Received a null pointer for targets")
+                .unwrap()
+                .into_raw(),
+        };
+    }
+    let targets = unsafe { std::slice::from_raw_parts(targets, length) };
+    let default = empty_return_safe_eject!(optional_number(default, "default"));
+    add_encoder(
+        file_id,
+        column_name,
+        EncoderType::Target(TargetEncoding {
+            table: categories
+                .into_iter()
+                .zip(targets.iter().cloned())
+                .collect(),
+            default,
+        }),
+    )
+}
@@ -1,13 +1,15 @@
-//! Defines the C interface for loading a surml file and getting the meta data around the model.
+//! Defines the C interface for loading a surml file, or just its header, and getting the meta data around the model.
 // Standard library imports
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_int};
 
 // External crate imports
+use surrealml_core::errors::error::SurrealError;
+use surrealml_core::storage::header::Header;
 use surrealml_core::storage::surml_file::SurMlFile;
 
 // Local module imports
-use crate::state::{generate_unique_id, STATE};
+use crate::state::{generate_unique_id, HEADER_ONLY, STATE};
 
 /// Holds the data around the outcome of the load_model function.
 ///
@@ -61,6 +63,42 @@ pub extern "C" fn free_file_info(info: FileInfo) {
 /// Meta data around the model and a unique identifier for the loaded model.
 #[no_mangle]
 pub extern "C" fn load_model(file_path_ptr: *const c_char) -> FileInfo {
+    load_file_info(file_path_ptr, false, SurMlFile::from_file)
+}
+
+/// Loads only the header of a model from a file so the meta data of many models can be listed without reading the
+/// model bytes. The loaded header can be used with the meta data functions but the model cannot be saved,
+/// converted to bytes, computed, signed, verified or validated.
+///
+/// # Arguments
+/// * `file_path_ptr` - A pointer to the file path of the model to load.
+///
+/// # Returns
+/// Meta data around the model and a unique identifier for the loaded header.
+#[no_mangle]
+pub extern "C" fn load_header(file_path_ptr: *const c_char) -> FileInfo {
+    load_file_info(file_path_ptr, true, |file_path| {
+        Ok(SurMlFile::new(
+            Header::read_from_path(file_path)?,
+            Vec::new(),
+        ))
+    })
+}
+
+/// Loads a file with the given loader, stores it in the state and returns the meta data around it.
+///
+/// # Arguments
+/// * `file_path_ptr` - A pointer to the file path of the model to load.
+/// * `header_only` - If `true` the loader only reads the header so the file is marked as not holding a model.
+/// * `load` - Loads the `SurMlFile` from the file path.
+///
+/// # Returns
+/// Meta data around the model and a unique identifier for the loaded model.
+fn load_file_info(
+    file_path_ptr: *const c_char,
+    header_only: bool,
+    load: impl FnOnce(&str) -> Result<SurMlFile, SurrealError>,
+) -> FileInfo {
     // checking that the file path pointer is not null
     if file_path_ptr.is_null() {
         return FileInfo {
@@ -95,7 +133,7 @@ pub extern "C" fn load_model(file_path_ptr: *const c_char) -> FileInfo {
         }
     };
 
-    let file = match SurMlFile::from_file(file_path) {
+    let file = match load(file_path) {
         Ok(file) => file,
         Err(e) => {
             return FileInfo {
@@ -118,6 +156,9 @@ pub extern "C" fn load_model(file_path_ptr: *const c_char) -> FileInfo {
     let file_id = generate_unique_id();
     let mut state = STATE.lock().unwrap();
     state.insert(file_id.clone(), file);
+    if header_only {
+        HEADER_ONLY.lock().unwrap().insert(file_id.clone());
+    }
 
     // return the meta data
     let file_id = CString::new(file_id).unwrap();
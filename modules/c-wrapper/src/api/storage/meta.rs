@@ -4,10 +4,15 @@ use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 
 // External crate imports
+use surrealml_core::storage::header::column::{ColumnSpec, ColumnType};
+use surrealml_core::storage::header::normalisers::parameters::NormaliserParameters;
+use surrealml_core::storage::header::normalisers::registry;
 use surrealml_core::storage::header::normalisers::wrapper::NormaliserType;
+use surrealml_core::storage::header::origin::Provenance;
+use surrealml_core::storage::header::Header;
 
 // Local module imports
-use crate::state::STATE;
+use crate::state::{invalidate_compiled, STATE};
 use crate::utils::EmptyReturn;
 use crate::{empty_return_safe_eject, process_string_for_empty_return};
 
@@ -25,6 +30,7 @@ pub extern "C" fn add_name(
     let model_name = process_string_for_empty_return!(model_name_ptr, "model name");
     let mut state = STATE.lock().unwrap();
     let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
     wrapped_file.header.add_name(model_name);
     EmptyReturn::success()
 }
@@ -43,6 +49,7 @@ pub extern "C" fn add_description(
     let description = process_string_for_empty_return!(description_ptr, "description");
     let mut state = STATE.lock().unwrap();
     let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
     wrapped_file.header.add_description(description);
     EmptyReturn::success()
 }
@@ -58,6 +65,7 @@ pub extern "C" fn add_version(file_id: *const c_char, version: *const c_char) ->
     let version = process_string_for_empty_return!(version, "version");
     let mut state = STATE.lock().unwrap();
     let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
     let _ = wrapped_file.header.add_version(version);
     EmptyReturn::success()
 }
@@ -73,6 +81,7 @@ pub extern "C" fn add_column(file_id: *const c_char, column_name: *const c_char)
     let column_name = process_string_for_empty_return!(column_name, "column name");
     let mut state = STATE.lock().unwrap();
     let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
     wrapped_file.header.add_column(column_name);
     EmptyReturn::success()
 }
@@ -88,6 +97,7 @@ pub extern "C" fn add_author(file_id: *const c_char, author: *const c_char) -> E
     let author = process_string_for_empty_return!(author, "author");
     let mut state = STATE.lock().unwrap();
     let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
     wrapped_file.header.add_author(author);
     EmptyReturn::success()
 }
@@ -96,14 +106,55 @@ pub extern "C" fn add_author(file_id: *const c_char, author: *const c_char) -> E
 ///
 /// # Arguments
 /// * `file_id` - The unique identifier for the SurMlFile struct.
-/// * `origin` - The origin to be added.
+/// * `origin` - The origin to be added which is `local`, `surreal_db` or a URI of the source system.
 #[no_mangle]
 pub extern "C" fn add_origin(file_id: *const c_char, origin: *const c_char) -> EmptyReturn {
     let file_id = process_string_for_empty_return!(file_id, "file id");
     let origin = process_string_for_empty_return!(origin, "origin");
     let mut state = STATE.lock().unwrap();
     let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
-    let _ = wrapped_file.header.add_origin(origin);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(wrapped_file.header.add_origin(origin));
+    EmptyReturn::success()
+}
+
+/// Records how the model of the SurMlFile struct was trained, replacing any provenance already recorded.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `run_id` (Optional) - The identifier of the training run in the source system.
+/// * `commit` (Optional) - The git commit of the training code.
+/// * `parent_digest` (Optional) - The hex encoded SHA-256 digest of the model this model was trained from.
+/// * `trained_at` (Optional) - When the model was trained as an RFC 3339 timestamp.
+#[no_mangle]
+pub extern "C" fn add_provenance(
+    file_id: *const c_char,
+    run_id: *const c_char,
+    commit: *const c_char,
+    parent_digest: *const c_char,
+    trained_at: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let mut provenance = Provenance::fresh();
+    if !run_id.is_null() {
+        provenance.run_id = Some(process_string_for_empty_return!(run_id, "run id"));
+    }
+    if !commit.is_null() {
+        provenance.commit = Some(process_string_for_empty_return!(commit, "commit"));
+    }
+    if !parent_digest.is_null() {
+        provenance.parent_digest = Some(process_string_for_empty_return!(
+            parent_digest,
+            "parent digest"
+        ));
+    }
+    if !trained_at.is_null() {
+        provenance.trained_at = Some(process_string_for_empty_return!(trained_at, "trained at"));
+    }
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(wrapped_file.header.add_provenance(provenance));
     EmptyReturn::success()
 }
 
@@ -118,7 +169,190 @@ pub extern "C" fn add_engine(file_id: *const c_char, engine: *const c_char) -> E
     let engine = process_string_for_empty_return!(engine, "engine");
     let mut state = STATE.lock().unwrap();
     let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
-    wrapped_file.header.add_engine(engine);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(wrapped_file.header.add_engine(engine));
+    EmptyReturn::success()
+}
+
+/// Records the tool that exported the model of the SurMlFile struct to ONNX.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `name` - The name of the exporter such as `skl2onnx`.
+/// * `version` (Optional) - The version of the exporter.
+/// * `opset` (Optional) - The ONNX opset the model was exported with.
+#[no_mangle]
+pub extern "C" fn add_exporter(
+    file_id: *const c_char,
+    name: *const c_char,
+    version: *const c_char,
+    opset: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let name = process_string_for_empty_return!(name, "name");
+    let version = if version.is_null() {
+        None
+    } else {
+        Some(process_string_for_empty_return!(version, "version"))
+    };
+    let opset = if opset.is_null() {
+        None
+    } else {
+        Some(empty_return_safe_eject!(process_string_for_empty_return!(
+            opset, "opset"
+        )
+        .parse::<u32>()))
+    };
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    wrapped_file.header.add_exporter(name, version, opset);
+    EmptyReturn::success()
+}
+
+/// Binds a column of the SurMlFile struct to a named input of the model.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column which must already have been added.
+/// * `input_name` - The name of the input in the ONNX graph.
+#[no_mangle]
+pub extern "C" fn add_input_binding(
+    file_id: *const c_char,
+    column_name: *const c_char,
+    input_name: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let column_name = process_string_for_empty_return!(column_name, "column name");
+    let input_name = process_string_for_empty_return!(input_name, "input name");
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(wrapped_file
+        .header
+        .add_input_binding(column_name, input_name));
+    EmptyReturn::success()
+}
+
+/// Sets the codec the model is compressed with when the SurMlFile struct is saved or converted to bytes.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `codec` - The compression codec (`deflate`, `zstd`, or an empty string for no compression).
+#[no_mangle]
+pub extern "C" fn add_compression(file_id: *const c_char, codec: *const c_char) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let codec = process_string_for_empty_return!(codec, "compression codec");
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(wrapped_file.header.add_compression(codec));
+    EmptyReturn::success()
+}
+
+/// Declares the shape of the input tensor of the SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `dims` - The comma separated axes of the input such as `batch,seq_len,8` where identifiers are dynamic axes.
+#[no_mangle]
+pub extern "C" fn add_input_dims(file_id: *const c_char, dims: *const c_char) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let dims = process_string_for_empty_return!(dims, "input dims");
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(wrapped_file.header.add_input_dims(dims));
+    EmptyReturn::success()
+}
+
+/// Replaces the header of the SurMlFile struct with a header inferred from the ONNX graph of its model, filling the
+/// input and output names, element types and shapes and the metadata_props of the graph.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+#[no_mangle]
+pub extern "C" fn infer_header(file_id: *const c_char) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    wrapped_file.header = empty_return_safe_eject!(Header::infer_from_onnx(&wrapped_file.model));
+    EmptyReturn::success()
+}
+
+/// Declares the type, default and allowed range of a column of the SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column which must already have been added.
+/// * `column_type` - The type of the column (`float`, `int`, `bool`, `categorical` or `timestamp`).
+/// * `default` (Optional) - The value used when the column is missing from the input values.
+/// * `min` (Optional) - The smallest allowed value of the column.
+/// * `max` (Optional) - The largest allowed value of the column.
+#[no_mangle]
+pub extern "C" fn add_column_type(
+    file_id: *const c_char,
+    column_name: *const c_char,
+    column_type: *const c_char,
+    default: *const c_char,
+    min: *const c_char,
+    max: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let column_name = process_string_for_empty_return!(column_name, "column name");
+    let column_type = process_string_for_empty_return!(column_type, "column type");
+    let mut spec = ColumnSpec::new(empty_return_safe_eject!(ColumnType::from_string(
+        column_type
+    )));
+
+    if !default.is_null() {
+        let default = process_string_for_empty_return!(default, "default");
+        spec.default = Some(empty_return_safe_eject!(spec
+            .column_type
+            .parse_value(&default)));
+    }
+    if !min.is_null() {
+        spec.min = Some(empty_return_safe_eject!(process_string_for_empty_return!(
+            min, "min"
+        )
+        .parse::<f64>()));
+    }
+    if !max.is_null() {
+        spec.max = Some(empty_return_safe_eject!(process_string_for_empty_return!(
+            max, "max"
+        )
+        .parse::<f64>()));
+    }
+
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(wrapped_file.header.add_column_spec(column_name, spec));
+    EmptyReturn::success()
+}
+
+/// Appends a category to a categorical column of the SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column which must already be declared as categorical.
+/// * `category` - The category to be added.
+#[no_mangle]
+pub extern "C" fn add_column_category(
+    file_id: *const c_char,
+    column_name: *const c_char,
+    category: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let column_name = process_string_for_empty_return!(column_name, "column name");
+    let category = process_string_for_empty_return!(category, "category");
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(wrapped_file
+        .header
+        .add_column_category(column_name, category));
     EmptyReturn::success()
 }
 
@@ -167,14 +401,21 @@ pub extern "C" fn add_output(
         .parse::<f32>()))
     };
 
+    let normaliser = match normaliser_label {
+        Some(normaliser_label) => {
+            // a missing parameter leaves it open for normalisers that support it such as clipping
+            let parameters = one.into_iter().chain(two).fold(
+                NormaliserParameters::new(&normaliser_label),
+                |parameters, value| parameters.with_value(value),
+            );
+            Some(empty_return_safe_eject!(registry::build(&parameters)))
+        }
+        None => None,
+    };
     let mut state = STATE.lock().unwrap();
     let file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
-    if let Some(normaliser_label) = normaliser_label {
-        let normaliser = NormaliserType::new(normaliser_label, one.unwrap(), two.unwrap());
-        file.header.add_output(output_name, Some(normaliser));
-    } else {
-        file.header.add_output(output_name, None);
-    }
+    invalidate_compiled(&file_id);
+    file.header.add_output(output_name, normaliser);
     EmptyReturn::success()
 }
 
@@ -199,12 +440,110 @@ pub extern "C" fn add_normaliser(
     let normaliser_label =
         process_string_for_empty_return!(normaliser_label_ptr, "normaliser label");
 
-    let normaliser = NormaliserType::new(normaliser_label, one, two);
+    let normaliser = empty_return_safe_eject!(NormaliserType::new(normaliser_label, one, two));
     let mut state = STATE.lock().unwrap();
     let file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
     let _ = file
         .header
         .normalisers
         .add_normaliser(normaliser, column_name, &file.header.keys);
     EmptyReturn::success()
 }
+
+/// Appends a normaliser to the pipeline of a column of the SurMlFile struct so it is applied after the normalisers
+/// already on the column.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column to which the normaliser will be applied.
+/// * `normaliser_label` - The label of the normaliser to be appended.
+/// * `one` - The first parameter of the normaliser.
+/// * `two` - The second parameter of the normaliser.
+#[no_mangle]
+pub extern "C" fn add_normaliser_step(
+    file_id: *const c_char,
+    column_name: *const c_char,
+    normaliser_label: *const c_char,
+    one: f32,
+    two: f32,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let column_name = process_string_for_empty_return!(column_name, "column name");
+    let normaliser_label = process_string_for_empty_return!(normaliser_label, "normaliser label");
+    let normaliser = empty_return_safe_eject!(NormaliserType::new(normaliser_label, one, two));
+    let mut state = STATE.lock().unwrap();
+    let file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(file.header.add_normaliser_step(column_name, normaliser));
+    EmptyReturn::success()
+}
+
+/// Appends a normaliser to the pipeline of the output of the SurMlFile struct. The inverse of the pipeline is applied
+/// to the outputs of the model in reverse order.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `normaliser_label` - The label of the normaliser to be appended.
+/// * `one` - The first parameter of the normaliser.
+/// * `two` - The second parameter of the normaliser.
+#[no_mangle]
+pub extern "C" fn add_output_normaliser_step(
+    file_id: *const c_char,
+    normaliser_label: *const c_char,
+    one: f32,
+    two: f32,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let normaliser_label = process_string_for_empty_return!(normaliser_label, "normaliser label");
+    let normaliser = empty_return_safe_eject!(NormaliserType::new(normaliser_label, one, two));
+    let mut state = STATE.lock().unwrap();
+    let file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    file.header.add_output_normaliser_step(normaliser);
+    EmptyReturn::success()
+}
+
+/// Sets the normaliser of a column of the SurMlFile struct from its definition. Parameters can be named such as
+/// `clipping(min=0)` and a pipeline is defined by its steps separated by `|`.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `column_name` - The name of the column to which the normaliser will be applied.
+/// * `definition` - The definition of the normaliser such as `robust_scaling(median=3,iqr=2)`.
+#[no_mangle]
+pub extern "C" fn add_normaliser_definition(
+    file_id: *const c_char,
+    column_name: *const c_char,
+    definition: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let column_name = process_string_for_empty_return!(column_name, "column name");
+    let definition = process_string_for_empty_return!(definition, "definition");
+    let normaliser = empty_return_safe_eject!(NormaliserType::from_definition(&definition));
+    let mut state = STATE.lock().unwrap();
+    let file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(file.header.add_normaliser(column_name, normaliser));
+    EmptyReturn::success()
+}
+
+/// Sets the normaliser of the output of the SurMlFile struct from its definition.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `definition` - The definition of the normaliser such as `yeo_johnson(lambda=0.5)|z_score(1,2)`.
+#[no_mangle]
+pub extern "C" fn add_output_normaliser_definition(
+    file_id: *const c_char,
+    definition: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let definition = process_string_for_empty_return!(definition, "definition");
+    let normaliser = empty_return_safe_eject!(NormaliserType::from_definition(&definition));
+    let mut state = STATE.lock().unwrap();
+    let file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    file.header.outputs.first_mut().add_normaliser(normaliser);
+    EmptyReturn::success()
+}
@@ -0,0 +1,106 @@
+//! Defines the C API interface for the free form metadata of a SurML file such as metrics, licenses and tags.
+// Standard library imports
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+// External crate imports
+use surrealml_core::storage::header::metadata::MetadataValue;
+
+// Local module imports
+use crate::state::{invalidate_compiled, STATE};
+use crate::utils::{EmptyReturn, StringReturn};
+use crate::{
+    empty_return_safe_eject, process_string_for_empty_return, process_string_for_string_return,
+};
+
+/// Sets a metadata value of the SurMlFile struct replacing any value already under the key.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `key` - The name of the value.
+/// * `value` - The value as JSON such as `{"rmse": 0.4}`, `["housing"]`, `true` or `"MIT"`.
+#[no_mangle]
+pub extern "C" fn set_metadata(
+    file_id: *const c_char,
+    key: *const c_char,
+    value: *const c_char,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let key = process_string_for_empty_return!(key, "key");
+    let value = process_string_for_empty_return!(value, "value");
+    let value = empty_return_safe_eject!(MetadataValue::from_json(&value));
+    let mut state = STATE.lock().unwrap();
+    let file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    empty_return_safe_eject!(file.header.set_metadata(key, value));
+    EmptyReturn::success()
+}
+
+/// Gets a metadata value of the SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `path` - The key of the value or a dot separated path into nested values such as `metrics.rmse`.
+///
+/// # Returns
+/// The value as JSON, or `null` if there is no value at the path.
+#[no_mangle]
+pub extern "C" fn get_metadata(file_id: *const c_char, path: *const c_char) -> StringReturn {
+    let file_id = process_string_for_string_return!(file_id, "file id");
+    let path = process_string_for_string_return!(path, "path");
+    let state = STATE.lock().unwrap();
+    let file = match state.get(&file_id) {
+        Some(file) => file,
+        None => {
+            return StringReturn {
+                string: std::ptr::null_mut(),
+                is_error: 1,
+                error_message: CString::new("Model not found").unwrap().into_raw(),
+            }
+        }
+    };
+    match file.header.get_metadata(&path) {
+        Some(value) => StringReturn::success(value.to_string()),
+        None => StringReturn::success("null".to_string()),
+    }
+}
+
+/// Gets all of the metadata of the SurMlFile struct.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+///
+/// # Returns
+/// The metadata as a JSON object.
+#[no_mangle]
+pub extern "C" fn list_metadata(file_id: *const c_char) -> StringReturn {
+    let file_id = process_string_for_string_return!(file_id, "file id");
+    let state = STATE.lock().unwrap();
+    let file = match state.get(&file_id) {
+        Some(file) => file,
+        None => {
+            return StringReturn {
+                string: std::ptr::null_mut(),
+                is_error: 1,
+                error_message: CString::new("Model not found").unwrap().into_raw(),
+            }
+        }
+    };
+    StringReturn::success(file.header.metadata.to_string())
+}
+
+/// Removes a metadata value from the SurMlFile struct. Removing a key that is not set is not an error.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `key` - The name of the value.
+#[no_mangle]
+pub extern "C" fn remove_metadata(file_id: *const c_char, key: *const c_char) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id, "file id");
+    let key = process_string_for_empty_return!(key, "key");
+    let mut state = STATE.lock().unwrap();
+    let file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    invalidate_compiled(&file_id);
+    file.header.remove_metadata(&key);
+    EmptyReturn::success()
+}
@@ -1,7 +1,12 @@
 //! C Storage API
+pub mod encoders;
 pub mod load_cached_raw_model;
 pub mod load_model;
 pub mod meta;
+pub mod metadata;
 pub mod save_model;
+pub mod signing;
 pub mod to_bytes;
+pub mod unload_model;
 pub mod upload_model;
+pub mod validate;
@@ -7,7 +7,7 @@ use std::os::raw::c_char;
 use surrealml_core::storage::surml_file::SurMlFile;
 
 // Local module imports
-use crate::state::STATE;
+use crate::state::{check_model_loaded, invalidate_compiled, STATE, TRUSTED_KEYS};
 use crate::utils::EmptyReturn;
 use crate::{empty_return_safe_eject, process_string_for_empty_return};
 
@@ -26,10 +26,13 @@ pub extern "C" fn save_model(
 ) -> EmptyReturn {
     let file_path_str = process_string_for_empty_return!(file_path_ptr, "file path");
     let file_id_str = process_string_for_empty_return!(file_id_ptr, "file id");
+    empty_return_safe_eject!(check_model_loaded(&file_id_str));
     let mut state = STATE.lock().unwrap();
     let file: &mut SurMlFile =
         empty_return_safe_eject!(state.get_mut(&file_id_str), "Model not found", Option);
     empty_return_safe_eject!(file.write(&file_path_str));
     state.remove(&file_id_str);
+    TRUSTED_KEYS.lock().unwrap().remove(&file_id_str);
+    invalidate_compiled(&file_id_str);
     EmptyReturn::success()
 }
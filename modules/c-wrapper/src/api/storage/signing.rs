@@ -0,0 +1,128 @@
+//! Defines the C API interface for signing a SurML file and verifying its signature.
+// Standard library imports
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+// External crate imports
+use surrealml_core::storage::signature::{SigningKey, VerifyingKey};
+
+// Local module imports
+use crate::state::{check_model_loaded, STATE, TRUSTED_KEYS};
+use crate::utils::EmptyReturn;
+use crate::{empty_return_safe_eject, process_string_for_empty_return};
+
+/// The number of bytes in an Ed25519 private or public key.
+const KEY_LENGTH: usize = 32;
+
+/// Signs the header and model of the SurMlFile struct with an Ed25519 private key.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `key_ptr` - A pointer to the 32 bytes of the private key.
+/// * `key_length` - The number of bytes of the private key.
+#[no_mangle]
+pub extern "C" fn sign_model(
+    file_id_ptr: *const c_char,
+    key_ptr: *const u8,
+    key_length: usize,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id_ptr, "file id");
+    empty_return_safe_eject!(check_model_loaded(&file_id));
+    if key_ptr.is_null() {
+        return EmptyReturn {
+            is_error: 1,
+            error_message: CString::new("Received a null pointer for signing key")
+                .unwrap()
+                .into_raw(),
+        };
+    }
+    let key_bytes = unsafe { std::slice::from_raw_parts(key_ptr, key_length) };
+    let key_bytes: &[u8; KEY_LENGTH] = empty_return_safe_eject!(
+        key_bytes.try_into().ok(),
+        "Signing key must be 32 bytes",
+        Option
+    );
+    let signing_key = SigningKey::from_bytes(key_bytes);
+
+    let mut state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get_mut(&file_id), "Model not found", Option);
+    empty_return_safe_eject!(wrapped_file.sign(&signing_key));
+    EmptyReturn::success()
+}
+
+/// Verifies that the SurMlFile struct is signed by one of the trusted Ed25519 public keys.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `keys_ptr` - A pointer to the trusted public keys, each 32 bytes, concatenated together.
+/// * `keys_length` - The total number of bytes of the trusted public keys.
+#[no_mangle]
+pub extern "C" fn verify_model(
+    file_id_ptr: *const c_char,
+    keys_ptr: *const u8,
+    keys_length: usize,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id_ptr, "file id");
+    empty_return_safe_eject!(check_model_loaded(&file_id));
+    let trusted_keys = match read_trusted_keys(keys_ptr, keys_length) {
+        Ok(trusted_keys) => trusted_keys,
+        Err(error) => return EmptyReturn::error(error),
+    };
+
+    let state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get(&file_id), "Model not found", Option);
+    empty_return_safe_eject!(wrapped_file.verify(&trusted_keys));
+    EmptyReturn::success()
+}
+
+/// Requires the SurMlFile struct to be signed by one of the trusted Ed25519 public keys before it is computed. The
+/// signature is checked now and again on every computation so a model that is changed afterwards is not computed.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+/// * `keys_ptr` - A pointer to the trusted public keys, each 32 bytes, concatenated together.
+/// * `keys_length` - The total number of bytes of the trusted public keys.
+#[no_mangle]
+pub extern "C" fn require_signature(
+    file_id_ptr: *const c_char,
+    keys_ptr: *const u8,
+    keys_length: usize,
+) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id_ptr, "file id");
+    empty_return_safe_eject!(check_model_loaded(&file_id));
+    let trusted_keys = match read_trusted_keys(keys_ptr, keys_length) {
+        Ok(trusted_keys) => trusted_keys,
+        Err(error) => return EmptyReturn::error(error),
+    };
+
+    let state = STATE.lock().unwrap();
+    let wrapped_file = empty_return_safe_eject!(state.get(&file_id), "Model not found", Option);
+    empty_return_safe_eject!(wrapped_file.verify(&trusted_keys));
+    TRUSTED_KEYS.lock().unwrap().insert(file_id, trusted_keys);
+    EmptyReturn::success()
+}
+
+/// Reads the trusted Ed25519 public keys passed in from C.
+///
+/// # Arguments
+/// * `keys_ptr` - A pointer to the trusted public keys, each 32 bytes, concatenated together.
+/// * `keys_length` - The total number of bytes of the trusted public keys.
+///
+/// # Returns
+/// The public keys, or an error message if the pointer is null or the bytes are not valid keys.
+fn read_trusted_keys(keys_ptr: *const u8, keys_length: usize) -> Result<Vec<VerifyingKey>, String> {
+    if keys_ptr.is_null() {
+        return Err("Received a null pointer for trusted keys".to_string());
+    }
+    if !keys_length.is_multiple_of(KEY_LENGTH) {
+        return Err("Trusted keys must be a multiple of 32 bytes".to_string());
+    }
+    let keys_bytes = unsafe { std::slice::from_raw_parts(keys_ptr, keys_length) };
+    keys_bytes
+        .chunks_exact(KEY_LENGTH)
+        .map(|chunk| {
+            let key_bytes: &[u8; KEY_LENGTH] = chunk.try_into().unwrap();
+            VerifyingKey::from_bytes(key_bytes).map_err(|error| error.to_string())
+        })
+        .collect()
+}
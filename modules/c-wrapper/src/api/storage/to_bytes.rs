@@ -5,7 +5,7 @@ use std::os::raw::c_char;
 
 // Local module imports
 use crate::process_string_for_vec_u8_return;
-use crate::state::STATE;
+use crate::state::{check_model_loaded, STATE};
 use crate::utils::VecU8Return;
 
 /// Converts the entire SurML file to bytes.
@@ -20,6 +20,17 @@ pub extern "C" fn to_bytes(file_id_ptr: *const c_char) -> VecU8Return {
     let file_id = process_string_for_vec_u8_return!(file_id_ptr, "file id");
     let mut state = STATE.lock().unwrap();
     let file = state.get_mut(&file_id).unwrap();
-    let raw_bytes = file.to_bytes();
+    let raw_bytes = match check_model_loaded(&file_id).and_then(|_| file.to_bytes()) {
+        Ok(raw_bytes) => raw_bytes,
+        Err(e) => {
+            return VecU8Return {
+                data: std::ptr::null_mut(),
+                length: 0,
+                capacity: 0,
+                is_error: 1,
+                error_message: CString::new(e.to_string()).unwrap().into_raw(),
+            }
+        }
+    };
     VecU8Return::success(raw_bytes)
 }
@@ -0,0 +1,28 @@
+//! Defines the C interface for removing a loaded model, or header, from the `STATE` once the caller is done with it.
+// Standard library imports
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+// Local module imports
+use crate::state::{invalidate_compiled, HEADER_ONLY, STATE, TRUSTED_KEYS};
+use crate::utils::EmptyReturn;
+use crate::{empty_return_safe_eject, process_string_for_empty_return};
+
+/// Removes a loaded model or header from the `STATE` so its memory is freed. The unique identifier cannot be used
+/// again after this call.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the loaded model.
+///
+/// # Returns
+/// An empty return object indicating success or failure.
+#[no_mangle]
+pub extern "C" fn unload_model(file_id_ptr: *const c_char) -> EmptyReturn {
+    let file_id = process_string_for_empty_return!(file_id_ptr, "file id");
+    let mut state = STATE.lock().unwrap();
+    empty_return_safe_eject!(state.remove(&file_id), "Model not found", Option);
+    HEADER_ONLY.lock().unwrap().remove(&file_id);
+    TRUSTED_KEYS.lock().unwrap().remove(&file_id);
+    invalidate_compiled(&file_id);
+    EmptyReturn::success()
+}
@@ -70,9 +70,8 @@ pub extern "C" fn upload_model(
             empty_return_safe_eject!(HeaderValue::from_str(&db)),
         );
 
-    let req = if username.is_some() && password.is_some() {
-        // unwraps are safe because we have already checked that the values are not None
-        let encoded_credentials = encode(format!("{}:{}", username.unwrap(), password.unwrap()));
+    let req = if let (Some(username), Some(password)) = (username, password) {
+        let encoded_credentials = encode(format!("{}:{}", username, password));
         empty_return_safe_eject!(part_req
             .header(AUTHORIZATION, format!("Basic {}", encoded_credentials))
             .body(body))
@@ -0,0 +1,34 @@
+//! Defines the C API interface for checking that the header of a SurML file is consistent with its model.
+// Standard library imports
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+// Local module imports
+use crate::state::{check_model_loaded, STATE};
+use crate::utils::StringReturn;
+use crate::{process_string_for_string_return, string_return_safe_eject};
+
+/// Validates the SurMlFile struct against its model.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the SurMlFile struct.
+///
+/// # Returns
+/// The report with one `severity [location]: message` line per issue, which is empty if there are no issues.
+#[no_mangle]
+pub extern "C" fn validate_model(file_id_ptr: *const c_char) -> StringReturn {
+    let file_id = process_string_for_string_return!(file_id_ptr, "file id");
+    string_return_safe_eject!(check_model_loaded(&file_id));
+    let state = STATE.lock().unwrap();
+    let file = match state.get(&file_id) {
+        Some(file) => file,
+        None => {
+            return StringReturn {
+                string: std::ptr::null_mut(),
+                is_error: 1,
+                error_message: CString::new("Model not found").unwrap().into_raw(),
+            }
+        }
+    };
+    StringReturn::success(file.validate().to_string())
+}
@@ -1,9 +1,12 @@
 //! Defines operations for handling memory of a python program that is accessing the rust library.
 // Standard library imports
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, LazyLock, Mutex};
 
 // External crate imports
+use surrealml_core::errors::error::{SurrealError, SurrealErrorStatus};
+use surrealml_core::execution::compiled::CompiledModel;
+use surrealml_core::storage::signature::VerifyingKey;
 use surrealml_core::storage::surml_file::SurMlFile;
 
 // External library imports
@@ -17,6 +20,51 @@ use uuid::Uuid;
 pub static STATE: LazyLock<Arc<Mutex<HashMap<String, SurMlFile>>>> =
     LazyLock::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+/// A hashmap of unique identifiers of loaded models to the public keys that must have signed them. A model in this
+/// map is only computed if its signature is valid for one of the keys at the time of the computation.
+pub static TRUSTED_KEYS: LazyLock<Mutex<HashMap<String, Vec<VerifyingKey>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A hashmap of unique identifiers of loaded models to their compiled models so the ONNX session of a model is built
+/// on its first computation and reused by the computations after it. An entry is removed when the header of the model
+/// is changed or the model is unloaded, so the next computation compiles the changed model.
+pub static COMPILED: LazyLock<Mutex<HashMap<String, Arc<CompiledModel>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Removes the compiled model of a loaded model so it is compiled again on its next computation.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the loaded model.
+pub fn invalidate_compiled(file_id: &str) {
+    COMPILED.lock().unwrap().remove(file_id);
+}
+
+/// The unique identifiers of files loaded with `load_header`. Only the header of these files is in the `STATE` so they
+/// can be used with the meta data functions but cannot be saved, converted to bytes, computed, signed, verified or
+/// validated as their model and signature were never read.
+pub static HEADER_ONLY: LazyLock<Mutex<HashSet<String>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Checks that the model of a loaded file is in the `STATE` and not only its header.
+///
+/// # Arguments
+/// * `file_id` - The unique identifier for the loaded file.
+///
+/// # Returns
+/// An error with a `BadRequest` status if only the header of the file was loaded.
+pub fn check_model_loaded(file_id: &str) -> Result<(), SurrealError> {
+    if HEADER_ONLY.lock().unwrap().contains(file_id) {
+        return Err(SurrealError::new(
+            format!(
+                "Only the header of {} was loaded so the model cannot be used, load it with load_model instead",
+                file_id
+            ),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+    Ok(())
+}
+
 /// Generates a unique identifier that can be used to access a loaded machine learning model.
 ///
 /// # Returns
@@ -1,5 +1,6 @@
 //! Defines macros and C structs for reducing the amount of boilerplate code required for the C API.
-use std::ffi::CString;
+use ndarray::ArrayD;
+use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_int};
 
 /// Checks that the pointer to the string is not null and converts to a Rust string. Any errors are returned as an `EmptyReturn`.
@@ -283,6 +284,20 @@ impl EmptyReturn {
             error_message: std::ptr::null_mut(),
         }
     }
+
+    /// Returns a new `EmptyReturn` object with an error message.
+    ///
+    /// # Arguments
+    /// * `message` - The error message.
+    ///
+    /// # Returns
+    /// A new `EmptyReturn` object.
+    pub fn error(message: String) -> Self {
+        EmptyReturn {
+            is_error: 1,
+            error_message: CString::new(message).unwrap().into_raw(),
+        }
+    }
 }
 
 /// Frees the memory allocated for the `EmptyReturn` object.
@@ -364,6 +379,8 @@ pub extern "C" fn free_vec_u8(vec_u8: VecU8Return) {
 /// * `capacity` - The capacity of the data.
 /// * `is_error` - A flag indicating if an error occurred (1 for error, 0 for success).
 /// * `error_message` - An error message if the computation failed.
+/// * `shape` - The shape of the output so the data can be rebuilt into an array.
+/// * `shape_length` - The number of dimensions in the shape.
 #[repr(C)]
 pub struct Vecf32Return {
     pub data: *mut f32,
@@ -371,6 +388,55 @@ pub struct Vecf32Return {
     pub capacity: usize, // Optional if you want to include capacity for clarity
     pub is_error: c_int,
     pub error_message: *mut c_char,
+    pub shape: *mut usize,
+    pub shape_length: usize,
+}
+
+impl Vecf32Return {
+    /// Returns a new `Vecf32Return` object with an error message and no data.
+    ///
+    /// # Arguments
+    /// * `message` - The error message.
+    ///
+    /// # Returns
+    /// A new `Vecf32Return` object.
+    pub fn error(message: String) -> Self {
+        Vecf32Return {
+            data: std::ptr::null_mut(),
+            length: 0,
+            capacity: 0,
+            is_error: 1,
+            error_message: CString::new(message).unwrap().into_raw(),
+            shape: std::ptr::null_mut(),
+            shape_length: 0,
+        }
+    }
+
+    /// Returns a new `Vecf32Return` object with the data and shape of the output and no error.
+    ///
+    /// # Arguments
+    /// * `output` - The output tensor of the computation.
+    ///
+    /// # Returns
+    /// A new `Vecf32Return` object.
+    pub fn success(output: ArrayD<f32>) -> Self {
+        let shape = output.shape().to_vec().into_boxed_slice();
+        let shape_length = shape.len();
+        let mut data: Vec<f32> = output.iter().cloned().collect();
+        let data_ptr = data.as_mut_ptr();
+        let length = data.len();
+        let capacity = data.capacity();
+        std::mem::forget(data);
+        Vecf32Return {
+            data: data_ptr,
+            length,
+            capacity,
+            is_error: 0,
+            error_message: std::ptr::null_mut(),
+            shape: Box::into_raw(shape) as *mut usize,
+            shape_length,
+        }
+    }
 }
 
 /// Frees the memory allocated for the Vecf32Return.
@@ -393,4 +459,45 @@ pub extern "C" fn free_vecf32_return(vecf32_return: Vecf32Return) {
     if !vecf32_return.error_message.is_null() {
         unsafe { drop(CString::from_raw(vecf32_return.error_message)) };
     }
+    // Free the shape if it is not null
+    if !vecf32_return.shape.is_null() {
+        unsafe {
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                vecf32_return.shape,
+                vecf32_return.shape_length,
+            )))
+        };
+    }
+}
+
+/// Copies an array of C strings into owned strings.
+///
+/// # Arguments
+/// * `strings` - A pointer to the array of strings.
+/// * `count` - The number of strings in the array.
+/// * `var_name` - The name of the array to include in the error message.
+///
+/// # Returns
+/// The strings, or an error message if the array or one of the strings is null or not UTF-8.
+pub fn strings_from_array(
+    strings: *const *const c_char,
+    count: usize,
+    var_name: &str,
+) -> Result<Vec<String>, String> {
+    if strings.is_null() {
+        return Err(format!("Received a null pointer for {}", var_name));
+    }
+    let c_strings = unsafe { std::slice::from_raw_parts(strings, count) };
+    c_strings
+        .iter()
+        .map(|&string| {
+            if string.is_null() {
+                return Err(format!("Received a null pointer in {}", var_name));
+            }
+            match unsafe { CStr::from_ptr(string) }.to_str() {
+                Ok(string) => Ok(string.to_owned()),
+                Err(_) => Err(format!("Invalid UTF-8 string received in {}", var_name)),
+            }
+        })
+        .collect()
 }
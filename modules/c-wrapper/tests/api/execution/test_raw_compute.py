@@ -1,4 +1,5 @@
 import ctypes
+import math
 from unittest import TestCase, main
 
 from test_utils.c_lib_loader import load_library
@@ -45,6 +46,10 @@ class TestExecution(TestCase):
         outcome = [result.data[i] for i in range(result.length)]
         self.assertEqual(1.8246129751205444, outcome[0])
 
+        # the shape describes every value in the outcome
+        shape = [result.shape[i] for i in range(result.shape_length)]
+        self.assertEqual(result.length, math.prod(shape))
+
         # Free allocated memory
         self.lib.free_vecf32_return(result)
         self.lib.free_file_info(file_info)
@@ -0,0 +1,97 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo
+from test_utils.routes import TEST_SURML_PATH
+
+
+class TestColumnType(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.add_column_type.restype = EmptyReturn
+        self.lib.add_column_type.argtypes = [ctypes.c_char_p] * 6
+        self.lib.add_column_category.restype = EmptyReturn
+        self.lib.add_column_category.argtypes = [ctypes.c_char_p] * 3
+
+        self.model: FileInfo = self.lib.load_model(str(TEST_SURML_PATH).encode('utf-8'))
+        self.file_id = self.model.file_id
+
+    def tearDown(self) -> None:
+        self.lib.free_file_info(self.model)
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.add_column_type(None, None, None, None, None, None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+
+    def test_model_not_found(self):
+        outcome: EmptyReturn = self.lib.add_column_type(
+            "missing".encode('utf-8'), "num_floors".encode('utf-8'), "int".encode('utf-8'), None, None, None
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Model not found", outcome.error_message.decode('utf-8'))
+
+    def test_add_column_type(self):
+        outcome: EmptyReturn = self.lib.add_column_type(
+            self.file_id,
+            "num_floors".encode('utf-8'),
+            "int".encode('utf-8'),
+            "1".encode('utf-8'),
+            "1".encode('utf-8'),
+            "5".encode('utf-8'),
+        )
+        self.assertEqual(0, outcome.is_error)
+
+    def test_timestamp_default(self):
+        for default in ["1706659200", "2024-01-31"]:
+            outcome: EmptyReturn = self.lib.add_column_type(
+                self.file_id, "num_floors".encode('utf-8'), "timestamp".encode('utf-8'),
+                default.encode('utf-8'), None, None
+            )
+            self.assertEqual(0, outcome.is_error)
+
+        outcome = self.lib.add_column_type(
+            self.file_id, "num_floors".encode('utf-8'), "timestamp".encode('utf-8'),
+            "2024-02-31".encode('utf-8'), None, None
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("'2024-02-31' is not a valid timestamp value", outcome.error_message.decode('utf-8'))
+
+    def test_default_outside_range(self):
+        outcome: EmptyReturn = self.lib.add_column_type(
+            self.file_id, "num_floors".encode('utf-8'), "int".encode('utf-8'),
+            "9".encode('utf-8'), "1".encode('utf-8'), "5".encode('utf-8')
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertIn("outside of the allowed range", outcome.error_message.decode('utf-8'))
+
+    def test_unknown_type_and_column(self):
+        outcome: EmptyReturn = self.lib.add_column_type(
+            self.file_id, "num_floors".encode('utf-8'), "complex".encode('utf-8'), None, None, None
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Unsupported column type: complex", outcome.error_message.decode('utf-8'))
+
+        outcome = self.lib.add_column_type(
+            self.file_id, "missing".encode('utf-8'), "float".encode('utf-8'), None, None, None
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Column missing is not in the key bindings", outcome.error_message.decode('utf-8'))
+
+    def test_add_column_category(self):
+        outcome: EmptyReturn = self.lib.add_column_category(
+            self.file_id, "num_floors".encode('utf-8'), "two".encode('utf-8')
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual(
+            "Column num_floors is not declared as categorical", outcome.error_message.decode('utf-8')
+        )
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,89 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo
+from test_utils.routes import TEST_SURML_PATH
+
+
+def string_array(strings):
+    return (ctypes.c_char_p * len(strings))(*[string.encode('utf-8') for string in strings])
+
+
+class TestEncoders(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.add_one_hot_encoder.restype = EmptyReturn
+        self.lib.add_one_hot_encoder.argtypes = [
+            ctypes.c_char_p, ctypes.c_char_p, ctypes.POINTER(ctypes.c_char_p), ctypes.c_size_t
+        ]
+        self.lib.add_hashing_encoder.restype = EmptyReturn
+        self.lib.add_hashing_encoder.argtypes = [ctypes.c_char_p, ctypes.c_char_p, ctypes.c_uint32]
+        self.lib.add_target_encoder.restype = EmptyReturn
+        self.lib.add_target_encoder.argtypes = [
+            ctypes.c_char_p,
+            ctypes.c_char_p,
+            ctypes.POINTER(ctypes.c_char_p),
+            ctypes.POINTER(ctypes.c_float),
+            ctypes.c_size_t,
+            ctypes.c_char_p,
+        ]
+
+        self.model: FileInfo = self.lib.load_model(str(TEST_SURML_PATH).encode('utf-8'))
+        self.file_id = self.model.file_id
+
+    def tearDown(self) -> None:
+        self.lib.free_file_info(self.model)
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.add_one_hot_encoder(None, None, None, 0)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+
+    def test_model_not_found(self):
+        outcome: EmptyReturn = self.lib.add_hashing_encoder(
+            "missing".encode('utf-8'), "num_floors".encode('utf-8'), 8
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Model not found", outcome.error_message.decode('utf-8'))
+
+    def test_add_one_hot_encoder(self):
+        vocabulary = ["one", "two", "three"]
+        outcome: EmptyReturn = self.lib.add_one_hot_encoder(
+            self.file_id, "num_floors".encode('utf-8'), string_array(vocabulary), len(vocabulary)
+        )
+        self.assertEqual(0, outcome.is_error)
+
+        outcome = self.lib.add_one_hot_encoder(self.file_id, "num_floors".encode('utf-8'), string_array([]), 0)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual(
+            "The one_hot encoder of column num_floors does not produce any columns",
+            outcome.error_message.decode('utf-8'),
+        )
+
+    def test_add_target_encoder(self):
+        categories = ["one", "two"]
+        targets = (ctypes.c_float * 2)(1.0, 2.0)
+        outcome: EmptyReturn = self.lib.add_target_encoder(
+            self.file_id,
+            "num_floors".encode('utf-8'),
+            string_array(categories),
+            targets,
+            len(categories),
+            "1.5".encode('utf-8'),
+        )
+        self.assertEqual(0, outcome.is_error)
+
+        outcome = self.lib.add_target_encoder(
+            self.file_id, "missing".encode('utf-8'), string_array(categories), targets, len(categories), None
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Column missing is not in the key bindings", outcome.error_message.decode('utf-8'))
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,54 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo
+from test_utils.routes import TEST_SURML_PATH
+
+
+class TestEngine(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.add_engine.restype = EmptyReturn
+        self.lib.add_engine.argtypes = [ctypes.c_char_p] * 2
+        self.lib.add_exporter.restype = EmptyReturn
+        self.lib.add_exporter.argtypes = [ctypes.c_char_p] * 4
+
+        self.model: FileInfo = self.lib.load_model(str(TEST_SURML_PATH).encode('utf-8'))
+        self.file_id = self.model.file_id
+
+    def tearDown(self) -> None:
+        self.lib.free_file_info(self.model)
+
+    def test_add_engine(self):
+        for engine in ["sklearn", "tensorflow", "keras", "onnx", "xgboost", "lightgbm", "candle"]:
+            outcome: EmptyReturn = self.lib.add_engine(self.file_id, engine.encode('utf-8'))
+            self.assertEqual(0, outcome.is_error)
+
+    def test_unknown_engine(self):
+        outcome: EmptyReturn = self.lib.add_engine(self.file_id, "caffe".encode('utf-8'))
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Unknown engine: caffe", outcome.error_message.decode('utf-8'))
+
+    def test_add_exporter(self):
+        outcome: EmptyReturn = self.lib.add_exporter(
+            self.file_id, "skl2onnx".encode('utf-8'), "1.16.0".encode('utf-8'), "17".encode('utf-8')
+        )
+        self.assertEqual(0, outcome.is_error)
+        outcome = self.lib.add_exporter(self.file_id, "tf2onnx".encode('utf-8'), None, None)
+        self.assertEqual(0, outcome.is_error)
+        outcome = self.lib.add_exporter(self.file_id, "tf2onnx".encode('utf-8'), None, "latest".encode('utf-8'))
+        self.assertEqual(1, outcome.is_error)
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.add_exporter(self.file_id, None, None, None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for name", outcome.error_message.decode('utf-8'))
+
+
+if __name__ == '__main__':
+    main()
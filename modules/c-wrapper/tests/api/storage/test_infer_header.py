@@ -0,0 +1,37 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, StringReturn
+from test_utils.routes import TEST_ONNX_FILE_PATH
+
+
+class TestInferHeader(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_cached_raw_model.restype = StringReturn
+        self.lib.load_cached_raw_model.argtypes = [ctypes.c_char_p]
+        self.lib.infer_header.restype = EmptyReturn
+        self.lib.infer_header.argtypes = [ctypes.c_char_p]
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.infer_header(None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+
+    def test_model_not_found(self):
+        outcome: EmptyReturn = self.lib.infer_header("missing".encode('utf-8'))
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Model not found", outcome.error_message.decode('utf-8'))
+
+    def test_success(self):
+        loaded: StringReturn = self.lib.load_cached_raw_model(str(TEST_ONNX_FILE_PATH).encode('utf-8'))
+        self.assertEqual(0, loaded.is_error)
+
+        outcome: EmptyReturn = self.lib.infer_header(loaded.string)
+        self.assertEqual(0, outcome.is_error)
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,126 @@
+import ctypes
+import json
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo, StringReturn, Vecf32Return, VecU8Return
+from test_utils.routes import SHOULD_BREAK_FILE, TEST_SURML_PATH
+
+
+class TestLoadHeader(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.load_header.restype = FileInfo
+        self.lib.load_header.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.list_metadata.restype = StringReturn
+        self.lib.list_metadata.argtypes = [ctypes.c_char_p]
+        self.lib.save_model.restype = EmptyReturn
+        self.lib.save_model.argtypes = [ctypes.c_char_p, ctypes.c_char_p]
+        self.lib.to_bytes.restype = VecU8Return
+        self.lib.to_bytes.argtypes = [ctypes.c_char_p]
+        self.lib.raw_compute.restype = Vecf32Return
+        self.lib.raw_compute.argtypes = [ctypes.c_char_p, ctypes.POINTER(ctypes.c_float), ctypes.c_size_t]
+        self.lib.buffered_compute.restype = Vecf32Return
+        self.lib.buffered_compute.argtypes = [
+            ctypes.c_char_p,
+            ctypes.POINTER(ctypes.c_float),
+            ctypes.c_size_t,
+            ctypes.POINTER(ctypes.c_char_p),
+            ctypes.c_int
+        ]
+        self.lib.free_vecf32_return.argtypes = [Vecf32Return]
+        self.lib.free_vec_u8.argtypes = [VecU8Return]
+        for function in [self.lib.sign_model, self.lib.verify_model, self.lib.require_signature]:
+            function.argtypes = [ctypes.c_char_p, ctypes.c_char_p, ctypes.c_size_t]
+            function.restype = EmptyReturn
+        self.lib.validate_model.restype = StringReturn
+        self.lib.validate_model.argtypes = [ctypes.c_char_p]
+
+    def test_null_pointer_protection(self):
+        outcome: FileInfo = self.lib.load_header(None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file path", outcome.error_message.decode('utf-8'))
+
+    def test_wrong_file(self):
+        outcome: FileInfo = self.lib.load_header(str(SHOULD_BREAK_FILE).encode('utf-8'))
+        self.assertEqual(1, outcome.is_error)
+        self.assertIn("failed to fill whole buffer", outcome.error_message.decode('utf-8'))
+
+    def test_success(self):
+        surml_file_path = str(TEST_SURML_PATH).encode('utf-8')
+        header: FileInfo = self.lib.load_header(surml_file_path)
+        self.assertEqual(0, header.is_error)
+        model: FileInfo = self.lib.load_model(surml_file_path)
+        self.assertEqual(model.name, header.name)
+        self.assertEqual(model.version, header.version)
+
+        outcome: StringReturn = self.lib.list_metadata(header.file_id)
+        self.assertEqual(0, outcome.is_error)
+        self.assertEqual({}, json.loads(outcome.string.decode('utf-8')))
+
+        self.lib.free_file_info(header)
+        self.lib.free_file_info(model)
+
+    def assert_header_only(self, error_message: bytes) -> None:
+        self.assertIn("Only the header of", error_message.decode('utf-8'))
+
+    def test_save_header_only(self):
+        header: FileInfo = self.lib.load_header(str(TEST_SURML_PATH).encode('utf-8'))
+        outcome: EmptyReturn = self.lib.save_model(
+            str(TEST_SURML_PATH.with_name("header_only.surml")).encode('utf-8'), header.file_id
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assert_header_only(outcome.error_message)
+        self.assertFalse(TEST_SURML_PATH.with_name("header_only.surml").exists())
+        self.lib.free_file_info(header)
+
+    def test_to_bytes_header_only(self):
+        header: FileInfo = self.lib.load_header(str(TEST_SURML_PATH).encode('utf-8'))
+        outcome: VecU8Return = self.lib.to_bytes(header.file_id)
+        self.assertEqual(1, outcome.is_error)
+        self.assert_header_only(outcome.error_message)
+        self.lib.free_vec_u8(outcome)
+        self.lib.free_file_info(header)
+
+    def test_raw_compute_header_only(self):
+        header: FileInfo = self.lib.load_header(str(TEST_SURML_PATH).encode('utf-8'))
+        input_data = (ctypes.c_float * 2)(1.0, 4.0)
+        outcome: Vecf32Return = self.lib.raw_compute(header.file_id, input_data, 2)
+        self.assertEqual(1, outcome.is_error)
+        self.assert_header_only(outcome.error_message)
+        self.lib.free_vecf32_return(outcome)
+        self.lib.free_file_info(header)
+
+    def test_buffered_compute_header_only(self):
+        header: FileInfo = self.lib.load_header(str(TEST_SURML_PATH).encode('utf-8'))
+        input_data = (ctypes.c_float * 2)(500.0, 2.0)
+        keys = (ctypes.c_char_p * 2)(b"squarefoot", b"num_floors")
+        outcome: Vecf32Return = self.lib.buffered_compute(header.file_id, input_data, 2, keys, 2)
+        self.assertEqual(1, outcome.is_error)
+        self.assert_header_only(outcome.error_message)
+        self.lib.free_vecf32_return(outcome)
+        self.lib.free_file_info(header)
+
+    def test_signing_header_only(self):
+        header: FileInfo = self.lib.load_header(str(TEST_SURML_PATH).encode('utf-8'))
+        key = bytes([7] * 32)
+        for function in [self.lib.sign_model, self.lib.verify_model, self.lib.require_signature]:
+            outcome: EmptyReturn = function(header.file_id, key, len(key))
+            self.assertEqual(1, outcome.is_error)
+            self.assert_header_only(outcome.error_message)
+        self.lib.free_file_info(header)
+
+    def test_validate_header_only(self):
+        header: FileInfo = self.lib.load_header(str(TEST_SURML_PATH).encode('utf-8'))
+        outcome: StringReturn = self.lib.validate_model(header.file_id)
+        self.assertEqual(1, outcome.is_error)
+        self.assert_header_only(outcome.error_message)
+        self.lib.free_file_info(header)
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,85 @@
+import ctypes
+import json
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo, StringReturn
+from test_utils.routes import TEST_SURML_PATH
+
+
+class TestMetadata(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.set_metadata.restype = EmptyReturn
+        self.lib.set_metadata.argtypes = [ctypes.c_char_p] * 3
+        self.lib.get_metadata.restype = StringReturn
+        self.lib.get_metadata.argtypes = [ctypes.c_char_p] * 2
+        self.lib.list_metadata.restype = StringReturn
+        self.lib.list_metadata.argtypes = [ctypes.c_char_p]
+        self.lib.remove_metadata.restype = EmptyReturn
+        self.lib.remove_metadata.argtypes = [ctypes.c_char_p] * 2
+
+        self.model: FileInfo = self.lib.load_model(str(TEST_SURML_PATH).encode('utf-8'))
+        self.file_id = self.model.file_id
+
+    def tearDown(self) -> None:
+        self.lib.free_file_info(self.model)
+
+    def get(self, path: str):
+        outcome: StringReturn = self.lib.get_metadata(self.file_id, path.encode('utf-8'))
+        self.assertEqual(0, outcome.is_error)
+        return json.loads(outcome.string.decode('utf-8'))
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.set_metadata(None, None, None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+        outcome: StringReturn = self.lib.get_metadata(self.file_id, None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for path", outcome.error_message.decode('utf-8'))
+
+    def test_model_not_found(self):
+        outcome: StringReturn = self.lib.list_metadata("missing".encode('utf-8'))
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Model not found", outcome.error_message.decode('utf-8'))
+
+    def test_set_get_and_remove(self):
+        metrics = {"rmse": 0.4, "auc": 0.9}
+        outcome: EmptyReturn = self.lib.set_metadata(
+            self.file_id, "metrics".encode('utf-8'), json.dumps(metrics).encode('utf-8')
+        )
+        self.assertEqual(0, outcome.is_error)
+        outcome = self.lib.set_metadata(
+            self.file_id, "tags".encode('utf-8'), json.dumps(["housing", True]).encode('utf-8')
+        )
+        self.assertEqual(0, outcome.is_error)
+
+        self.assertEqual(metrics, self.get("metrics"))
+        self.assertEqual(0.4, self.get("metrics.rmse"))
+        self.assertEqual("housing", self.get("tags.0"))
+        self.assertIsNone(self.get("missing"))
+
+        outcome: StringReturn = self.lib.list_metadata(self.file_id)
+        self.assertEqual(
+            {"metrics": metrics, "tags": ["housing", True]},
+            json.loads(outcome.string.decode('utf-8'))
+        )
+
+        outcome = self.lib.remove_metadata(self.file_id, "tags".encode('utf-8'))
+        self.assertEqual(0, outcome.is_error)
+        self.assertIsNone(self.get("tags"))
+
+    def test_invalid_value(self):
+        outcome: EmptyReturn = self.lib.set_metadata(
+            self.file_id, "license".encode('utf-8'), "MIT".encode('utf-8')
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertIn("Invalid metadata JSON", outcome.error_message.decode('utf-8'))
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,60 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo
+from test_utils.routes import TEST_SURML_PATH
+
+
+class TestNormaliserDefinition(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.add_normaliser_definition.restype = EmptyReturn
+        self.lib.add_normaliser_definition.argtypes = [ctypes.c_char_p] * 3
+        self.lib.add_output_normaliser_definition.restype = EmptyReturn
+        self.lib.add_output_normaliser_definition.argtypes = [ctypes.c_char_p] * 2
+
+        self.model: FileInfo = self.lib.load_model(str(TEST_SURML_PATH).encode('utf-8'))
+        self.file_id = self.model.file_id
+
+    def tearDown(self) -> None:
+        self.lib.free_file_info(self.model)
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.add_normaliser_definition(None, None, None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+
+    def test_add_definitions(self):
+        outcome: EmptyReturn = self.lib.add_normaliser_definition(
+            self.file_id, "squarefoot".encode('utf-8'), "clipping(min=0)|robust_scaling(median=3,iqr=2)".encode('utf-8')
+        )
+        self.assertEqual(0, outcome.is_error)
+        outcome = self.lib.add_output_normaliser_definition(
+            self.file_id, "yeo_johnson(lambda=0.5)|z_score(1,2)".encode('utf-8')
+        )
+        self.assertEqual(0, outcome.is_error)
+
+    def test_invalid_definitions(self):
+        outcome: EmptyReturn = self.lib.add_output_normaliser_definition(
+            self.file_id, "box_cox(alpha=1)".encode('utf-8')
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual(
+            "Normaliser box_cox does not accept the parameter alpha", outcome.error_message.decode('utf-8')
+        )
+        outcome = self.lib.add_normaliser_definition(
+            self.file_id, "squarefoot".encode('utf-8'), "z_score(mean=1)".encode('utf-8')
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual(
+            "Normaliser z_score is missing the parameter std_dev", outcome.error_message.decode('utf-8')
+        )
+
+
+if __name__ == '__main__':
+    main()
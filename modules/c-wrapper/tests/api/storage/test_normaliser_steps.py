@@ -0,0 +1,54 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo
+from test_utils.routes import TEST_SURML_PATH
+
+
+class TestNormaliserSteps(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.add_normaliser_step.restype = EmptyReturn
+        self.lib.add_normaliser_step.argtypes = [
+            ctypes.c_char_p, ctypes.c_char_p, ctypes.c_char_p, ctypes.c_float, ctypes.c_float
+        ]
+        self.lib.add_output_normaliser_step.restype = EmptyReturn
+        self.lib.add_output_normaliser_step.argtypes = [
+            ctypes.c_char_p, ctypes.c_char_p, ctypes.c_float, ctypes.c_float
+        ]
+
+        self.model: FileInfo = self.lib.load_model(str(TEST_SURML_PATH).encode('utf-8'))
+        self.file_id = self.model.file_id
+
+    def tearDown(self) -> None:
+        self.lib.free_file_info(self.model)
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.add_normaliser_step(None, None, None, 0.0, 1.0)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+
+    def test_add_steps(self):
+        for label in ["clipping", "z_score"]:
+            outcome: EmptyReturn = self.lib.add_normaliser_step(
+                self.file_id, "squarefoot".encode('utf-8'), label.encode('utf-8'), 0.0, 1.0
+            )
+            self.assertEqual(0, outcome.is_error)
+        outcome = self.lib.add_output_normaliser_step(self.file_id, "z_score".encode('utf-8'), 0.0, 1.0)
+        self.assertEqual(0, outcome.is_error)
+
+    def test_unknown_normaliser(self):
+        outcome: EmptyReturn = self.lib.add_output_normaliser_step(
+            self.file_id, "unknown".encode('utf-8'), 0.0, 1.0
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Unknown normaliser type: unknown", outcome.error_message.decode('utf-8'))
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,60 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo
+from test_utils.routes import TEST_SURML_PATH
+
+
+class TestProvenance(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.add_origin.restype = EmptyReturn
+        self.lib.add_origin.argtypes = [ctypes.c_char_p] * 2
+        self.lib.add_provenance.restype = EmptyReturn
+        self.lib.add_provenance.argtypes = [ctypes.c_char_p] * 5
+
+        self.model: FileInfo = self.lib.load_model(str(TEST_SURML_PATH).encode('utf-8'))
+        self.file_id = self.model.file_id
+
+    def tearDown(self) -> None:
+        self.lib.free_file_info(self.model)
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.add_provenance(None, None, None, None, None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+
+    def test_add_origin(self):
+        outcome: EmptyReturn = self.lib.add_origin(self.file_id, "mlflow://runs/42".encode('utf-8'))
+        self.assertEqual(0, outcome.is_error)
+        outcome = self.lib.add_origin(self.file_id, "elsewhere".encode('utf-8'))
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("invalid origin: elsewhere", outcome.error_message.decode('utf-8'))
+
+    def test_add_provenance(self):
+        outcome: EmptyReturn = self.lib.add_provenance(
+            self.file_id,
+            "run-7".encode('utf-8'),
+            "9edbc98".encode('utf-8'),
+            ("ab" * 32).encode('utf-8'),
+            "2024-01-31T12:00:00Z".encode('utf-8'),
+        )
+        self.assertEqual(0, outcome.is_error)
+        outcome = self.lib.add_provenance(self.file_id, "run-8".encode('utf-8'), None, None, None)
+        self.assertEqual(0, outcome.is_error)
+
+    def test_invalid_provenance(self):
+        outcome: EmptyReturn = self.lib.add_provenance(
+            self.file_id, None, None, "abc".encode('utf-8'), None
+        )
+        self.assertEqual(1, outcome.is_error)
+        self.assertIn("Parent digest must be a hex encoded SHA-256 digest", outcome.error_message.decode('utf-8'))
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,92 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo, Vecf32Return
+from test_utils.routes import TEST_SURML_PATH
+
+
+SIGNING_KEY = bytes([7] * 32)
+VERIFYING_KEY = bytes.fromhex("ea4a6c63e29c520abef5507b132ec5f9954776aebebe7b92421eea691446d22c")
+
+
+class TestSigning(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        for function in [self.lib.sign_model, self.lib.verify_model, self.lib.require_signature]:
+            function.argtypes = [ctypes.c_char_p, ctypes.c_char_p, ctypes.c_size_t]
+            function.restype = EmptyReturn
+        self.lib.add_description.argtypes = [ctypes.c_char_p, ctypes.c_char_p]
+        self.lib.add_description.restype = EmptyReturn
+        self.lib.raw_compute.argtypes = [ctypes.c_char_p, ctypes.POINTER(ctypes.c_float), ctypes.c_size_t]
+        self.lib.raw_compute.restype = Vecf32Return
+        self.lib.free_vecf32_return.argtypes = [Vecf32Return]
+
+        self.model: FileInfo = self.lib.load_model(str(TEST_SURML_PATH).encode('utf-8'))
+        self.file_id = self.model.file_id
+
+    def tearDown(self) -> None:
+        self.lib.free_file_info(self.model)
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.sign_model(None, SIGNING_KEY, len(SIGNING_KEY))
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+
+        outcome: EmptyReturn = self.lib.sign_model(self.file_id, None, 0)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for signing key", outcome.error_message.decode('utf-8'))
+
+    def test_wrong_key_length(self):
+        outcome: EmptyReturn = self.lib.sign_model(self.file_id, SIGNING_KEY[:5], 5)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Signing key must be 32 bytes", outcome.error_message.decode('utf-8'))
+
+    def test_unsigned_model(self):
+        outcome: EmptyReturn = self.lib.verify_model(self.file_id, bytes(32), 32)
+        self.assertEqual(1, outcome.is_error)
+
+    def test_untrusted_key(self):
+        outcome: EmptyReturn = self.lib.sign_model(self.file_id, SIGNING_KEY, len(SIGNING_KEY))
+        self.assertEqual(0, outcome.is_error)
+
+        outcome = self.lib.verify_model(self.file_id, bytes(32), 32)
+        self.assertEqual(1, outcome.is_error)
+
+    def test_keys_length(self):
+        outcome: EmptyReturn = self.lib.verify_model(self.file_id, bytes(33), 33)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Trusted keys must be a multiple of 32 bytes", outcome.error_message.decode('utf-8'))
+
+    def test_require_signature_of_unsigned_model(self):
+        outcome: EmptyReturn = self.lib.require_signature(self.file_id, VERIFYING_KEY, len(VERIFYING_KEY))
+        self.assertEqual(1, outcome.is_error)
+
+    def test_require_signature(self):
+        outcome: EmptyReturn = self.lib.sign_model(self.file_id, SIGNING_KEY, len(SIGNING_KEY))
+        self.assertEqual(0, outcome.is_error)
+        outcome = self.lib.verify_model(self.file_id, VERIFYING_KEY, len(VERIFYING_KEY))
+        self.assertEqual(0, outcome.is_error)
+        outcome = self.lib.require_signature(self.file_id, VERIFYING_KEY, len(VERIFYING_KEY))
+        self.assertEqual(0, outcome.is_error)
+
+        input_data = (ctypes.c_float * 2)(1.0, 4.0)
+        result: Vecf32Return = self.lib.raw_compute(self.file_id, input_data, 2)
+        self.assertEqual(0, result.is_error)
+        self.lib.free_vecf32_return(result)
+
+        # changing the model after requiring a signature stops it from being computed
+        outcome = self.lib.add_description(self.file_id, "changed".encode('utf-8'))
+        self.assertEqual(0, outcome.is_error)
+        result = self.lib.raw_compute(self.file_id, input_data, 2)
+        self.assertEqual(1, result.is_error)
+        self.assertIn("changed since it was signed", result.error_message.decode('utf-8'))
+        self.lib.free_vecf32_return(result)
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,47 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo, StringReturn
+from test_utils.routes import TEST_SURML_PATH
+
+
+class TestUnloadModel(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.load_header.restype = FileInfo
+        self.lib.load_header.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.unload_model.restype = EmptyReturn
+        self.lib.unload_model.argtypes = [ctypes.c_char_p]
+        self.lib.list_metadata.restype = StringReturn
+        self.lib.list_metadata.argtypes = [ctypes.c_char_p]
+
+    def test_null_pointer_protection(self):
+        outcome: EmptyReturn = self.lib.unload_model(None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+
+    def test_unload(self):
+        surml_file_path = str(TEST_SURML_PATH).encode('utf-8')
+        for loader in [self.lib.load_model, self.lib.load_header]:
+            info: FileInfo = loader(surml_file_path)
+            self.assertEqual(0, info.is_error)
+
+            outcome: EmptyReturn = self.lib.unload_model(info.file_id)
+            self.assertEqual(0, outcome.is_error)
+
+            # the id cannot be used once the model is unloaded
+            listed: StringReturn = self.lib.list_metadata(info.file_id)
+            self.assertEqual(1, listed.is_error)
+            outcome = self.lib.unload_model(info.file_id)
+            self.assertEqual(1, outcome.is_error)
+            self.assertEqual("Model not found", outcome.error_message.decode('utf-8'))
+            self.lib.free_file_info(info)
+
+
+if __name__ == '__main__':
+    main()
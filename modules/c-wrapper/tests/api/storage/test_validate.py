@@ -0,0 +1,45 @@
+import ctypes
+from unittest import TestCase, main
+
+from test_utils.c_lib_loader import load_library
+from test_utils.return_structs import EmptyReturn, FileInfo, StringReturn
+from test_utils.routes import TEST_SURML_PATH
+
+
+class TestValidate(TestCase):
+
+    def setUp(self) -> None:
+        self.lib = load_library()
+        self.lib.load_model.restype = FileInfo
+        self.lib.load_model.argtypes = [ctypes.c_char_p]
+        self.lib.free_file_info.argtypes = [FileInfo]
+        self.lib.validate_model.restype = StringReturn
+        self.lib.validate_model.argtypes = [ctypes.c_char_p]
+        self.lib.add_column.restype = EmptyReturn
+        self.lib.add_column.argtypes = [ctypes.c_char_p, ctypes.c_char_p]
+
+        self.model: FileInfo = self.lib.load_model(str(TEST_SURML_PATH).encode('utf-8'))
+        self.file_id = self.model.file_id
+
+    def tearDown(self) -> None:
+        self.lib.free_file_info(self.model)
+
+    def test_null_pointer_protection(self):
+        outcome: StringReturn = self.lib.validate_model(None)
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Received a null pointer for file id", outcome.error_message.decode('utf-8'))
+
+    def test_model_not_found(self):
+        outcome: StringReturn = self.lib.validate_model("missing".encode('utf-8'))
+        self.assertEqual(1, outcome.is_error)
+        self.assertEqual("Model not found", outcome.error_message.decode('utf-8'))
+
+    def test_column_count_mismatch(self):
+        self.lib.add_column(self.file_id, "extra".encode('utf-8'))
+        outcome: StringReturn = self.lib.validate_model(self.file_id)
+        self.assertEqual(0, outcome.is_error)
+        self.assertIn("error [onnx input", outcome.string.decode('utf-8'))
+
+
+if __name__ == '__main__':
+    main()
@@ -1,7 +1,7 @@
 """
 Defines all the C structs that are used in the tests.
 """
-from ctypes import Structure, c_char_p, c_int, c_size_t, POINTER, c_float
+from ctypes import Structure, c_char_p, c_int, c_size_t, POINTER, c_float, c_uint8
 
 
 class StringReturn(Structure):
@@ -61,4 +61,16 @@ class Vecf32Return(Structure):
         ("capacity", c_size_t),            # Capacity of the array
         ("is_error", c_int),               # Indicates if it's an error
         ("error_message", c_char_p),       # Optional error message
+        ("shape", POINTER(c_size_t)),      # Pointer to the shape of the output
+        ("shape_length", c_size_t),        # Number of dimensions in the shape
+    ]
+
+
+class VecU8Return(Structure):
+    _fields_ = [
+        ("data", POINTER(c_uint8)),  # Pointer to the bytes
+        ("length", c_size_t),        # Length of the bytes
+        ("capacity", c_size_t),      # Capacity of the bytes
+        ("is_error", c_int),         # Indicates if it's an error
+        ("error_message", c_char_p), # Optional error message
     ]
@@ -0,0 +1,283 @@
+//! Defines a least recently used cache of compiled models keyed by the digest of the model.
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use crate::execution::compiled::CompiledModel;
+use crate::safe_eject;
+use crate::storage::header::Header;
+use crate::storage::integrity;
+use crate::storage::surml_file::SurMlFile;
+
+/// Holds the most recently used compiled models so hot models do not have to rebuild their sessions. When the cache
+/// is full the least recently used model is dropped.
+///
+/// # Fields
+/// * `capacity` - The maximum number of models held in the cache.
+/// * `entries` - The cached models ordered from least to most recently used.
+/// * `compiling` - A lock per digest of the models being compiled so concurrent misses for the same model compile it
+///   once.
+pub struct SessionCache {
+    capacity: usize,
+    entries: Mutex<VecDeque<Arc<CompiledModel>>>,
+    compiling: Mutex<HashMap<Vec<u8>, Arc<Mutex<()>>>>,
+}
+
+impl SessionCache {
+    /// Creates a new empty cache.
+    ///
+    /// # Arguments
+    /// * `capacity` - The maximum number of models held in the cache.
+    ///
+    /// # Returns
+    /// A new `SessionCache`.
+    pub fn new(capacity: usize) -> Self {
+        SessionCache {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            compiling: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Gets a compiled model from the cache marking it as the most recently used.
+    ///
+    /// # Arguments
+    /// * `digest` - The digest of the model.
+    ///
+    /// # Returns
+    /// The compiled model if it is in the cache.
+    pub fn get(&self, digest: &[u8]) -> Result<Option<Arc<CompiledModel>>, SurrealError> {
+        let mut entries = self.lock()?;
+        let position = match entries.iter().position(|model| model.digest() == digest) {
+            Some(position) => position,
+            None => return Ok(None),
+        };
+        let model = entries.remove(position);
+        if let Some(model) = &model {
+            entries.push_back(model.clone());
+        }
+        Ok(model)
+    }
+
+    /// Adds a compiled model to the cache, dropping the least recently used model if the cache is full. A model
+    /// with the same digest that is already cached is replaced.
+    ///
+    /// # Arguments
+    /// * `model` - The compiled model.
+    ///
+    /// # Returns
+    /// The shared compiled model.
+    pub fn insert(&self, model: CompiledModel) -> Result<Arc<CompiledModel>, SurrealError> {
+        let model = Arc::new(model);
+        if self.capacity == 0 {
+            return Ok(model);
+        }
+        let mut entries = self.lock()?;
+        entries.retain(|cached| cached.digest() != model.digest());
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(model.clone());
+        Ok(model)
+    }
+
+    /// Gets the compiled model for a loaded model from the cache, building and caching its session if it is not
+    /// cached. The digest recorded in the file is used as the key so a loaded model that has not been changed is not
+    /// hashed again.
+    ///
+    /// # Arguments
+    /// * `surml_file` - The loaded model.
+    ///
+    /// # Returns
+    /// The shared compiled model.
+    pub fn get_or_compile(
+        &self,
+        surml_file: SurMlFile,
+    ) -> Result<Arc<CompiledModel>, SurrealError> {
+        let digest = surml_file.digest()?;
+        self.get_or_compile_with(digest, || CompiledModel::new(surml_file))
+    }
+
+    /// Gets the compiled model for a `surml` file from the cache. Only the header is read to look the model up by the
+    /// digest recorded in it, so the model bytes are only read if the model is not cached. Files without a recorded
+    /// digest are loaded in full and hashed.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path to the `surml` file.
+    ///
+    /// # Returns
+    /// The shared compiled model.
+    pub fn get_or_compile_file(&self, file_path: &str) -> Result<Arc<CompiledModel>, SurrealError> {
+        let file = safe_eject!(File::open(file_path), SurrealErrorStatus::NotFound);
+        match integrity::recorded_digest(&Header::read_section(file)?)? {
+            Some((_, digest)) => self.get_or_compile_with(digest, || {
+                CompiledModel::new(SurMlFile::from_file(file_path)?)
+            }),
+            None => self.get_or_compile(SurMlFile::from_file(file_path)?),
+        }
+    }
+
+    /// Gets a compiled model from the cache, compiling it on a miss. Concurrent misses for the same digest wait for
+    /// the first caller to compile the model instead of each compiling it. The session is built without holding the
+    /// lock on the entries so other models can be served in the meantime.
+    ///
+    /// # Arguments
+    /// * `digest` - The digest of the model.
+    /// * `compile` - Builds the compiled model on a miss.
+    ///
+    /// # Returns
+    /// The shared compiled model.
+    fn get_or_compile_with(
+        &self,
+        digest: Vec<u8>,
+        compile: impl FnOnce() -> Result<CompiledModel, SurrealError>,
+    ) -> Result<Arc<CompiledModel>, SurrealError> {
+        if let Some(model) = self.get(&digest)? {
+            return Ok(model);
+        }
+        let guard = Self::lock_mutex(&self.compiling)?
+            .entry(digest.clone())
+            .or_default()
+            .clone();
+        let outcome = {
+            let _compiling = Self::lock_mutex(&guard)?;
+
+            // another caller may have compiled the model while this one was waiting
+            match self.get(&digest)? {
+                Some(model) => Ok(model),
+                None => compile().and_then(|model| self.insert(model)),
+            }
+        };
+        Self::lock_mutex(&self.compiling)?.remove(&digest);
+        outcome
+    }
+
+    /// The number of models in the cache.
+    pub fn len(&self) -> Result<usize, SurrealError> {
+        Ok(self.lock()?.len())
+    }
+
+    /// Returns `true` if there are no models in the cache.
+    pub fn is_empty(&self) -> Result<bool, SurrealError> {
+        Ok(self.lock()?.is_empty())
+    }
+
+    /// Removes every model from the cache.
+    pub fn clear(&self) -> Result<(), SurrealError> {
+        self.lock()?.clear();
+        Ok(())
+    }
+
+    /// Locks the entries of the cache.
+    fn lock(&self) -> Result<MutexGuard<'_, VecDeque<Arc<CompiledModel>>>, SurrealError> {
+        Self::lock_mutex(&self.entries)
+    }
+
+    /// Locks one of the mutexes of the cache.
+    fn lock_mutex<T>(mutex: &Mutex<T>) -> Result<MutexGuard<'_, T>, SurrealError> {
+        match mutex.lock() {
+            Ok(guard) => Ok(guard),
+            Err(_) => Err(SurrealError::new(
+                "The session cache lock is poisoned".to_string(),
+                SurrealErrorStatus::Unknown,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_empty_cache() {
+        let cache = SessionCache::new(2);
+        assert!(cache.is_empty().unwrap());
+        assert!(cache.get(&[1, 2, 3]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_failed_compile_is_not_cached() {
+        let cache = SessionCache::new(2);
+        let error = cache
+            .get_or_compile_with(vec![1, 2, 3], || {
+                Err(SurrealError::new(
+                    "invalid model".to_string(),
+                    SurrealErrorStatus::BadRequest,
+                ))
+            })
+            .err()
+            .unwrap();
+        assert_eq!(error.message, "invalid model");
+        assert!(cache.is_empty().unwrap());
+        assert!(cache.compiling.lock().unwrap().is_empty());
+
+        assert_eq!(
+            cache
+                .get_or_compile_file("./stash/missing.surml")
+                .err()
+                .unwrap()
+                .status,
+            SurrealErrorStatus::NotFound
+        );
+    }
+
+    #[cfg(feature = "sklearn-tests")]
+    #[test]
+    fn test_concurrent_misses_compile_once() {
+        use crate::execution::session::set_environment;
+
+        set_environment().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("linear.surml");
+        let path = path.to_str().unwrap().to_string();
+        SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml")
+            .unwrap()
+            .write(&path)
+            .unwrap();
+        let cache = Arc::new(SessionCache::new(2));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let cache = cache.clone();
+                let path = path.clone();
+                std::thread::spawn(move || cache.get_or_compile_file(&path).unwrap())
+            })
+            .collect();
+        let models: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        for model in &models {
+            assert!(Arc::ptr_eq(model, &models[0]));
+        }
+        assert_eq!(cache.len().unwrap(), 1);
+    }
+
+    #[cfg(feature = "sklearn-tests")]
+    #[test]
+    fn test_least_recently_used_is_dropped() {
+        use crate::execution::session::set_environment;
+
+        set_environment().unwrap();
+        let load = |name: &str| {
+            let mut file =
+                SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+            file.header.add_name(name.to_string());
+            file
+        };
+        let cache = SessionCache::new(2);
+
+        let first = cache.get_or_compile(load("first")).unwrap();
+        let second = cache.get_or_compile(load("second")).unwrap();
+        assert_eq!(cache.len().unwrap(), 2);
+
+        // using the first model makes the second the least recently used
+        let again = cache.get_or_compile(load("first")).unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+
+        let _third = cache.get_or_compile(load("third")).unwrap();
+        assert_eq!(cache.len().unwrap(), 2);
+        assert!(cache.get(first.digest()).unwrap().is_some());
+        assert!(cache.get(second.digest()).unwrap().is_none());
+    }
+}
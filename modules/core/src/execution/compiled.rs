@@ -0,0 +1,331 @@
+//! Defines a compiled model that builds its ONNX session once and serves computations from it.
+use std::collections::HashMap;
+
+use ndarray::ArrayD;
+use ort::session::Session;
+
+use crate::errors::error::SurrealError;
+use crate::execution::compute::{
+    batch_tensor_from_header, buffered_compute_named_with, denormalise_output_tensor,
+    denormalise_outputs, input_shape, input_vector_from_header, input_vector_from_values,
+    name_outputs, normalise_inputs, run_session, run_session_batch, run_session_named,
+    run_session_shaped, run_session_typed, session_input_dims,
+};
+use crate::execution::session::get_session;
+use crate::execution::tensor::TensorValue;
+use crate::storage::header::column::FeatureValue;
+use crate::storage::header::Header;
+use crate::storage::signature::VerifyingKey;
+use crate::storage::surml_file::SurMlFile;
+
+/// A loaded model with its ONNX session already built. Building the session parses and optimises the graph, so a
+/// `CompiledModel` should be kept around and shared between calls. It is `Send` and `Sync` so it can be shared across
+/// threads behind an `Arc` and serve concurrent computations.
+///
+/// # Fields
+/// * `header` - The header of the model holding the key bindings and normalisers.
+/// * `session` - The ONNX session built from the model.
+/// * `input_dims` - The dimensions of the model input where dynamic dimensions are negative.
+/// * `digest` - The SHA-256 digest of the header and model that identifies the model.
+pub struct CompiledModel {
+    pub header: Header,
+    session: Session,
+    input_dims: Vec<i64>,
+    digest: Vec<u8>,
+}
+
+impl CompiledModel {
+    /// Builds the session for a loaded model.
+    ///
+    /// # Arguments
+    /// * `surml_file` - The loaded model.
+    ///
+    /// # Returns
+    /// A new `CompiledModel`.
+    pub fn new(surml_file: SurMlFile) -> Result<Self, SurrealError> {
+        let digest = surml_file.digest()?;
+        Self::build(surml_file.header, &surml_file.model, digest)
+    }
+
+    /// Builds the session for a borrowed model, copying the header so the loaded model can still be changed and saved.
+    ///
+    /// # Arguments
+    /// * `surml_file` - The loaded model.
+    ///
+    /// # Returns
+    /// A new `CompiledModel`.
+    pub fn from_file(surml_file: &SurMlFile) -> Result<Self, SurrealError> {
+        Self::build(
+            surml_file.header.clone(),
+            &surml_file.model,
+            surml_file.digest()?,
+        )
+    }
+
+    /// Builds the session for a header and model.
+    ///
+    /// # Arguments
+    /// * `header` - The header of the model.
+    /// * `model` - The bytes of the model.
+    /// * `digest` - The digest of the header and model.
+    ///
+    /// # Returns
+    /// A new `CompiledModel`.
+    fn build(header: Header, model: &[u8], digest: Vec<u8>) -> Result<Self, SurrealError> {
+        let session = get_session(model)?;
+        let input_dims = session_input_dims(&session)?;
+        Ok(CompiledModel {
+            header,
+            session,
+            input_dims,
+            digest,
+        })
+    }
+
+    /// Builds the session for a loaded model only if the model is signed by one of the trusted keys.
+    ///
+    /// # Arguments
+    /// * `surml_file` - The loaded model.
+    /// * `trusted_keys` - The public keys that are allowed to sign models.
+    ///
+    /// # Returns
+    /// A new `CompiledModel`, or an error with an `Unauthorized` status if the model is unsigned or wrongly signed.
+    pub fn new_verified(
+        surml_file: SurMlFile,
+        trusted_keys: &[VerifyingKey],
+    ) -> Result<Self, SurrealError> {
+        surml_file.verify(trusted_keys)?;
+        Self::new(surml_file)
+    }
+
+    /// The SHA-256 digest of the header and model that the session was built from.
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// Performs a raw computation on the compiled model.
+    ///
+    /// # Arguments
+    /// * `tensor` - The input tensor to the model.
+    ///
+    /// # Returns
+    /// The computed output tensor from the model.
+    pub fn raw_compute(&self, tensor: ArrayD<f32>) -> Result<Vec<f32>, SurrealError> {
+        let dims = input_shape(&self.header, &self.input_dims, tensor.len())?;
+        run_session(&self.session, tensor, dims)
+    }
+
+    /// Performs a raw computation on the compiled model with the input reshaped to dimensions given by the caller
+    /// instead of the dimensions in the header or model.
+    ///
+    /// # Arguments
+    /// * `tensor` - The input tensor to the model.
+    /// * `dims` - The dimensions to reshape the input tensor to.
+    ///
+    /// # Returns
+    /// The computed output tensor from the model.
+    pub(crate) fn raw_compute_with_dims(
+        &self,
+        tensor: ArrayD<f32>,
+        dims: Vec<usize>,
+    ) -> Result<Vec<f32>, SurrealError> {
+        run_session(&self.session, tensor, dims)
+    }
+
+    /// Applies the normalisers in the header to the inputs, performs a raw computation on the compiled model and then
+    /// applies the inverse normaliser of each output if there is one.
+    ///
+    /// # Arguments
+    /// * `input_values` - A hashmap of keys and values that will be used to create the input tensor.
+    ///
+    /// # Returns
+    /// The value of each output of the model keyed by the output names in the header.
+    pub fn buffered_compute(
+        &self,
+        input_values: &mut HashMap<String, f32>,
+    ) -> Result<HashMap<String, f32>, SurrealError> {
+        let output = self.buffered_compute_positional(input_values)?;
+        name_outputs(&self.header, output)
+    }
+
+    /// Performs a buffered computation returning the outputs in the order the model returns them rather than keyed by
+    /// name.
+    ///
+    /// # Arguments
+    /// * `input_values` - A hashmap of keys and values that will be used to create the input tensor.
+    ///
+    /// # Returns
+    /// The computed output tensor from the model.
+    pub fn buffered_compute_positional(
+        &self,
+        input_values: &mut HashMap<String, f32>,
+    ) -> Result<Vec<f32>, SurrealError> {
+        normalise_inputs(&self.header, input_values)?;
+        let buffer = input_vector_from_header(&self.header, input_values.clone())?;
+        let output = self.raw_compute(ndarray::arr1::<f32>(&buffer).into_dyn())?;
+        denormalise_outputs(&self.header, output)
+    }
+
+    /// Coerces input values of any type to the declared column types in the header, expands encoded string columns
+    /// into their numeric columns and then performs a buffered computation on the compiled model.
+    ///
+    /// # Arguments
+    /// * `input_values` - A hashmap of keys and values of any type.
+    ///
+    /// # Returns
+    /// The computed output tensor from the model.
+    pub fn buffered_compute_values(
+        &self,
+        input_values: &HashMap<String, FeatureValue>,
+    ) -> Result<Vec<f32>, SurrealError> {
+        let buffer = input_vector_from_values(&self.header, input_values)?;
+        let output = self.raw_compute(ndarray::arr1::<f32>(&buffer).into_dyn())?;
+        denormalise_outputs(&self.header, output)
+    }
+
+    /// Performs a raw computation on the compiled model keeping the shape of the output.
+    ///
+    /// # Arguments
+    /// * `tensor` - The input tensor to the model.
+    ///
+    /// # Returns
+    /// The first output tensor of the model with its shape.
+    pub fn raw_compute_shaped(&self, tensor: ArrayD<f32>) -> Result<ArrayD<f32>, SurrealError> {
+        let dims = input_shape(&self.header, &self.input_dims, tensor.len())?;
+        run_session_shaped(&self.session, tensor, dims)
+    }
+
+    /// Performs a buffered computation on the compiled model keeping the shape of the output.
+    ///
+    /// # Arguments
+    /// * `input_values` - A hashmap of keys and values that will be used to create the input tensor.
+    ///
+    /// # Returns
+    /// The first output tensor of the model with its shape in the scale of the training data.
+    pub fn buffered_compute_shaped(
+        &self,
+        input_values: &mut HashMap<String, f32>,
+    ) -> Result<ArrayD<f32>, SurrealError> {
+        normalise_inputs(&self.header, input_values)?;
+        let buffer = input_vector_from_header(&self.header, input_values.clone())?;
+        let output = self.raw_compute_shaped(ndarray::arr1::<f32>(&buffer).into_dyn())?;
+        denormalise_output_tensor(&self.header, output)
+    }
+
+    /// Performs a raw computation on a batch of rows in a single session call if the model has a dynamic batch axis.
+    ///
+    /// # Arguments
+    /// * `tensor` - The input tensor with the rows along the first axis, for example `[N, features]`.
+    ///
+    /// # Returns
+    /// One output vector per row.
+    pub fn raw_compute_batch(&self, tensor: ArrayD<f32>) -> Result<Vec<Vec<f32>>, SurrealError> {
+        run_session_batch(&self.session, &self.header, &self.input_dims, tensor)
+    }
+
+    /// Applies the normalisers in the header column-wise to a batch of rows, performs a raw computation on the batch
+    /// and applies the inverse of the output normaliser to the outputs of every row.
+    ///
+    /// # Arguments
+    /// * `rows` - A hashmap of keys and values for each row.
+    ///
+    /// # Returns
+    /// One output vector per row in the same order as the rows.
+    pub fn buffered_compute_batch(
+        &self,
+        rows: &[HashMap<String, f32>],
+    ) -> Result<Vec<Vec<f32>>, SurrealError> {
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+        let tensor = batch_tensor_from_header(&self.header, rows)?;
+        self.raw_compute_batch(tensor)?
+            .into_iter()
+            .map(|output| denormalise_outputs(&self.header, output))
+            .collect()
+    }
+
+    /// Performs a raw computation feeding each tensor to the model input of the same name.
+    ///
+    /// # Arguments
+    /// * `inputs` - The tensors keyed by the name of the input in the ONNX graph.
+    ///
+    /// # Returns
+    /// The tensor outputs of the model keyed by output name. Outputs that are not tensors are skipped.
+    pub fn raw_compute_named(
+        &self,
+        inputs: HashMap<String, ArrayD<f32>>,
+    ) -> Result<HashMap<String, ArrayD<f32>>, SurrealError> {
+        run_session_named(&self.session, inputs)
+    }
+
+    /// Performs a raw computation with typed tensors so non `f32` inputs and outputs keep their element type.
+    ///
+    /// # Arguments
+    /// * `inputs` - The typed tensors keyed by the name of the input in the ONNX graph.
+    ///
+    /// # Returns
+    /// The typed tensor outputs of the model keyed by output name. Outputs that are not tensors are skipped.
+    pub fn raw_compute_typed(
+        &self,
+        inputs: HashMap<String, TensorValue>,
+    ) -> Result<HashMap<String, TensorValue>, SurrealError> {
+        run_session_typed(&self.session, inputs)
+    }
+
+    /// Applies the normalisers in the header, feeds the columns to the named inputs they are bound to and returns
+    /// every output of the model.
+    ///
+    /// # Arguments
+    /// * `input_values` - A hashmap of keys and values that will be used to create the input tensors.
+    ///
+    /// # Returns
+    /// The tensor outputs of the model keyed by output name.
+    pub fn buffered_compute_named(
+        &self,
+        input_values: &mut HashMap<String, f32>,
+    ) -> Result<HashMap<String, ArrayD<f32>>, SurrealError> {
+        buffered_compute_named_with(&self.header, &self.session, input_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        assert_send_sync::<CompiledModel>();
+    }
+
+    #[cfg(feature = "sklearn-tests")]
+    #[test]
+    fn test_concurrent_compute_linear_sklearn() {
+        use crate::execution::session::set_environment;
+        use std::sync::Arc;
+
+        set_environment().unwrap();
+        let file = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+        let model = Arc::new(CompiledModel::new(file).unwrap());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let model = model.clone();
+                std::thread::spawn(move || {
+                    let mut input_values = HashMap::new();
+                    input_values.insert(String::from("squarefoot"), 1000.0);
+                    input_values.insert(String::from("num_floors"), 2.0);
+                    let raw = ndarray::arr1::<f32>(&[1000.0, 2.0]).into_dyn();
+                    assert_eq!(model.raw_compute(raw).unwrap(), vec![985.57745]);
+                    model.buffered_compute(&mut input_values).unwrap()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap().len(), 1);
+        }
+    }
+}
@@ -1,23 +1,80 @@
 //! Defines the operations around performing computations on a loaded model.
+use crate::storage::header::column::FeatureValue;
+use crate::storage::header::input_dims::{Dimension, InputDims};
+use crate::storage::header::Header;
+use crate::storage::signature::VerifyingKey;
 use crate::storage::surml_file::SurMlFile;
 use ndarray::ArrayD;
-use ort::session::Session;
+use ort::session::{Session, SessionInputValue, SessionInputs};
 use ort::value::ValueType;
 use std::collections::HashMap;
+use std::ops::Deref;
 
 use crate::errors::error::{SurrealError, SurrealErrorStatus};
-use crate::execution::session::get_session;
+use crate::execution::compiled::CompiledModel;
+use crate::execution::tensor::TensorValue;
 use crate::safe_eject;
 
-/// A wrapper for the loaded machine learning model so we can perform computations on the loaded model.
+/// A wrapper for the loaded machine learning model so we can perform computations on the loaded model. Every
+/// computation is served by a `CompiledModel`, so the session is built once when the wrapper is created rather than on
+/// every call.
 ///
 /// # Attributes
-/// * `surml_file` - The loaded machine learning model using interior mutability to allow mutable access to the model
+/// * `compiled` - The compiled model the computations are delegated to, either built by the wrapper or borrowed.
 pub struct ModelComputation<'a> {
-    pub surml_file: &'a mut SurMlFile,
+    compiled: Compiled<'a>,
 }
 
-impl ModelComputation<'_> {
+/// The compiled model behind a `ModelComputation`.
+enum Compiled<'a> {
+    Built(Box<CompiledModel>),
+    Borrowed(&'a CompiledModel),
+}
+
+impl<'a> ModelComputation<'a> {
+    /// Creates a new `ModelComputation` building the session for the loaded model.
+    ///
+    /// # Arguments
+    /// * `surml_file` - The loaded machine learning model.
+    ///
+    /// # Returns
+    /// A new `ModelComputation`.
+    pub fn new(surml_file: &SurMlFile) -> Result<Self, SurrealError> {
+        Ok(ModelComputation {
+            compiled: Compiled::Built(Box::new(CompiledModel::from_file(surml_file)?)),
+        })
+    }
+
+    /// Creates a new `ModelComputation` only if the loaded model is signed by one of the trusted keys.
+    ///
+    /// # Arguments
+    /// * `surml_file` - The loaded machine learning model.
+    /// * `trusted_keys` - The public keys that are allowed to sign models.
+    ///
+    /// # Returns
+    /// A new `ModelComputation`, or an error with an `Unauthorized` status if the model is unsigned or wrongly signed.
+    pub fn new_verified(
+        surml_file: &SurMlFile,
+        trusted_keys: &[VerifyingKey],
+    ) -> Result<Self, SurrealError> {
+        surml_file.verify(trusted_keys)?;
+        Self::new(surml_file)
+    }
+
+    /// Creates a new `ModelComputation` that serves computations from a model that is already compiled, such as one
+    /// held in a `SessionCache`.
+    ///
+    /// # Arguments
+    /// * `compiled` - The compiled model.
+    ///
+    /// # Returns
+    /// A new `ModelComputation` borrowing the compiled model.
+    pub fn from_compiled(compiled: &'a CompiledModel) -> Self {
+        ModelComputation {
+            compiled: Compiled::Borrowed(compiled),
+        }
+    }
+
     /// Creates a Tensor that can be used as input to the loaded model from a hashmap of keys and values.
     ///
     /// # Arguments
@@ -33,38 +90,6 @@ impl ModelComputation<'_> {
         Ok(ndarray::arr1::<f32>(&buffer).into_dyn())
     }
 
-    /// Creates a vector of dimensions for the input tensor from the loaded model.
-    ///
-    /// # Arguments
-    /// * `session_ref` - A reference to the session to get the input shape
-    ///
-    /// # Returns
-    /// A vector of dimensions for the input tensor to be reshaped into from the loaded model.
-    fn process_input_dims(session_ref: &Session) -> Result<Vec<usize>, SurrealError> {
-        let unwrapped_dims = match &session_ref.inputs[0].input_type {
-            ValueType::Tensor {
-                ty: _,
-                dimensions: new_dims,
-                dimension_symbols: _,
-            } => new_dims,
-            _ => {
-                return Err(SurrealError {
-                    message: "input dims not found".into(),
-                    status: SurrealErrorStatus::Unknown,
-                })
-            }
-        };
-        let mut dims_cache = Vec::new();
-        for dim in unwrapped_dims.iter() {
-            if dim < &0 {
-                dims_cache.push((dim * -1) as usize);
-            } else {
-                dims_cache.push(*dim as usize);
-            }
-        }
-        Ok(dims_cache)
-    }
-
     /// Creates a Vector that can be used manipulated with other operations such as normalisation from a hashmap of keys and values.
     ///
     /// # Arguments
@@ -74,151 +99,773 @@ impl ModelComputation<'_> {
     /// A Vector that can be used manipulated with other operations such as normalisation.
     pub fn input_vector_from_key_bindings(
         &self,
-        mut input_values: HashMap<String, f32>,
+        input_values: HashMap<String, f32>,
     ) -> Result<Vec<f32>, SurrealError> {
-        let mut buffer = Vec::with_capacity(self.surml_file.header.keys.store.len());
-
-        for key in &self.surml_file.header.keys.store {
-            let value = match input_values.get_mut(key) {
-                Some(value) => value,
-                None => {
-                    return Err(SurrealError::new(
-                        format!(
-                            "src/execution/compute.rs 67: Key {} not found in input values",
-                            key
-                        ),
-                        SurrealErrorStatus::NotFound,
-                    ))
-                }
-            };
-            buffer.push(std::mem::take(value));
-        }
-
-        Ok(buffer)
+        input_vector_from_header(&self.header, input_values)
     }
 
     /// Performs a raw computation on the loaded model.
     ///
     /// # Arguments
     /// * `tensor` - The input tensor to the loaded model.
+    /// * `dims` - The rows and columns to reshape the input tensor to, or `None` to use the dimensions in the header
+    ///   or model.
     ///
     /// # Returns
     /// The computed output tensor from the loaded model.
     pub fn raw_compute(
         &self,
         tensor: ArrayD<f32>,
-        _dims: Option<(i32, i32)>,
+        dims: Option<(i32, i32)>,
     ) -> Result<Vec<f32>, SurrealError> {
-        let session = get_session(self.surml_file.model.clone())?;
-        let dims_cache = ModelComputation::process_input_dims(&session)?;
-        let tensor = match tensor.into_shape_with_order(dims_cache) {
-            Ok(tensor) => tensor,
-            Err(_) => {
-                return Err(SurrealError::new(
-                    "Failed to reshape tensor to input dimensions".to_string(),
-                    SurrealErrorStatus::Unknown,
-                ))
+        match dims {
+            Some(dims) => {
+                let dims = explicit_input_shape(dims, tensor.len())?;
+                self.raw_compute_with_dims(tensor, dims)
             }
-        };
-        let tensor = match ort::value::Tensor::from_array(tensor) {
-            Ok(tensor) => tensor,
-            Err(_) => {
-                return Err(SurrealError::new(
-                    "Failed to convert tensor to ort tensor".to_string(),
-                    SurrealErrorStatus::Unknown,
-                ))
+            None => CompiledModel::raw_compute(self, tensor),
+        }
+    }
+}
+
+/// Gives access to every computation of the `CompiledModel` such as `buffered_compute`, `raw_compute_batch` and
+/// `raw_compute_typed`.
+impl Deref for ModelComputation<'_> {
+    type Target = CompiledModel;
+
+    fn deref(&self) -> &CompiledModel {
+        match &self.compiled {
+            Compiled::Built(compiled) => compiled,
+            Compiled::Borrowed(compiled) => compiled,
+        }
+    }
+}
+
+/// Applies the inverse of the output normalisers in the header to an output tensor of the model. With several
+/// declared outputs the last axis of the tensor holds one value per output.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the output normalisers.
+/// * `output` - The output tensor of the model.
+///
+/// # Returns
+/// The output tensor in the scale of the training data with its shape unchanged.
+pub(crate) fn denormalise_output_tensor(
+    header: &Header,
+    mut output: ArrayD<f32>,
+) -> Result<ArrayD<f32>, SurrealError> {
+    let declared = header.outputs.store.len();
+    if declared > 1 && output.shape().last() != Some(&declared) {
+        return Err(SurrealError::new(
+            format!(
+                "The model returned an output of shape {:?} but the header declares {} outputs",
+                output.shape(),
+                declared
+            ),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+    denormalise_values(header, output.iter_mut())?;
+    Ok(output)
+}
+
+/// Applies the inverse of the output normalisers in the header in place. A single output normaliser is applied to
+/// every value, otherwise the values cycle through the declared outputs so rows of several outputs can be passed at
+/// once.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the output normalisers.
+/// * `values` - The values the model returned.
+fn denormalise_values<'a>(
+    header: &Header,
+    values: impl ExactSizeIterator<Item = &'a mut f32>,
+) -> Result<(), SurrealError> {
+    let outputs = &header.outputs.store;
+    if outputs.len() <= 1 {
+        if let Some(normaliser) = outputs
+            .first()
+            .and_then(|output| output.normaliser.as_ref())
+        {
+            for value in values {
+                *value = normaliser.inverse_normalise(*value)?;
             }
-        };
-        let x = match ort::inputs![tensor] {
-            Ok(x) => x,
-            Err(_) => {
+        }
+        return Ok(());
+    }
+    if !values.len().is_multiple_of(outputs.len()) {
+        return Err(SurrealError::new(
+            format!(
+                "The model returned {} values but the header declares {} outputs",
+                values.len(),
+                outputs.len()
+            ),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+    for (index, value) in values.enumerate() {
+        if let Some(normaliser) = &outputs[index % outputs.len()].normaliser {
+            *value = normaliser.inverse_normalise(*value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Keys the outputs of the model by the names of the outputs declared in the header.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the outputs.
+/// * `output` - The outputs of the model in the scale of the training data.
+///
+/// # Returns
+/// The value of each output keyed by its name.
+pub(crate) fn name_outputs(
+    header: &Header,
+    output: Vec<f32>,
+) -> Result<HashMap<String, f32>, SurrealError> {
+    let names = header.outputs.names(output.len())?;
+    Ok(names.into_iter().zip(output).collect())
+}
+
+/// Reads the dimensions of the first input of the session where dynamic dimensions are negative.
+///
+/// # Arguments
+/// * `session_ref` - A reference to the session to get the input shape
+///
+/// # Returns
+/// The dimensions of the first input as declared by the model.
+pub(crate) fn session_input_dims(session_ref: &Session) -> Result<Vec<i64>, SurrealError> {
+    match session_ref.inputs.first() {
+        Some(input) => tensor_dims(&input.input_type),
+        None => Err(SurrealError {
+            message: "input dims not found".into(),
+            status: SurrealErrorStatus::Unknown,
+        }),
+    }
+}
+
+/// Reads the dimensions of a named input of the session where dynamic dimensions are negative.
+///
+/// # Arguments
+/// * `session_ref` - A reference to the session to get the input shape
+/// * `input_name` - The name of the input in the ONNX graph.
+///
+/// # Returns
+/// The dimensions of the input as declared by the model.
+pub(crate) fn named_input_dims(
+    session_ref: &Session,
+    input_name: &str,
+) -> Result<Vec<i64>, SurrealError> {
+    match session_ref
+        .inputs
+        .iter()
+        .find(|input| input.name == input_name)
+    {
+        Some(input) => tensor_dims(&input.input_type),
+        None => Err(SurrealError::new(
+            format!("Model has no input named {}", input_name),
+            SurrealErrorStatus::NotFound,
+        )),
+    }
+}
+
+/// Reads the dimensions of a tensor type.
+///
+/// # Arguments
+/// * `value_type` - The type of an input or output of the model.
+///
+/// # Returns
+/// The dimensions of the tensor, or an error if the type is not a tensor.
+fn tensor_dims(value_type: &ValueType) -> Result<Vec<i64>, SurrealError> {
+    match value_type {
+        ValueType::Tensor {
+            ty: _,
+            dimensions: new_dims,
+            dimension_symbols: _,
+        } => Ok(new_dims.clone()),
+        _ => Err(SurrealError {
+            message: "input dims not found".into(),
+            status: SurrealErrorStatus::Unknown,
+        }),
+    }
+}
+
+/// Reads the declared shape of the input. The input dimensions declared in the header are used if there are any,
+/// otherwise the dimensions declared by the model are used.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model.
+/// * `model_dims` - The dimensions of the first input as declared by the model.
+///
+/// # Returns
+/// The declared shape of the input.
+fn declared_input_dims(header: &Header, model_dims: &[i64]) -> InputDims {
+    if header.input_dims.is_empty() {
+        return InputDims::from_model_dims(model_dims);
+    }
+    header.input_dims.clone()
+}
+
+/// Works out the shape an input with a number of values is reshaped into. The input dimensions declared in the header
+/// are used if there are any, otherwise the dimensions declared by the model are used.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model.
+/// * `model_dims` - The dimensions of the first input as declared by the model.
+/// * `length` - The number of values in the input.
+///
+/// # Returns
+/// The shape the input is reshaped into.
+pub(crate) fn input_shape(
+    header: &Header,
+    model_dims: &[i64],
+    length: usize,
+) -> Result<Vec<usize>, SurrealError> {
+    declared_input_dims(header, model_dims).resolve(length)
+}
+
+/// Checks the rows and columns passed to `ModelComputation::raw_compute` against the length of the input tensor.
+///
+/// # Arguments
+/// * `dims` - The rows and columns to reshape the input tensor to.
+/// * `length` - The number of values in the input tensor.
+///
+/// # Returns
+/// The dimensions of the input tensor, or an error with a `BadRequest` status if they do not hold the input.
+pub(crate) fn explicit_input_shape(
+    dims: (i32, i32),
+    length: usize,
+) -> Result<Vec<usize>, SurrealError> {
+    let (rows, columns) = match (usize::try_from(dims.0), usize::try_from(dims.1)) {
+        (Ok(rows), Ok(columns)) => (rows, columns),
+        _ => {
+            return Err(SurrealError::new(
+                format!("The input dims {:?} cannot be negative", dims),
+                SurrealErrorStatus::BadRequest,
+            ))
+        }
+    };
+    if rows.checked_mul(columns) != Some(length) {
+        return Err(SurrealError::new(
+            format!(
+                "The input dims {:?} do not hold the {} input values",
+                dims, length
+            ),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+    Ok(vec![rows, columns])
+}
+
+/// Builds an `[N, width]` tensor from a batch of rows applying the normalisers in the header to every row. The rows
+/// go through the same coercion and normalisation as a single buffered computation, so encoded columns are rejected
+/// in the same way.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the key bindings and normalisers.
+/// * `rows` - A hashmap of keys and values for each row.
+///
+/// # Returns
+/// The input tensor for the batch.
+pub(crate) fn batch_tensor_from_header(
+    header: &Header,
+    rows: &[HashMap<String, f32>],
+) -> Result<ArrayD<f32>, SurrealError> {
+    let width = header.input_width();
+    let mut buffer = Vec::with_capacity(rows.len() * width);
+    for row in rows {
+        let mut row = row.clone();
+        normalise_inputs(header, &mut row)?;
+        buffer.extend(input_vector_from_header(header, row)?);
+    }
+    match ArrayD::from_shape_vec(vec![rows.len(), width], buffer) {
+        Ok(tensor) => Ok(tensor),
+        Err(error) => Err(SurrealError::new(
+            format!("Failed to build the batch tensor: {}", error),
+            SurrealErrorStatus::BadRequest,
+        )),
+    }
+}
+
+/// The shape a batch of rows is run with.
+///
+/// # Fields
+/// * `Stacked` - The shape of the whole batch with the rows stacked along the dynamic first axis.
+/// * `PerRow` - The shape of a single row when the first axis is fixed so each row is run on its own.
+#[derive(Debug, PartialEq)]
+pub(crate) enum BatchShape {
+    Stacked(Vec<usize>),
+    PerRow(Vec<usize>),
+}
+
+/// Works out the shape a batch of rows is run with. Each row is shaped in the same way as a single input, using the
+/// input dimensions in the header if there are any.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model.
+/// * `model_dims` - The dimensions of the first input as declared by the model.
+/// * `rows` - The number of rows in the batch.
+/// * `length` - The number of values in the whole batch.
+///
+/// # Returns
+/// The shape of the batch.
+pub(crate) fn batch_shape(
+    header: &Header,
+    model_dims: &[i64],
+    rows: usize,
+    length: usize,
+) -> Result<BatchShape, SurrealError> {
+    let declared = declared_input_dims(header, model_dims);
+    let mut shape = declared.resolve(length / rows)?;
+    match declared.dims.first() {
+        Some(Dimension::Dynamic(_)) => {
+            shape[0] *= rows;
+            Ok(BatchShape::Stacked(shape))
+        }
+        _ => Ok(BatchShape::PerRow(shape)),
+    }
+}
+
+/// Splits the output of a batch run into one output vector per row.
+///
+/// # Arguments
+/// * `output` - The output of the model for the whole batch.
+/// * `rows` - The number of rows in the batch.
+///
+/// # Returns
+/// One output vector per row, or an error if the output can not be split evenly between the rows.
+pub(crate) fn split_batch_output(
+    output: Vec<f32>,
+    rows: usize,
+) -> Result<Vec<Vec<f32>>, SurrealError> {
+    if output.len() < rows || !output.len().is_multiple_of(rows) {
+        return Err(SurrealError::new(
+            format!(
+                "Model returned {} values which can not be split into {} rows",
+                output.len(),
+                rows
+            ),
+            SurrealErrorStatus::Unknown,
+        ));
+    }
+    Ok(output
+        .chunks(output.len() / rows)
+        .map(|chunk| chunk.to_vec())
+        .collect())
+}
+
+/// Runs the session on a batch of rows. If the first axis of the input is dynamic the rows are stacked along it and
+/// the whole batch is run in a single session call, otherwise each row is run on its own.
+///
+/// # Arguments
+/// * `session` - The session of the loaded model.
+/// * `header` - The header of the loaded model.
+/// * `model_dims` - The dimensions of the first input as declared by the model.
+/// * `tensor` - The input tensor with the rows along the first axis.
+///
+/// # Returns
+/// One output vector per row.
+pub(crate) fn run_session_batch(
+    session: &Session,
+    header: &Header,
+    model_dims: &[i64],
+    tensor: ArrayD<f32>,
+) -> Result<Vec<Vec<f32>>, SurrealError> {
+    let rows = match tensor.shape().first() {
+        Some(rows) => *rows,
+        None => {
+            return Err(SurrealError::new(
+                "A batch tensor needs at least one axis for the rows".to_string(),
+                SurrealErrorStatus::BadRequest,
+            ))
+        }
+    };
+    if rows == 0 {
+        return Ok(Vec::new());
+    }
+
+    match batch_shape(header, model_dims, rows, tensor.len())? {
+        BatchShape::Stacked(shape) => {
+            split_batch_output(run_session(session, tensor, shape)?, rows)
+        }
+        BatchShape::PerRow(shape) => tensor
+            .outer_iter()
+            .map(|row| run_session(session, row.to_owned(), shape.clone()))
+            .collect(),
+    }
+}
+
+/// Creates a Vector ordered by the key bindings of the header from a hashmap of keys and values.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the key bindings.
+/// * `input_values` - A hashmap of keys and values that will be used to create the input vector.
+///
+/// # Returns
+/// A Vector of the input values in the order of the key bindings.
+pub(crate) fn input_vector_from_header(
+    header: &Header,
+    mut input_values: HashMap<String, f32>,
+) -> Result<Vec<f32>, SurrealError> {
+    let mut buffer = Vec::with_capacity(header.keys.store.len());
+
+    for key in &header.keys.store {
+        let value = match input_values.get_mut(key) {
+            Some(value) => value,
+            None => {
                 return Err(SurrealError::new(
-                    "Failed to create input tensor".to_string(),
-                    SurrealErrorStatus::Unknown,
+                    format!("Column {} is missing from the input values", key),
+                    SurrealErrorStatus::NotFound,
                 ))
             }
         };
-        let outputs = safe_eject!(session.run(x), SurrealErrorStatus::Unknown);
+        buffer.push(std::mem::take(value));
+    }
+
+    Ok(buffer)
+}
 
-        let mut buffer: Vec<f32> = Vec::new();
+/// Runs the session on an input tensor reshaped to the input dimensions of the model.
+///
+/// # Arguments
+/// * `session` - The session of the loaded model.
+/// * `tensor` - The input tensor to the loaded model.
+/// * `dims` - The dimensions the input tensor is reshaped into.
+///
+/// # Returns
+/// The first output tensor of the loaded model with its shape, converting the values to `f32` if they are `i64`.
+pub(crate) fn run_session_shaped(
+    session: &Session,
+    tensor: ArrayD<f32>,
+    dims: Vec<usize>,
+) -> Result<ArrayD<f32>, SurrealError> {
+    let tensor = match tensor.into_shape_with_order(dims) {
+        Ok(tensor) => tensor,
+        Err(_) => {
+            return Err(SurrealError::new(
+                "Failed to reshape tensor to input dimensions".to_string(),
+                SurrealErrorStatus::Unknown,
+            ))
+        }
+    };
+    let tensor = match ort::value::Tensor::from_array(tensor) {
+        Ok(tensor) => tensor,
+        Err(_) => {
+            return Err(SurrealError::new(
+                "Failed to convert tensor to ort tensor".to_string(),
+                SurrealErrorStatus::Unknown,
+            ))
+        }
+    };
+    let x = match ort::inputs![tensor] {
+        Ok(x) => x,
+        Err(_) => {
+            return Err(SurrealError::new(
+                "Failed to create input tensor".to_string(),
+                SurrealErrorStatus::Unknown,
+            ))
+        }
+    };
+    let outputs = safe_eject!(session.run(x), SurrealErrorStatus::Unknown);
+
+    // extract the output tensor converting the values to f32 if they are i64
+    let output = match outputs[0].try_extract_tensor::<f32>() {
+        Ok(y) => y.to_owned(),
+        Err(_) => safe_eject!(
+            outputs[0].try_extract_tensor::<i64>(),
+            SurrealErrorStatus::Unknown
+        )
+        .mapv(|value| value as f32),
+    };
+    Ok(output)
+}
 
-        // extract the output tensor converting the values to f32 if they are i64
-        match outputs[0].try_extract_tensor::<f32>() {
-            Ok(y) => {
-                for i in y.iter() {
-                    buffer.push(*i);
+/// Runs the session on an input tensor reshaped to the input dimensions of the model.
+///
+/// # Arguments
+/// * `session` - The session of the loaded model.
+/// * `tensor` - The input tensor to the loaded model.
+/// * `dims` - The dimensions the input tensor is reshaped into.
+///
+/// # Returns
+/// The computed output tensor from the loaded model flattened into a vector.
+pub(crate) fn run_session(
+    session: &Session,
+    tensor: ArrayD<f32>,
+    dims: Vec<usize>,
+) -> Result<Vec<f32>, SurrealError> {
+    Ok(run_session_shaped(session, tensor, dims)?
+        .iter()
+        .cloned()
+        .collect())
+}
+
+/// Builds the input vector from input values of any type. Encoded columns are expanded into their numeric columns at
+/// their position in the key bindings, and every other column is coerced to its declared type and normalised.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the key bindings, encoders and normalisers.
+/// * `input_values` - A hashmap of keys and values of any type.
+///
+/// # Returns
+/// The input vector of `header.input_width()` values.
+pub(crate) fn input_vector_from_values(
+    header: &Header,
+    input_values: &HashMap<String, FeatureValue>,
+) -> Result<Vec<f32>, SurrealError> {
+    let mut buffer = Vec::with_capacity(header.input_width());
+    for column in &header.keys.store {
+        let value = input_values.get(column);
+        if let Some(encoder) = header.encoders.get_encoder(column) {
+            let default = header
+                .keys
+                .specs
+                .get(column)
+                .and_then(|spec| spec.default.as_ref());
+            match value.or(default) {
+                Some(value) => buffer.extend(encoder.encode(&value.to_string())?),
+                None => {
+                    return Err(SurrealError::new(
+                        format!(
+                            "Column {} is missing from the input values and has no default",
+                            column
+                        ),
+                        SurrealErrorStatus::NotFound,
+                    ))
                 }
             }
-            Err(_) => {
-                for i in safe_eject!(
-                    outputs[0].try_extract_tensor::<i64>(),
-                    SurrealErrorStatus::Unknown
-                )
-                .iter()
-                {
-                    buffer.push(*i as f32);
-                }
+            continue;
+        }
+        let mut value = header.keys.coerce(column, value)?;
+        if let Some(normaliser) = header.get_normaliser(column)? {
+            value = normaliser.normalise(value)?;
+        }
+        buffer.push(value);
+    }
+    Ok(buffer)
+}
+
+/// Fills in the defaults of missing columns and rejects values outside of the allowed range of their column in place.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the key bindings.
+/// * `input_values` - A hashmap of keys and values to be checked.
+pub(crate) fn coerce_inputs(
+    header: &Header,
+    input_values: &mut HashMap<String, f32>,
+) -> Result<(), SurrealError> {
+    for column in &header.keys.store {
+        if header.encoders.get_encoder(column).is_some() {
+            return Err(SurrealError::new(
+                format!(
+                    "Column {} is encoded from strings so the inputs have to be passed as values",
+                    column
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        let value = input_values
+            .get(column)
+            .map(|value| FeatureValue::from(*value));
+        let coerced = header.keys.coerce(column, value.as_ref())?;
+        input_values.insert(column.clone(), coerced);
+    }
+    Ok(())
+}
+
+/// Applies the normalisers in the header to the input values in place once they have been coerced to their declared
+/// column types.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the normalisers.
+/// * `input_values` - A hashmap of keys and values to be normalised.
+pub(crate) fn normalise_inputs(
+    header: &Header,
+    input_values: &mut HashMap<String, f32>,
+) -> Result<(), SurrealError> {
+    coerce_inputs(header, input_values)?;
+    for (key, value) in &mut *input_values {
+        let value_ref = *value;
+        if let Some(normaliser) = header.get_normaliser(&key.to_string())? {
+            *value = normaliser.normalise(value_ref)?;
+        }
+    }
+    Ok(())
+}
+
+/// Applies the inverse of the output normalisers in the header to the outputs of the model.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the output normalisers.
+/// * `output` - The outputs of the model.
+///
+/// # Returns
+/// The outputs in the scale of the training data.
+pub(crate) fn denormalise_outputs(
+    header: &Header,
+    mut output: Vec<f32>,
+) -> Result<Vec<f32>, SurrealError> {
+    denormalise_values(header, output.iter_mut())?;
+    Ok(output)
+}
+
+/// Builds a tensor for each named input of the model from the columns bound to it. Without input bindings every
+/// column is fed to the first input of the model.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model holding the key bindings and input bindings.
+/// * `session` - The session of the loaded model.
+/// * `input_values` - A hashmap of keys and values that will be used to create the input tensors.
+///
+/// # Returns
+/// The input tensors keyed by the name of the input, each reshaped to the dimensions of that input.
+pub(crate) fn named_input_tensors(
+    header: &Header,
+    session: &Session,
+    input_values: &HashMap<String, f32>,
+) -> Result<HashMap<String, ArrayD<f32>>, SurrealError> {
+    let mut tensors = HashMap::new();
+    if header.inputs.is_empty() {
+        let input_name = match session.inputs.first() {
+            Some(input) => input.name.clone(),
+            None => {
+                return Err(SurrealError::new(
+                    "Model has no inputs".to_string(),
+                    SurrealErrorStatus::BadRequest,
+                ))
             }
         };
-        Ok(buffer)
+        let buffer = input_vector_from_header(header, input_values.clone())?;
+        let dims = input_shape(header, &session_input_dims(session)?, buffer.len())?;
+        tensors.insert(input_name, reshape_input(buffer, dims)?);
+        return Ok(tensors);
     }
 
-    /// Checks the header applying normalisers if present and then performs a raw computation on the loaded model. Will
-    /// also apply inverse normalisers if present on the outputs.
-    ///
-    /// # Notes
-    /// This function is fairly coupled and will consider breaking out the functions later on if needed.
-    ///
-    /// # Arguments
-    /// * `input_values` - A hashmap of keys and values that will be used to create the input tensor.
-    ///
-    /// # Returns
-    /// The computed output tensor from the loaded model.
-    pub fn buffered_compute(
-        &self,
-        input_values: &mut HashMap<String, f32>,
-    ) -> Result<Vec<f32>, SurrealError> {
-        // applying normalisers if present
-        for (key, value) in &mut *input_values {
-            let value_ref = *value;
-            if let Some(normaliser) = self.surml_file.header.get_normaliser(&key.to_string())? {
-                *value = normaliser.normalise(value_ref);
+    for binding in &header.inputs.store {
+        let mut buffer = Vec::with_capacity(binding.columns.len());
+        for column in &binding.columns {
+            match input_values.get(column) {
+                Some(value) => buffer.push(*value),
+                None => {
+                    return Err(SurrealError::new(
+                        format!("Column {} is missing from the input values", column),
+                        SurrealErrorStatus::NotFound,
+                    ))
+                }
             }
         }
-        let tensor = self.input_tensor_from_key_bindings(input_values.clone())?;
-        let output = self.raw_compute(tensor, None)?;
+        let dims = InputDims::from_model_dims(&named_input_dims(session, &binding.name)?)
+            .resolve(buffer.len())?;
+        tensors.insert(binding.name.clone(), reshape_input(buffer, dims)?);
+    }
+    Ok(tensors)
+}
+
+/// Reshapes a flat buffer of input values into the dimensions of an input.
+///
+/// # Arguments
+/// * `buffer` - The input values.
+/// * `dims` - The dimensions of the input.
+///
+/// # Returns
+/// The input tensor.
+fn reshape_input(buffer: Vec<f32>, dims: Vec<usize>) -> Result<ArrayD<f32>, SurrealError> {
+    match ArrayD::from_shape_vec(dims.clone(), buffer) {
+        Ok(tensor) => Ok(tensor),
+        Err(_) => Err(SurrealError::new(
+            format!(
+                "Failed to reshape input values to input dimensions {:?}",
+                dims
+            ),
+            SurrealErrorStatus::BadRequest,
+        )),
+    }
+}
+
+/// Runs the session feeding each typed tensor to the input of the same name.
+///
+/// # Arguments
+/// * `session` - The session of the loaded model.
+/// * `inputs` - The typed tensors keyed by the name of the input in the ONNX graph.
+///
+/// # Returns
+/// The typed tensor outputs of the model keyed by output name. Outputs that are not tensors, such as the maps of
+/// probabilities some classifiers emit, are skipped.
+pub(crate) fn run_session_typed(
+    session: &Session,
+    inputs: HashMap<String, TensorValue>,
+) -> Result<HashMap<String, TensorValue>, SurrealError> {
+    let mut session_inputs: Vec<(String, SessionInputValue)> = Vec::with_capacity(inputs.len());
+    for (name, tensor) in inputs {
+        session_inputs.push((name, tensor.into_session_input()?));
+    }
+    let outputs = safe_eject!(
+        session.run(SessionInputs::from(session_inputs)),
+        SurrealErrorStatus::Unknown
+    );
 
-        // if no normaliser is present, return the output
-        if self.surml_file.header.output.normaliser.is_none() {
-            return Ok(output);
+    let mut buffer = HashMap::new();
+    for (name, value) in outputs.iter() {
+        if let Some(tensor) = TensorValue::from_value(&value)? {
+            buffer.insert(name.to_string(), tensor);
         }
+    }
+    Ok(buffer)
+}
 
-        // apply the normaliser to the output
-        let output_normaliser = match self.surml_file.header.output.normaliser.as_ref() {
-            Some(normaliser) => normaliser,
-            None => return Err(SurrealError::new(
-                String::from("No normaliser present for output which shouldn't happen as passed initial check for").to_string(), 
-                SurrealErrorStatus::Unknown
-            ))
-        };
-        let mut buffer = Vec::with_capacity(output.len());
+/// Runs the session feeding each tensor to the input of the same name.
+///
+/// # Arguments
+/// * `session` - The session of the loaded model.
+/// * `inputs` - The tensors keyed by the name of the input in the ONNX graph.
+///
+/// # Returns
+/// The tensor outputs of the model keyed by output name with numeric outputs converted to `f32`. Outputs that are
+/// not tensors, such as the maps of probabilities some classifiers emit, are skipped.
+pub(crate) fn run_session_named(
+    session: &Session,
+    inputs: HashMap<String, ArrayD<f32>>,
+) -> Result<HashMap<String, ArrayD<f32>>, SurrealError> {
+    let inputs = inputs
+        .into_iter()
+        .map(|(name, tensor)| (name, TensorValue::F32(tensor)))
+        .collect();
+    run_session_typed(session, inputs)?
+        .into_iter()
+        .map(|(name, tensor)| Ok((name, tensor.to_f32()?)))
+        .collect()
+}
+
+/// Applies the normalisers in the header, runs the session on the named inputs and applies the inverse of the output
+/// normaliser to the first output of the model.
+///
+/// # Arguments
+/// * `header` - The header of the loaded model.
+/// * `session` - The session of the loaded model.
+/// * `input_values` - A hashmap of keys and values that will be used to create the input tensors.
+///
+/// # Returns
+/// The tensor outputs of the model keyed by output name.
+pub(crate) fn buffered_compute_named_with(
+    header: &Header,
+    session: &Session,
+    input_values: &mut HashMap<String, f32>,
+) -> Result<HashMap<String, ArrayD<f32>>, SurrealError> {
+    normalise_inputs(header, input_values)?;
+    let inputs = named_input_tensors(header, session, input_values)?;
+    let mut outputs = run_session_named(session, inputs)?;
 
-        for value in output {
-            buffer.push(output_normaliser.inverse_normalise(value));
+    if let Some(first) = session.outputs.first() {
+        if let Some(output) = outputs.remove(&first.name) {
+            let output = denormalise_output_tensor(header, output)?;
+            outputs.insert(first.name.clone(), output);
         }
-        Ok(buffer)
     }
+    Ok(outputs)
 }
 
 #[cfg(test)]
 mod tests {
 
-    #[cfg(any(
-        feature = "sklearn-tests",
-        feature = "onnx-tests",
-        feature = "torch-tests",
-        feature = "tensorflow-tests"
-    ))]
     use super::*;
     #[cfg(any(
         feature = "sklearn-tests",
@@ -232,10 +879,8 @@ mod tests {
     #[test]
     fn test_raw_compute_linear_sklearn() {
         set_environment().unwrap();
-        let mut file = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
-        let model_computation = ModelComputation {
-            surml_file: &mut file,
-        };
+        let file = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
 
         let mut input_values = HashMap::new();
         input_values.insert(String::from("squarefoot"), 1000.0);
@@ -256,10 +901,8 @@ mod tests {
     #[test]
     fn test_buffered_compute_linear_sklearn() {
         set_environment().unwrap();
-        let mut file = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
-        let model_computation = ModelComputation {
-            surml_file: &mut file,
-        };
+        let file = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
 
         let mut input_values = HashMap::new();
         input_values.insert(String::from("squarefoot"), 1000.0);
@@ -275,10 +918,8 @@ mod tests {
     #[test]
     fn test_raw_compute_linear_onnx() {
         set_environment().unwrap();
-        let mut file = SurMlFile::from_file("./model_stash/onnx/surml/linear.surml").unwrap();
-        let model_computation = ModelComputation {
-            surml_file: &mut file,
-        };
+        let file = SurMlFile::from_file("./model_stash/onnx/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
 
         let mut input_values = HashMap::new();
         input_values.insert(String::from("squarefoot"), 1000.0);
@@ -299,10 +940,8 @@ mod tests {
     #[test]
     fn test_buffered_compute_linear_onnx() {
         set_environment().unwrap();
-        let mut file = SurMlFile::from_file("./model_stash/onnx/surml/linear.surml").unwrap();
-        let model_computation = ModelComputation {
-            surml_file: &mut file,
-        };
+        let file = SurMlFile::from_file("./model_stash/onnx/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
 
         let mut input_values = HashMap::new();
         input_values.insert(String::from("squarefoot"), 1000.0);
@@ -318,10 +957,8 @@ mod tests {
     #[test]
     fn test_raw_compute_linear_torch() {
         set_environment().unwrap();
-        let mut file = SurMlFile::from_file("./model_stash/torch/surml/linear.surml").unwrap();
-        let model_computation = ModelComputation {
-            surml_file: &mut file,
-        };
+        let file = SurMlFile::from_file("./model_stash/torch/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
 
         let mut input_values = HashMap::new();
         input_values.insert(String::from("squarefoot"), 1000.0);
@@ -339,10 +976,8 @@ mod tests {
     #[test]
     fn test_buffered_compute_linear_torch() {
         set_environment().unwrap();
-        let mut file = SurMlFile::from_file("./model_stash/torch/surml/linear.surml").unwrap();
-        let model_computation = ModelComputation {
-            surml_file: &mut file,
-        };
+        let file = SurMlFile::from_file("./model_stash/torch/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
 
         let mut input_values = HashMap::new();
         input_values.insert(String::from("squarefoot"), 1000.0);
@@ -358,10 +993,8 @@ mod tests {
     #[test]
     fn test_raw_compute_linear_tensorflow() {
         set_environment().unwrap();
-        let mut file = SurMlFile::from_file("./model_stash/tensorflow/surml/linear.surml").unwrap();
-        let model_computation = ModelComputation {
-            surml_file: &mut file,
-        };
+        let file = SurMlFile::from_file("./model_stash/tensorflow/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
 
         let mut input_values = HashMap::new();
         input_values.insert(String::from("squarefoot"), 1000.0);
@@ -379,10 +1012,8 @@ mod tests {
     #[test]
     fn test_buffered_compute_linear_tensorflow() {
         set_environment().unwrap();
-        let mut file = SurMlFile::from_file("./model_stash/tensorflow/surml/linear.surml").unwrap();
-        let model_computation = ModelComputation {
-            surml_file: &mut file,
-        };
+        let file = SurMlFile::from_file("./model_stash/tensorflow/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
 
         let mut input_values = HashMap::new();
         input_values.insert(String::from("squarefoot"), 1000.0);
@@ -393,4 +1024,332 @@ mod tests {
             .unwrap();
         assert_eq!(output.len(), 1);
     }
+
+    #[test]
+    fn test_explicit_input_shape() {
+        assert_eq!(explicit_input_shape((1, 2), 2).unwrap(), vec![1, 2]);
+        assert_eq!(explicit_input_shape((3, 2), 6).unwrap(), vec![3, 2]);
+
+        let error = explicit_input_shape((2, 2), 2).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert_eq!(
+            error.message,
+            "The input dims (2, 2) do not hold the 2 input values"
+        );
+        assert_eq!(
+            explicit_input_shape((-1, 2), 2).unwrap_err().message,
+            "The input dims (-1, 2) cannot be negative"
+        );
+    }
+
+    #[test]
+    fn test_batch_tensor_from_header() {
+        use crate::storage::header::normalisers::{
+            linear_scaling::LinearScaling, wrapper::NormaliserType,
+        };
+
+        let mut header = Header::fresh();
+        header.add_column(String::from("squarefoot"));
+        header.add_column(String::from("num_floors"));
+        header
+            .add_normaliser(
+                String::from("squarefoot"),
+                NormaliserType::LinearScaling(LinearScaling {
+                    min: 0.0,
+                    max: 1000.0,
+                }),
+            )
+            .unwrap();
+
+        let rows: Vec<HashMap<String, f32>> = [(500.0, 1.0), (1000.0, 2.0), (250.0, 3.0)]
+            .iter()
+            .map(|(squarefoot, num_floors)| {
+                HashMap::from([
+                    (String::from("squarefoot"), *squarefoot),
+                    (String::from("num_floors"), *num_floors),
+                ])
+            })
+            .collect();
+
+        let tensor = batch_tensor_from_header(&header, &rows).unwrap();
+        assert_eq!(tensor.shape(), &[3, 2]);
+        assert_eq!(
+            tensor.iter().cloned().collect::<Vec<f32>>(),
+            vec![0.5, 1.0, 1.0, 2.0, 0.25, 3.0]
+        );
+
+        let error = batch_tensor_from_header(&header, &[HashMap::new()]).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::NotFound);
+    }
+
+    #[test]
+    fn test_multiple_named_outputs() {
+        use crate::storage::header::normalisers::wrapper::NormaliserType;
+
+        let mut header = Header::fresh();
+        header.add_output(
+            String::from("price"),
+            Some(NormaliserType::from_definition("z_score(100,10)").unwrap()),
+        );
+        header.add_output(
+            String::from("days_on_market"),
+            Some(NormaliserType::from_definition("log_scaling(10,1)").unwrap()),
+        );
+
+        let output = denormalise_outputs(&header, vec![1.0, 2.0]).unwrap();
+        assert_eq!(output, vec![110.0, 99.0]);
+        assert_eq!(
+            name_outputs(&header, output).unwrap(),
+            HashMap::from([
+                (String::from("price"), 110.0),
+                (String::from("days_on_market"), 99.0)
+            ])
+        );
+
+        let rows = ndarray::arr2(&[[1.0, 2.0], [-1.0, 1.0]]).into_dyn();
+        let rows = denormalise_output_tensor(&header, rows).unwrap();
+        assert_eq!(
+            rows.iter().cloned().collect::<Vec<f32>>(),
+            vec![110.0, 99.0, 90.0, 9.0]
+        );
+
+        let error = denormalise_outputs(&header, vec![1.0, 2.0, 3.0]).unwrap_err();
+        assert_eq!(
+            error.message,
+            "The model returned 3 values but the header declares 2 outputs"
+        );
+        let error = denormalise_output_tensor(&header, ndarray::arr2(&[[1.0], [2.0]]).into_dyn())
+            .unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_log_scaled_outputs_and_inputs() {
+        use crate::storage::header::normalisers::{log_scale::LogScaling, wrapper::NormaliserType};
+
+        let mut header = Header::fresh();
+        header.add_column(String::from("squarefoot"));
+        let log_scaling = || {
+            NormaliserType::LogScaling(LogScaling {
+                base: 10.0,
+                min: 1.0,
+            })
+        };
+        header.add_output(String::from("house_price"), Some(log_scaling()));
+        header
+            .add_normaliser(String::from("squarefoot"), log_scaling())
+            .unwrap();
+
+        assert_eq!(
+            denormalise_outputs(&header, vec![2.0, 3.0]).unwrap(),
+            vec![99.0, 999.0]
+        );
+
+        let mut inputs = HashMap::from([(String::from("squarefoot"), 999.0)]);
+        normalise_inputs(&header, &mut inputs).unwrap();
+        assert_eq!(inputs["squarefoot"], 3.0);
+
+        let mut inputs = HashMap::from([(String::from("squarefoot"), -5.0)]);
+        let error = normalise_inputs(&header, &mut inputs).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_input_vector_from_values() {
+        use crate::storage::header::column::{ColumnSpec, ColumnType};
+        use crate::storage::header::encoders::{one_hot::OneHot, wrapper::EncoderType, EncoderMap};
+
+        let mut header = Header::fresh();
+        header.add_column(String::from("squarefoot"));
+        header.add_column(String::from("kind"));
+        header.add_column(String::from("num_floors"));
+        let mut kind = ColumnSpec::new(ColumnType::Categorical);
+        kind.categories = vec![String::from("flat"), String::from("house")];
+        header.add_column_spec(String::from("kind"), kind).unwrap();
+        let mut num_floors = ColumnSpec::new(ColumnType::Int);
+        num_floors.default = Some(FeatureValue::Int(1));
+        num_floors.min = Some(1.0);
+        num_floors.max = Some(5.0);
+        header
+            .add_column_spec(String::from("num_floors"), num_floors)
+            .unwrap();
+
+        let input_values = HashMap::from([
+            (String::from("squarefoot"), FeatureValue::Float(500.0)),
+            (String::from("kind"), FeatureValue::from("house")),
+        ]);
+        let buffer = input_vector_from_values(&header, &input_values).unwrap();
+        assert_eq!(buffer, vec![500.0, 1.0, 1.0]);
+
+        header
+            .add_encoder(
+                String::from("kind"),
+                EncoderType::OneHot(OneHot {
+                    vocabulary: vec![String::from("flat"), String::from("house")],
+                }),
+            )
+            .unwrap();
+        assert_eq!(header.input_width(), 4);
+        let buffer = input_vector_from_values(&header, &input_values).unwrap();
+        assert_eq!(buffer, vec![500.0, 0.0, 1.0, 1.0]);
+        let mut encoded_values = HashMap::from([(String::from("squarefoot"), 500.0)]);
+        let error = normalise_inputs(&header, &mut encoded_values).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        header.encoders = EncoderMap::fresh();
+
+        let mut input_values = HashMap::from([
+            (String::from("squarefoot"), 500.0),
+            (String::from("kind"), 0.0),
+            (String::from("num_floors"), 9.0),
+        ]);
+        let error = normalise_inputs(&header, &mut input_values).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+
+        input_values.remove("num_floors");
+        normalise_inputs(&header, &mut input_values).unwrap();
+        assert_eq!(input_values["num_floors"], 1.0);
+
+        input_values.remove("squarefoot");
+        let error = normalise_inputs(&header, &mut input_values).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Column squarefoot is missing from the input values and has no default"
+        );
+    }
+
+    #[cfg(feature = "sklearn-tests")]
+    #[test]
+    fn test_buffered_compute_batch_linear_sklearn() {
+        set_environment().unwrap();
+        let file = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
+
+        let rows: Vec<HashMap<String, f32>> = (0..5)
+            .map(|i| {
+                HashMap::from([
+                    (String::from("squarefoot"), 1000.0 + i as f32 * 100.0),
+                    (String::from("num_floors"), 2.0),
+                ])
+            })
+            .collect();
+
+        let outputs = model_computation.buffered_compute_batch(&rows).unwrap();
+        assert_eq!(outputs.len(), rows.len());
+        for (row, output) in rows.iter().zip(outputs) {
+            let single = model_computation
+                .buffered_compute_positional(&mut row.clone())
+                .unwrap();
+            assert_eq!(output, single);
+        }
+    }
+
+    #[test]
+    fn test_batch_shape() {
+        let mut header = Header::fresh();
+        header.add_column(String::from("squarefoot"));
+        header.add_column(String::from("num_floors"));
+
+        // a dynamic batch axis declared by the model stacks the rows
+        assert_eq!(
+            batch_shape(&header, &[-1, 2], 3, 6).unwrap(),
+            BatchShape::Stacked(vec![3, 2])
+        );
+        // a fixed batch axis runs each row on its own
+        assert_eq!(
+            batch_shape(&header, &[1, 2], 3, 6).unwrap(),
+            BatchShape::PerRow(vec![1, 2])
+        );
+        // the input dimensions in the header take precedence over the model and every row keeps its own dynamic axes
+        header
+            .add_input_dims("batch,seq_len,1".to_string())
+            .unwrap();
+        assert_eq!(
+            batch_shape(&header, &[-1, -1, -1], 3, 6).unwrap(),
+            BatchShape::Stacked(vec![3, 2, 1])
+        );
+        header.add_input_dims("batch,2".to_string()).unwrap();
+        assert!(batch_shape(&header, &[-1, 2], 2, 6).is_err());
+    }
+
+    #[test]
+    fn test_split_batch_output() {
+        assert_eq!(
+            split_batch_output(vec![1.0, 2.0, 3.0, 4.0], 2).unwrap(),
+            vec![vec![1.0, 2.0], vec![3.0, 4.0]]
+        );
+        assert!(split_batch_output(Vec::new(), 2).is_err());
+        assert!(split_batch_output(vec![1.0, 2.0, 3.0], 2).is_err());
+    }
+
+    #[test]
+    fn test_reshape_input() {
+        let error = reshape_input(vec![1.0, 2.0, 3.0], vec![1, 2]).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        let tensor = reshape_input(vec![1.0, 2.0], vec![1, 2]).unwrap();
+        assert_eq!(tensor.shape(), &[1, 2]);
+    }
+
+    #[cfg(feature = "sklearn-tests")]
+    #[test]
+    fn test_buffered_compute_named_linear_sklearn() {
+        set_environment().unwrap();
+        let file = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
+
+        let mut input_values = HashMap::new();
+        input_values.insert(String::from("squarefoot"), 1000.0);
+        input_values.insert(String::from("num_floors"), 2.0);
+
+        let single = model_computation
+            .buffered_compute_positional(&mut input_values.clone())
+            .unwrap();
+        let outputs = model_computation
+            .buffered_compute_named(&mut input_values)
+            .unwrap();
+        assert_eq!(outputs.len(), 1);
+        let output = outputs.values().next().unwrap();
+        assert_eq!(output.iter().cloned().collect::<Vec<f32>>(), single);
+    }
+
+    #[cfg(feature = "sklearn-tests")]
+    #[test]
+    fn test_raw_compute_typed_linear_sklearn() {
+        set_environment().unwrap();
+        let file = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
+        let session = crate::execution::session::get_session(&file.model).unwrap();
+        let input_name = session.inputs[0].name.clone();
+
+        let mut inputs = HashMap::new();
+        inputs.insert(
+            input_name,
+            TensorValue::from(ndarray::arr2::<f32, _>(&[[1000.0, 2.0]]).into_dyn()),
+        );
+        let outputs = model_computation.raw_compute_typed(inputs).unwrap();
+        let output = outputs.values().next().unwrap();
+        assert_eq!(output.type_name(), "f32");
+        assert_eq!(
+            output
+                .to_f32()
+                .unwrap()
+                .iter()
+                .cloned()
+                .collect::<Vec<f32>>(),
+            vec![985.57745]
+        );
+    }
+
+    #[cfg(feature = "sklearn-tests")]
+    #[test]
+    fn test_raw_compute_shaped_linear_sklearn() {
+        set_environment().unwrap();
+        let file = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+        let model_computation = ModelComputation::new(&file).unwrap();
+
+        let tensor = ndarray::arr1::<f32>(&[1000.0, 2.0]).into_dyn();
+        let flat = model_computation.raw_compute(tensor.clone(), None).unwrap();
+        let shaped = model_computation.raw_compute_shaped(tensor).unwrap();
+        assert_eq!(shaped.shape().iter().product::<usize>(), flat.len());
+        assert_eq!(shaped.iter().cloned().collect::<Vec<f32>>(), flat);
+    }
 }
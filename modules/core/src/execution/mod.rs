@@ -1,4 +1,7 @@
 //! Defines operations around performing computations on a loaded model.
+pub mod cache;
+pub mod compiled;
 pub mod compute;
 // pub mod onnx_environment;
 pub mod session;
+pub mod tensor;
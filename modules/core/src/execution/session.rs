@@ -25,7 +25,7 @@ use ort::execution_providers::ExecutionProvider;
 ///
 /// # Returns
 /// A session object.
-pub fn get_session(model_bytes: Vec<u8>) -> Result<Session, SurrealError> {
+pub fn get_session(model_bytes: &[u8]) -> Result<Session, SurrealError> {
     #[cfg(feature = "gpu")]
     let mut builder = safe_eject!(Session::builder(), SurrealErrorStatus::Unknown);
 
@@ -42,7 +42,7 @@ pub fn get_session(model_bytes: Vec<u8>) -> Result<Session, SurrealError> {
         }
     }
     let session: Session = safe_eject!(
-        builder.commit_from_memory(&model_bytes),
+        builder.commit_from_memory(model_bytes),
         SurrealErrorStatus::Unknown
     );
     Ok(session)
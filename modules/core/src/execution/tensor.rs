@@ -0,0 +1,252 @@
+//! Defines the typed tensors that are fed to and returned from a model.
+use half::f16;
+use ndarray::ArrayD;
+use ort::session::SessionInputValue;
+use ort::tensor::TensorElementType;
+use ort::value::{DynValue, Tensor, ValueType};
+
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use crate::safe_eject;
+
+/// A tensor with its element type and shape that can be an input or output of a model.
+///
+/// # Fields
+/// * `F32` - A tensor of 32-bit floats.
+/// * `F64` - A tensor of 64-bit floats.
+/// * `I32` - A tensor of 32-bit integers.
+/// * `I64` - A tensor of 64-bit integers such as category ids or class labels.
+/// * `Bool` - A tensor of booleans such as masks.
+/// * `String` - A tensor of strings such as the class labels of sklearn classifiers.
+/// * `F16` - A tensor of 16-bit floats.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TensorValue {
+    F32(ArrayD<f32>),
+    F64(ArrayD<f64>),
+    I32(ArrayD<i32>),
+    I64(ArrayD<i64>),
+    Bool(ArrayD<bool>),
+    String(ArrayD<String>),
+    F16(ArrayD<f16>),
+}
+
+impl TensorValue {
+    /// The shape of the tensor.
+    pub fn shape(&self) -> &[usize] {
+        match self {
+            TensorValue::F32(array) => array.shape(),
+            TensorValue::F64(array) => array.shape(),
+            TensorValue::I32(array) => array.shape(),
+            TensorValue::I64(array) => array.shape(),
+            TensorValue::Bool(array) => array.shape(),
+            TensorValue::String(array) => array.shape(),
+            TensorValue::F16(array) => array.shape(),
+        }
+    }
+
+    /// The name of the element type of the tensor.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            TensorValue::F32(_) => "f32",
+            TensorValue::F64(_) => "f64",
+            TensorValue::I32(_) => "i32",
+            TensorValue::I64(_) => "i64",
+            TensorValue::Bool(_) => "bool",
+            TensorValue::String(_) => "string",
+            TensorValue::F16(_) => "f16",
+        }
+    }
+
+    /// Converts a numeric or boolean tensor into a tensor of 32-bit floats keeping the shape.
+    ///
+    /// # Returns
+    /// The converted tensor, or an error for a string tensor.
+    pub fn to_f32(&self) -> Result<ArrayD<f32>, SurrealError> {
+        match self {
+            TensorValue::F32(array) => Ok(array.clone()),
+            TensorValue::F64(array) => Ok(array.mapv(|value| value as f32)),
+            TensorValue::I32(array) => Ok(array.mapv(|value| value as f32)),
+            TensorValue::I64(array) => Ok(array.mapv(|value| value as f32)),
+            TensorValue::Bool(array) => Ok(array.mapv(|value| if value { 1.0 } else { 0.0 })),
+            TensorValue::F16(array) => Ok(array.mapv(f32::from)),
+            TensorValue::String(_) => Err(SurrealError::new(
+                "A string tensor can not be converted to f32".to_string(),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+
+    /// Converts the tensor into a value that can be fed to a session.
+    ///
+    /// # Returns
+    /// The session input.
+    pub(crate) fn into_session_input(self) -> Result<SessionInputValue<'static>, SurrealError> {
+        let value: DynValue = match self {
+            TensorValue::F32(array) => {
+                safe_eject!(Tensor::from_array(array), SurrealErrorStatus::BadRequest).into_dyn()
+            }
+            TensorValue::F64(array) => {
+                safe_eject!(Tensor::from_array(array), SurrealErrorStatus::BadRequest).into_dyn()
+            }
+            TensorValue::I32(array) => {
+                safe_eject!(Tensor::from_array(array), SurrealErrorStatus::BadRequest).into_dyn()
+            }
+            TensorValue::I64(array) => {
+                safe_eject!(Tensor::from_array(array), SurrealErrorStatus::BadRequest).into_dyn()
+            }
+            TensorValue::Bool(array) => {
+                safe_eject!(Tensor::from_array(array), SurrealErrorStatus::BadRequest).into_dyn()
+            }
+            TensorValue::F16(array) => {
+                safe_eject!(Tensor::from_array(array), SurrealErrorStatus::BadRequest).into_dyn()
+            }
+            TensorValue::String(array) => safe_eject!(
+                Tensor::from_string_array(array),
+                SurrealErrorStatus::BadRequest
+            )
+            .into_dyn(),
+        };
+        Ok(value.into())
+    }
+
+    /// Reads a tensor returned by a session.
+    ///
+    /// # Arguments
+    /// * `value` - The value returned by the session.
+    ///
+    /// # Returns
+    /// The tensor, `None` if the value is not a tensor, or an error if the element type is not supported.
+    pub(crate) fn from_value(value: &DynValue) -> Result<Option<Self>, SurrealError> {
+        let element_type = match value.dtype() {
+            ValueType::Tensor { ty, .. } => *ty,
+            _ => return Ok(None),
+        };
+        let tensor = match element_type {
+            TensorElementType::Float32 => TensorValue::F32(
+                safe_eject!(
+                    value.try_extract_tensor::<f32>(),
+                    SurrealErrorStatus::Unknown
+                )
+                .to_owned(),
+            ),
+            TensorElementType::Float64 => TensorValue::F64(
+                safe_eject!(
+                    value.try_extract_tensor::<f64>(),
+                    SurrealErrorStatus::Unknown
+                )
+                .to_owned(),
+            ),
+            TensorElementType::Int32 => TensorValue::I32(
+                safe_eject!(
+                    value.try_extract_tensor::<i32>(),
+                    SurrealErrorStatus::Unknown
+                )
+                .to_owned(),
+            ),
+            TensorElementType::Int64 => TensorValue::I64(
+                safe_eject!(
+                    value.try_extract_tensor::<i64>(),
+                    SurrealErrorStatus::Unknown
+                )
+                .to_owned(),
+            ),
+            TensorElementType::Bool => TensorValue::Bool(
+                safe_eject!(
+                    value.try_extract_tensor::<bool>(),
+                    SurrealErrorStatus::Unknown
+                )
+                .to_owned(),
+            ),
+            TensorElementType::Float16 => TensorValue::F16(
+                safe_eject!(
+                    value.try_extract_tensor::<f16>(),
+                    SurrealErrorStatus::Unknown
+                )
+                .to_owned(),
+            ),
+            TensorElementType::String => TensorValue::String(safe_eject!(
+                value.try_extract_string_tensor(),
+                SurrealErrorStatus::Unknown
+            )),
+            other => {
+                return Err(SurrealError::new(
+                    format!("Unsupported tensor element type: {:?}", other),
+                    SurrealErrorStatus::BadRequest,
+                ))
+            }
+        };
+        Ok(Some(tensor))
+    }
+}
+
+impl From<ArrayD<f32>> for TensorValue {
+    fn from(array: ArrayD<f32>) -> Self {
+        TensorValue::F32(array)
+    }
+}
+
+impl From<ArrayD<f64>> for TensorValue {
+    fn from(array: ArrayD<f64>) -> Self {
+        TensorValue::F64(array)
+    }
+}
+
+impl From<ArrayD<i32>> for TensorValue {
+    fn from(array: ArrayD<i32>) -> Self {
+        TensorValue::I32(array)
+    }
+}
+
+impl From<ArrayD<i64>> for TensorValue {
+    fn from(array: ArrayD<i64>) -> Self {
+        TensorValue::I64(array)
+    }
+}
+
+impl From<ArrayD<bool>> for TensorValue {
+    fn from(array: ArrayD<bool>) -> Self {
+        TensorValue::Bool(array)
+    }
+}
+
+impl From<ArrayD<String>> for TensorValue {
+    fn from(array: ArrayD<String>) -> Self {
+        TensorValue::String(array)
+    }
+}
+
+impl From<ArrayD<f16>> for TensorValue {
+    fn from(array: ArrayD<f16>) -> Self {
+        TensorValue::F16(array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_shape_and_conversion() {
+        let tensor = TensorValue::from(ndarray::arr2(&[[1i64, 2, 3]]).into_dyn());
+        assert_eq!(tensor.shape(), &[1, 3]);
+        assert_eq!(tensor.type_name(), "i64");
+        assert_eq!(
+            tensor.to_f32().unwrap(),
+            ndarray::arr2(&[[1.0f32, 2.0, 3.0]]).into_dyn()
+        );
+
+        let mask = TensorValue::from(ndarray::arr1(&[true, false]).into_dyn());
+        assert_eq!(
+            mask.to_f32().unwrap(),
+            ndarray::arr1(&[1.0f32, 0.0]).into_dyn()
+        );
+
+        let half = TensorValue::from(ndarray::arr1(&[f16::from_f32(0.5)]).into_dyn());
+        assert_eq!(half.to_f32().unwrap(), ndarray::arr1(&[0.5f32]).into_dyn());
+
+        let labels = TensorValue::from(ndarray::arr1(&["cat".to_string()]).into_dyn());
+        assert_eq!(labels.shape(), &[1]);
+        let error = labels.to_f32().unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+    }
+}
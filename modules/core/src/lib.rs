@@ -53,9 +53,12 @@
 //! let surml_file = SurMlFile::new(header, model_bytes);
 //!
 //! // read and write surml files
-//! surml_file.write("./stash/test.surml").unwrap();
-//! let new_file = SurMlFile::from_file("./stash/test.surml").unwrap();
-//! let file_from_bytes = SurMlFile::from_bytes(surml_file.to_bytes()).unwrap();
+//! # let dir = tempfile::tempdir().unwrap();
+//! # let path = dir.path().join("test.surml");
+//! # let path = path.to_str().unwrap();
+//! surml_file.write(path).unwrap();
+//! let new_file = SurMlFile::from_file(path).unwrap();
+//! let file_from_bytes = SurMlFile::from_bytes(surml_file.to_bytes().unwrap()).unwrap();
 //! ```
 //!
 //! ### Executing models
@@ -67,17 +70,17 @@
 //! use std::collections::HashMap;
 //!
 //!
-//! let mut file = SurMlFile::from_file("./stash/test.surml").unwrap();
+//! let file = SurMlFile::from_file("./stash/test.surml").unwrap();
 //!
-//! let compute_unit = ModelComputation {
-//!     surml_file: &mut file,
-//! };
+//! // the session is built once and reused by every computation
+//! let compute_unit = ModelComputation::new(&file).unwrap();
 //!
 //! // automatically map inputs and apply normalisers to the compute if this data was put in the header
 //! let mut input_values = HashMap::new();
 //! input_values.insert(String::from("squarefoot"), 1000.0);
 //! input_values.insert(String::from("num_floors"), 2.0);
 //!
+//! // the outputs are keyed by the output names in the header
 //! let output = compute_unit.buffered_compute(&mut input_values).unwrap();
 //!
 //! // feed a raw ndarray into the model if no header was provided or if you want to bypass the header
@@ -0,0 +1,684 @@
+//! Defines the declared type, default and allowed range of an input column.
+use std::fmt;
+
+use super::encoding::{PayloadReader, PayloadWriter};
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// The type of the values of an input column.
+///
+/// # Fields
+/// * `Float` - A real number.
+/// * `Int` - A whole number.
+/// * `Bool` - A boolean fed to the model as `1.0` or `0.0`.
+/// * `Categorical` - A string from a fixed set of categories fed to the model as the index of the category.
+/// * `Timestamp` - A point in time fed to the model as seconds since the Unix epoch. Accepts numbers of seconds and
+///   RFC 3339 strings such as `2024-01-31T12:00:00Z` or `2024-01-31`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ColumnType {
+    Float,
+    Int,
+    Bool,
+    Categorical,
+    Timestamp,
+}
+
+impl ColumnType {
+    /// Creates a new `ColumnType` from a string.
+    ///
+    /// # Arguments
+    /// * `data` - The type as a string.
+    ///
+    /// # Returns
+    /// The column type, or an error if the type is not supported.
+    pub fn from_string(data: String) -> Result<Self, SurrealError> {
+        match data.as_str() {
+            "float" => Ok(ColumnType::Float),
+            "int" => Ok(ColumnType::Int),
+            "bool" => Ok(ColumnType::Bool),
+            "categorical" => Ok(ColumnType::Categorical),
+            "timestamp" => Ok(ColumnType::Timestamp),
+            _ => Err(SurrealError::new(
+                format!("Unsupported column type: {}", data),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+
+    /// Parses a value of the column type from a string such as a default passed over the C API. Timestamps are
+    /// parsed as a number of seconds if they are numeric and must otherwise be a valid RFC 3339 timestamp.
+    ///
+    /// # Arguments
+    /// * `data` - The value as a string.
+    ///
+    /// # Returns
+    /// The parsed value.
+    pub fn parse_value(&self, data: &str) -> Result<FeatureValue, SurrealError> {
+        let invalid = || {
+            SurrealError::new(
+                format!("'{}' is not a valid {} value", data, self),
+                SurrealErrorStatus::BadRequest,
+            )
+        };
+        match self {
+            ColumnType::Float => data
+                .parse::<f64>()
+                .map(FeatureValue::Float)
+                .map_err(|_| invalid()),
+            ColumnType::Int => data
+                .parse::<i64>()
+                .map(FeatureValue::Int)
+                .map_err(|_| invalid()),
+            ColumnType::Bool => data
+                .parse::<bool>()
+                .map(FeatureValue::Bool)
+                .map_err(|_| invalid()),
+            ColumnType::Categorical => Ok(FeatureValue::Text(data.to_string())),
+            ColumnType::Timestamp => match data.parse::<f64>() {
+                Ok(seconds) => Ok(FeatureValue::Float(seconds)),
+                Err(_) if parse_timestamp(data).is_some() => {
+                    Ok(FeatureValue::Text(data.to_string()))
+                }
+                Err(_) => Err(invalid()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnType::Float => write!(f, "float"),
+            ColumnType::Int => write!(f, "int"),
+            ColumnType::Bool => write!(f, "bool"),
+            ColumnType::Categorical => write!(f, "categorical"),
+            ColumnType::Timestamp => write!(f, "timestamp"),
+        }
+    }
+}
+
+/// A single input value before it is coerced into the `f32` fed to the model.
+///
+/// # Fields
+/// * `Float` - A real number.
+/// * `Int` - A whole number.
+/// * `Bool` - A boolean.
+/// * `Text` - A string such as a category or a timestamp.
+#[derive(Debug, PartialEq, Clone)]
+pub enum FeatureValue {
+    Float(f64),
+    Int(i64),
+    Bool(bool),
+    Text(String),
+}
+
+impl FeatureValue {
+    /// Writes the value to a payload as a kind byte followed by the value so it is read back as the same kind.
+    pub(crate) fn write(&self, writer: &mut PayloadWriter) -> Result<(), SurrealError> {
+        match self {
+            FeatureValue::Float(value) => {
+                writer.write_u8(0);
+                writer.write_str(&value.to_string())?;
+            }
+            FeatureValue::Int(value) => {
+                writer.write_u8(1);
+                writer.write_str(&value.to_string())?;
+            }
+            FeatureValue::Bool(value) => {
+                writer.write_u8(2);
+                writer.write_u8(*value as u8);
+            }
+            FeatureValue::Text(value) => {
+                writer.write_u8(3);
+                writer.write_str(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a value written by `FeatureValue::write`.
+    pub(crate) fn read(reader: &mut PayloadReader) -> Result<Self, SurrealError> {
+        let invalid = |value: String| {
+            SurrealError::new(
+                format!("Invalid column default: {}", value),
+                SurrealErrorStatus::BadRequest,
+            )
+        };
+        Ok(match reader.read_u8()? {
+            0 => {
+                let value = reader.read_str()?;
+                FeatureValue::Float(value.parse().map_err(|_| invalid(value))?)
+            }
+            1 => {
+                let value = reader.read_str()?;
+                FeatureValue::Int(value.parse().map_err(|_| invalid(value))?)
+            }
+            2 => FeatureValue::Bool(reader.read_u8()? != 0),
+            3 => FeatureValue::Text(reader.read_str()?),
+            kind => {
+                return Err(SurrealError::new(
+                    format!("Unknown column default kind: {}", kind),
+                    SurrealErrorStatus::BadRequest,
+                ))
+            }
+        })
+    }
+}
+
+impl fmt::Display for FeatureValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeatureValue::Float(value) => write!(f, "{}", value),
+            FeatureValue::Int(value) => write!(f, "{}", value),
+            FeatureValue::Bool(value) => write!(f, "{}", value),
+            FeatureValue::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<f32> for FeatureValue {
+    fn from(value: f32) -> Self {
+        FeatureValue::Float(value as f64)
+    }
+}
+
+impl From<f64> for FeatureValue {
+    fn from(value: f64) -> Self {
+        FeatureValue::Float(value)
+    }
+}
+
+impl From<i64> for FeatureValue {
+    fn from(value: i64) -> Self {
+        FeatureValue::Int(value)
+    }
+}
+
+impl From<bool> for FeatureValue {
+    fn from(value: bool) -> Self {
+        FeatureValue::Bool(value)
+    }
+}
+
+impl From<&str> for FeatureValue {
+    fn from(value: &str) -> Self {
+        FeatureValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for FeatureValue {
+    fn from(value: String) -> Self {
+        FeatureValue::Text(value)
+    }
+}
+
+/// The declared type of an input column with an optional default for missing values and an optional allowed range.
+///
+/// # Fields
+/// * `column_type` - The type of the values of the column.
+/// * `default` - The value used when the column is missing from the input values.
+/// * `min` - The smallest allowed value after the value is coerced to a number.
+/// * `max` - The largest allowed value after the value is coerced to a number.
+/// * `categories` - The allowed categories of a categorical column in the order of their index.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColumnSpec {
+    pub column_type: ColumnType,
+    pub default: Option<FeatureValue>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub categories: Vec<String>,
+}
+
+impl ColumnSpec {
+    /// Creates a new `ColumnSpec` with no default, range or categories.
+    ///
+    /// # Arguments
+    /// * `column_type` - The type of the values of the column.
+    ///
+    /// # Returns
+    /// A new `ColumnSpec`.
+    pub fn new(column_type: ColumnType) -> Self {
+        ColumnSpec {
+            column_type,
+            default: None,
+            min: None,
+            max: None,
+            categories: Vec::new(),
+        }
+    }
+
+    /// Coerces an input value of the column into the number fed to the model, filling in the default if the value is
+    /// missing and rejecting values outside of the allowed range.
+    ///
+    /// # Arguments
+    /// * `column` - The name of the column for error messages.
+    /// * `value` - The input value if there is one.
+    ///
+    /// # Returns
+    /// The coerced value.
+    pub fn coerce(&self, column: &str, value: Option<&FeatureValue>) -> Result<f32, SurrealError> {
+        let value = match value.or(self.default.as_ref()) {
+            Some(value) => value,
+            None => {
+                return Err(SurrealError::new(
+                    format!(
+                        "Column {} is missing from the input values and has no default",
+                        column
+                    ),
+                    SurrealErrorStatus::NotFound,
+                ))
+            }
+        };
+        let mismatch = || {
+            SurrealError::new(
+                format!(
+                    "Column {} expects a {} value but got '{}'",
+                    column, self.column_type, value
+                ),
+                SurrealErrorStatus::BadRequest,
+            )
+        };
+        let number = match (self.column_type, value) {
+            (ColumnType::Float, FeatureValue::Float(value)) => *value,
+            (ColumnType::Float, FeatureValue::Int(value)) => *value as f64,
+            (ColumnType::Float, FeatureValue::Bool(value)) => *value as u8 as f64,
+            (ColumnType::Int, FeatureValue::Int(value)) => *value as f64,
+            (ColumnType::Int, FeatureValue::Float(value)) if value.fract() == 0.0 => *value,
+            (ColumnType::Int, FeatureValue::Bool(value)) => *value as u8 as f64,
+            (ColumnType::Bool, FeatureValue::Bool(value)) => *value as u8 as f64,
+            (ColumnType::Bool, FeatureValue::Int(value)) if *value == 0 || *value == 1 => {
+                *value as f64
+            }
+            (ColumnType::Bool, FeatureValue::Float(value)) if *value == 0.0 || *value == 1.0 => {
+                *value
+            }
+            (ColumnType::Categorical, FeatureValue::Text(category)) => {
+                match self.categories.iter().position(|known| known == category) {
+                    Some(index) => index as f64,
+                    None => {
+                        return Err(SurrealError::new(
+                            format!(
+                                "Column {} does not have the category '{}'",
+                                column, category
+                            ),
+                            SurrealErrorStatus::BadRequest,
+                        ))
+                    }
+                }
+            }
+            (ColumnType::Timestamp, FeatureValue::Float(value)) => *value,
+            (ColumnType::Timestamp, FeatureValue::Int(value)) => *value as f64,
+            (ColumnType::Timestamp, FeatureValue::Text(text)) => {
+                parse_timestamp(text).ok_or_else(mismatch)?
+            }
+            // category indexes that were already encoded, for example by a client that only sends floats
+            (ColumnType::Categorical, FeatureValue::Float(value))
+                if value.fract() == 0.0
+                    && *value >= 0.0
+                    && (*value as usize) < self.categories.len() =>
+            {
+                *value
+            }
+            _ => return Err(mismatch()),
+        };
+
+        // NaN fails every comparison, so a non-finite value is rejected outright once a range is set
+        let bounded = self.min.is_some() || self.max.is_some();
+        if (bounded && !number.is_finite())
+            || self.min.is_some_and(|min| number < min)
+            || self.max.is_some_and(|max| number > max)
+        {
+            return Err(SurrealError::new(
+                format!(
+                    "Column {} has the value {} which is outside of the allowed range {}..={}",
+                    column,
+                    value,
+                    self.min.map(|min| min.to_string()).unwrap_or_default(),
+                    self.max.map(|max| max.to_string()).unwrap_or_default(),
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(number as f32)
+    }
+
+    /// Checks that the default can be fed to the model as a value of the column. The category of a categorical
+    /// default is not checked as categories can be added after the spec.
+    ///
+    /// # Arguments
+    /// * `column` - The name of the column for error messages.
+    ///
+    /// # Returns
+    /// An error with a `BadRequest` status if the default does not match the column type or range.
+    pub fn check_default(&self, column: &str) -> Result<(), SurrealError> {
+        match (&self.default, self.column_type) {
+            (None, _) | (Some(FeatureValue::Text(_)), ColumnType::Categorical) => Ok(()),
+            (Some(default), ColumnType::Categorical) => Err(SurrealError::new(
+                format!(
+                    "Column {} expects a categorical default but got '{}'",
+                    column, default
+                ),
+                SurrealErrorStatus::BadRequest,
+            )),
+            (Some(_), _) => self.coerce(column, None).map(|_| ()),
+        }
+    }
+
+    /// Writes the spec to a payload.
+    pub(crate) fn write(&self, writer: &mut PayloadWriter) -> Result<(), SurrealError> {
+        writer.write_str(&self.column_type.to_string())?;
+        match &self.default {
+            Some(default) => {
+                writer.write_u8(1);
+                default.write(writer)?;
+            }
+            None => writer.write_u8(0),
+        }
+        writer.write_optional_str(self.min.map(|min| min.to_string()).as_deref())?;
+        writer.write_optional_str(self.max.map(|max| max.to_string()).as_deref())?;
+        writer.write_u32(self.categories.len() as u32);
+        for category in &self.categories {
+            writer.write_str(category)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a spec written by `ColumnSpec::write`.
+    pub(crate) fn read(reader: &mut PayloadReader) -> Result<Self, SurrealError> {
+        let column_type = ColumnType::from_string(reader.read_str()?)?;
+        let default = match reader.read_u8()? {
+            0 => None,
+            _ => Some(FeatureValue::read(reader)?),
+        };
+        let min = Self::read_bound(reader)?;
+        let max = Self::read_bound(reader)?;
+        let mut categories = Vec::new();
+        for _ in 0..reader.read_u32()? {
+            categories.push(reader.read_str()?);
+        }
+        Ok(ColumnSpec {
+            column_type,
+            default,
+            min,
+            max,
+            categories,
+        })
+    }
+
+    /// Reads an optional bound of the allowed range.
+    fn read_bound(reader: &mut PayloadReader) -> Result<Option<f64>, SurrealError> {
+        match reader.read_optional_str()? {
+            Some(bound) => match bound.parse::<f64>() {
+                Ok(bound) => Ok(Some(bound)),
+                Err(_) => Err(SurrealError::new(
+                    format!("Invalid column bound: {}", bound),
+                    SurrealErrorStatus::BadRequest,
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+/// Parses an RFC 3339 timestamp such as `2024-01-31T12:00:00.5+01:00` or a date such as `2024-01-31`.
+///
+/// # Arguments
+/// * `data` - The timestamp as a string.
+///
+/// # Returns
+/// The seconds since the Unix epoch if the timestamp is valid.
+pub(crate) fn parse_timestamp(data: &str) -> Option<f64> {
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let part = data.get(range)?;
+        if !part.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        part.parse().ok()
+    };
+    let separator = |index: usize, expected: &[u8]| -> Option<()> {
+        expected.contains(data.as_bytes().get(index)?).then_some(())
+    };
+
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    separator(4, b"-")?;
+    separator(7, b"-")?;
+    let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if !(1..=12).contains(&month) || !(1..=days_in_month).contains(&day) {
+        return None;
+    }
+    // days since the epoch from the civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let shifted_year = if month <= 2 { year - 1 } else { year };
+    let era = shifted_year.div_euclid(400);
+    let year_of_era = shifted_year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+    if data.len() == 10 {
+        return Some((days * 86400) as f64);
+    }
+
+    separator(10, b"Tt ")?;
+    separator(13, b":")?;
+    separator(16, b":")?;
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let mut rest = &data[19..];
+    let mut fraction = 0.0;
+    if let Some(digits) = rest.strip_prefix('.') {
+        let length = digits.bytes().take_while(u8::is_ascii_digit).count();
+        if length == 0 {
+            return None;
+        }
+        fraction = format!("0.{}", &digits[..length]).parse().ok()?;
+        rest = &digits[length..];
+    }
+    let offset = match rest {
+        "" | "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            if rest.len() != 6 || rest.as_bytes()[3] != b':' {
+                return None;
+            }
+            let hours: i64 = rest[1..3].parse().ok()?;
+            let minutes: i64 = rest[4..6].parse().ok()?;
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+    let seconds = days * 86400 + hour * 3600 + minute * 60 + second - offset;
+    Some(seconds as f64 + fraction)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_coerce() {
+        let mut spec = ColumnSpec::new(ColumnType::Int);
+        spec.default = Some(FeatureValue::Int(3));
+        spec.min = Some(0.0);
+        spec.max = Some(10.0);
+
+        assert_eq!(
+            spec.coerce("floors", Some(&FeatureValue::Int(2))).unwrap(),
+            2.0
+        );
+        assert_eq!(
+            spec.coerce("floors", Some(&FeatureValue::Float(4.0)))
+                .unwrap(),
+            4.0
+        );
+        assert_eq!(spec.coerce("floors", None).unwrap(), 3.0);
+        assert!(spec
+            .coerce("floors", Some(&FeatureValue::Float(2.5)))
+            .is_err());
+        assert!(spec.coerce("floors", Some(&"two".into())).is_err());
+
+        let error = spec
+            .coerce("floors", Some(&FeatureValue::Int(11)))
+            .unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert_eq!(
+            error.message,
+            "Column floors has the value 11 which is outside of the allowed range 0..=10"
+        );
+
+        let mut spec = ColumnSpec::new(ColumnType::Float);
+        assert!(spec
+            .coerce("squarefoot", Some(&FeatureValue::Float(f64::NAN)))
+            .unwrap()
+            .is_nan());
+        spec.min = Some(0.0);
+        let error = spec
+            .coerce("squarefoot", Some(&FeatureValue::Float(f64::NAN)))
+            .unwrap_err();
+        assert_eq!(
+            error.message,
+            "Column squarefoot has the value NaN which is outside of the allowed range 0..="
+        );
+        assert!(spec
+            .coerce("squarefoot", Some(&FeatureValue::Float(f64::INFINITY)))
+            .is_err());
+        assert!(spec
+            .coerce(
+                "squarefoot",
+                Some(&ColumnType::Float.parse_value("NaN").unwrap())
+            )
+            .is_err());
+        spec.min = None;
+
+        let error = spec.coerce("squarefoot", None).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Column squarefoot is missing from the input values and has no default"
+        );
+    }
+
+    #[test]
+    fn test_coerce_categorical_and_bool() {
+        let mut spec = ColumnSpec::new(ColumnType::Categorical);
+        spec.categories = vec!["flat".to_string(), "house".to_string()];
+        assert_eq!(spec.coerce("kind", Some(&"house".into())).unwrap(), 1.0);
+        assert!(spec.coerce("kind", Some(&"castle".into())).is_err());
+
+        let spec = ColumnSpec::new(ColumnType::Bool);
+        assert_eq!(spec.coerce("garden", Some(&true.into())).unwrap(), 1.0);
+        assert_eq!(
+            spec.coerce("garden", Some(&FeatureValue::Float(0.0)))
+                .unwrap(),
+            0.0
+        );
+        assert!(spec.coerce("garden", Some(&FeatureValue::Int(2))).is_err());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01"), Some(0.0));
+        assert_eq!(parse_timestamp("2024-01-31"), Some(1706659200.0));
+        assert_eq!(parse_timestamp("2024-01-31T12:00:00Z"), Some(1706702400.0));
+        assert_eq!(
+            parse_timestamp("2024-01-31T13:00:00.5+01:00"),
+            Some(1706702400.5)
+        );
+        assert_eq!(parse_timestamp("2024-13-31"), None);
+        assert_eq!(parse_timestamp("2024-02-29"), Some(1709164800.0));
+        assert_eq!(parse_timestamp("2024-02-30"), None);
+        assert_eq!(parse_timestamp("2024-02-31T12:00:00Z"), None);
+        assert_eq!(parse_timestamp("2023-02-29"), None);
+        assert_eq!(parse_timestamp("1900-02-29"), None);
+        assert_eq!(parse_timestamp("2000-02-29"), Some(951782400.0));
+        assert_eq!(parse_timestamp("2024-04-31"), None);
+        assert_eq!(parse_timestamp("yesterday"), None);
+        assert_eq!(parse_timestamp("2024-01-31T12:00"), None);
+    }
+
+    #[test]
+    fn test_write_and_read() {
+        let mut spec = ColumnSpec::new(ColumnType::Categorical);
+        spec.default = Some(FeatureValue::Text("flat".to_string()));
+        spec.categories = vec!["flat".to_string(), "house".to_string()];
+        spec.max = Some(1.5);
+
+        let mut writer = PayloadWriter::new();
+        spec.write(&mut writer).unwrap();
+        let data = writer.finish();
+        let decoded = ColumnSpec::read(&mut PayloadReader::new(&data)).unwrap();
+        assert_eq!(decoded, spec);
+
+        // every default is read back as the kind it was written as
+        for (column_type, default) in [
+            (ColumnType::Timestamp, FeatureValue::Float(1706659200.0)),
+            (
+                ColumnType::Timestamp,
+                FeatureValue::Text("2024-01-31".to_string()),
+            ),
+            (ColumnType::Float, FeatureValue::Int(2)),
+            (ColumnType::Bool, FeatureValue::Bool(true)),
+            (ColumnType::Int, FeatureValue::Float(3.0)),
+        ] {
+            let mut spec = ColumnSpec::new(column_type);
+            spec.default = Some(default);
+            let mut writer = PayloadWriter::new();
+            spec.write(&mut writer).unwrap();
+            let data = writer.finish();
+            let decoded = ColumnSpec::read(&mut PayloadReader::new(&data)).unwrap();
+            assert_eq!(decoded, spec);
+            decoded.coerce("column", None).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(
+            ColumnType::Timestamp.parse_value("1706659200").unwrap(),
+            FeatureValue::Float(1706659200.0)
+        );
+        assert_eq!(
+            ColumnType::Timestamp.parse_value("2024-01-31").unwrap(),
+            FeatureValue::Text("2024-01-31".to_string())
+        );
+        assert!(ColumnType::Timestamp.parse_value("2024-02-31").is_err());
+        assert!(ColumnType::Int.parse_value("2.5").is_err());
+    }
+
+    #[test]
+    fn test_check_default() {
+        for (column_type, default) in [
+            (ColumnType::Bool, FeatureValue::Int(2)),
+            (ColumnType::Int, FeatureValue::Float(2.5)),
+            (ColumnType::Float, FeatureValue::Text("one".to_string())),
+            (
+                ColumnType::Timestamp,
+                FeatureValue::Text("2024-02-31".to_string()),
+            ),
+            (ColumnType::Categorical, FeatureValue::Int(1)),
+        ] {
+            let mut spec = ColumnSpec::new(column_type);
+            spec.default = Some(default);
+            let error = spec.check_default("column").unwrap_err();
+            assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        }
+
+        let mut spec = ColumnSpec::new(ColumnType::Int);
+        spec.default = Some(FeatureValue::Int(20));
+        spec.max = Some(10.0);
+        assert!(spec.check_default("column").is_err());
+
+        // categories can be added after the spec so they are not checked
+        let mut spec = ColumnSpec::new(ColumnType::Categorical);
+        spec.default = Some(FeatureValue::Text("flat".to_string()));
+        spec.check_default("column").unwrap();
+    }
+}
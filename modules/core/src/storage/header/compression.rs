@@ -0,0 +1,133 @@
+//! Defines the compression codec that is applied to the model bytes when they are stored in the file.
+use std::fmt;
+use std::io::{Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use crate::{safe_eject, safe_eject_internal};
+
+/// The compression level used when writing zstd payloads.
+const ZSTD_LEVEL: i32 = 19;
+
+/// Defines how the model bytes are compressed in the file.
+///
+/// # Fields
+/// * `None` - The model bytes are stored as they are.
+/// * `Deflate` - The model bytes are compressed with raw deflate.
+/// * `Zstd` - The model bytes are compressed with zstd.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Compression {
+    None,
+    Deflate,
+    Zstd,
+}
+
+impl Compression {
+    /// Creates a new `Compression` with no compression.
+    ///
+    /// # Returns
+    /// A new `Compression` with no compression.
+    pub fn fresh() -> Self {
+        Compression::None
+    }
+
+    /// Creates a new `Compression` from a string.
+    ///
+    /// # Arguments
+    /// * `codec` - The name of the codec, an empty string means no compression.
+    ///
+    /// # Returns
+    /// A new `Compression`, or an error if the codec is not supported.
+    pub fn from_string(codec: String) -> Result<Self, SurrealError> {
+        match codec.as_str() {
+            "" | "none" => Ok(Compression::None),
+            "deflate" => Ok(Compression::Deflate),
+            "zstd" => Ok(Compression::Zstd),
+            _ => Err(SurrealError::new(
+                format!("Unsupported compression codec: {}", codec),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+
+    /// Compresses the model bytes.
+    ///
+    /// # Arguments
+    /// * `data` - The uncompressed model bytes.
+    ///
+    /// # Returns
+    /// The bytes to be stored in the file.
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>, SurrealError> {
+        match self {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+                safe_eject_internal!(encoder.write_all(data));
+                Ok(safe_eject_internal!(encoder.finish()))
+            }
+            Compression::Zstd => Ok(safe_eject_internal!(zstd::encode_all(data, ZSTD_LEVEL))),
+        }
+    }
+
+    /// Decompresses the model bytes.
+    ///
+    /// # Arguments
+    /// * `data` - The model bytes as stored in the file.
+    ///
+    /// # Returns
+    /// The uncompressed model bytes.
+    pub fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, SurrealError> {
+        match self {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Deflate => {
+                let mut buffer = Vec::new();
+                safe_eject!(
+                    DeflateDecoder::new(data).read_to_end(&mut buffer),
+                    SurrealErrorStatus::BadRequest
+                );
+                Ok(buffer)
+            }
+            Compression::Zstd => Ok(safe_eject!(
+                zstd::decode_all(data),
+                SurrealErrorStatus::BadRequest
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Compression::None => write!(f, ""),
+            Compression::Deflate => write!(f, "deflate"),
+            Compression::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let data = b"tree tree tree tree tree tree tree tree".repeat(50);
+        for codec in [Compression::None, Compression::Deflate, Compression::Zstd] {
+            let compressed = codec.compress(&data).unwrap();
+            if codec != Compression::None {
+                assert!(compressed.len() < data.len());
+            }
+            assert_eq!(codec.decompress(&compressed).unwrap(), data);
+            assert_eq!(Compression::from_string(codec.to_string()).unwrap(), codec);
+        }
+    }
+
+    #[test]
+    fn test_unknown_codec() {
+        let error = Compression::from_string("brotli".to_string()).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+    }
+}
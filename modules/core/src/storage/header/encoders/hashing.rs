@@ -0,0 +1,74 @@
+//! The functionality and parameters around a hashing encoder.
+use super::traits::Encoder;
+use crate::errors::error::SurrealError;
+
+/// An encoder using the hashing trick where a category is hashed into one of a fixed number of buckets so no
+/// vocabulary has to be stored. The hash is 64 bit FNV-1a of the UTF-8 bytes of the category so it is the same on
+/// every platform.
+///
+/// # Fields
+/// * `buckets` - The number of buckets, which is the number of columns a value is expanded into.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Hashing {
+    pub buckets: u32,
+}
+
+impl Hashing {
+    /// The bucket a category is hashed into.
+    ///
+    /// # Arguments
+    /// * `input` - The category to hash.
+    ///
+    /// # Returns
+    /// The index of the bucket.
+    pub fn bucket(&self, input: &str) -> usize {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in input.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        (hash % self.buckets as u64) as usize
+    }
+}
+
+impl Encoder for Hashing {
+    fn width(&self) -> usize {
+        self.buckets as usize
+    }
+
+    /// Encodes a category.
+    ///
+    /// # Arguments
+    /// * `input` - The category to encode.
+    ///
+    /// # Returns
+    /// A `1.0` in the bucket of the category and `0.0` everywhere else.
+    fn encode(&self, input: &str) -> Result<Vec<f32>, SurrealError> {
+        let mut buffer = vec![0.0; self.width()];
+        if !buffer.is_empty() {
+            buffer[self.bucket(input)] = 1.0;
+        }
+        Ok(buffer)
+    }
+
+    fn key() -> String {
+        "hashing".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let encoder = Hashing { buckets: 8 };
+        let encoded = encoder.encode("london").unwrap();
+        assert_eq!(encoded.len(), 8);
+        assert_eq!(encoded.iter().sum::<f32>(), 1.0);
+        assert_eq!(encoded, encoder.encode("london").unwrap());
+        // FNV-1a of the empty string is the offset basis
+        assert_eq!(encoder.bucket(""), (0xcbf29ce484222325u64 % 8) as usize);
+    }
+}
@@ -0,0 +1,212 @@
+//! Defines the loading and saving functionality of encoders that expand string inputs into numeric columns.
+pub mod hashing;
+pub mod one_hot;
+pub mod ordinal;
+pub mod target;
+pub mod traits;
+pub mod wrapper;
+
+use super::encoding::{PayloadReader, PayloadWriter};
+use super::keys::KeyBindings;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use wrapper::EncoderType;
+
+/// A map of encoders so they can be accessed by column name. An encoded column takes a string and is expanded into
+/// `width` numeric columns at its position in the key bindings before the model is run.
+///
+/// # Fields
+/// * `store` - A vector of encoders.
+/// * `store_ref` - A vector of column names to correlate with the encoders in the store.
+#[derive(Debug, PartialEq, Clone)]
+pub struct EncoderMap {
+    pub store: Vec<EncoderType>,
+    pub store_ref: Vec<String>,
+}
+
+impl EncoderMap {
+    /// Constructs a new, empty `EncoderMap`.
+    ///
+    /// # Returns
+    /// A new, empty `EncoderMap`.
+    pub fn fresh() -> Self {
+        EncoderMap {
+            store: Vec::new(),
+            store_ref: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if no columns are encoded.
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    /// Adds an encoder to the map replacing the encoder of the column if it already has one.
+    ///
+    /// # Arguments
+    /// * `encoder` - The encoder to add.
+    /// * `column_name` - The name of the column that is encoded.
+    /// * `keys_reference` - A reference to the key bindings to check the column exists.
+    pub fn add_encoder(
+        &mut self,
+        encoder: EncoderType,
+        column_name: String,
+        keys_reference: &KeyBindings,
+    ) -> Result<(), SurrealError> {
+        if !keys_reference.reference.contains_key(&column_name) {
+            return Err(SurrealError::new(
+                format!("Column {} is not in the key bindings", column_name),
+                SurrealErrorStatus::NotFound,
+            ));
+        }
+        if encoder.width() == 0 {
+            return Err(SurrealError::new(
+                format!(
+                    "The {} encoder of column {} does not produce any columns",
+                    encoder.label(),
+                    column_name
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        match self
+            .store_ref
+            .iter()
+            .position(|column| *column == column_name)
+        {
+            Some(index) => self.store[index] = encoder,
+            None => {
+                self.store.push(encoder);
+                self.store_ref.push(column_name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the encoder of a column.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column.
+    ///
+    /// # Returns
+    /// The encoder of the column if it has one.
+    pub fn get_encoder(&self, column_name: &str) -> Option<&EncoderType> {
+        self.store_ref
+            .iter()
+            .position(|column| column == column_name)
+            .map(|index| &self.store[index])
+    }
+
+    /// The number of values fed to the model once every encoded column is expanded.
+    ///
+    /// # Arguments
+    /// * `keys_reference` - The key bindings holding the columns.
+    ///
+    /// # Returns
+    /// The width of the input.
+    pub fn input_width(&self, keys_reference: &KeyBindings) -> usize {
+        keys_reference
+            .store
+            .iter()
+            .map(|column| self.get_encoder(column).map_or(1, EncoderType::width))
+            .sum()
+    }
+
+    /// Encodes the encoders as a field of the binary header.
+    ///
+    /// # Returns
+    /// The number of encoders followed by the column name and parameters of each encoder.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_u32(self.store.len() as u32);
+        for (index, encoder) in self.store.iter().enumerate() {
+            writer.write_str(&self.store_ref[index])?;
+            encoder.write(&mut writer)?;
+        }
+        Ok(writer.finish())
+    }
+
+    /// Decodes the encoders from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    /// * `keys_reference` - A reference to the key bindings to check the columns exist.
+    ///
+    /// # Returns
+    /// An `EncoderMap` containing the encoders.
+    pub fn from_field(data: &[u8], keys_reference: &KeyBindings) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let mut encoder_map = EncoderMap::fresh();
+        for _ in 0..reader.read_u32()? {
+            let column_name = reader.read_str()?;
+            let encoder = EncoderType::read(&mut reader)?;
+            encoder_map.add_encoder(encoder, column_name, keys_reference)?;
+        }
+        Ok(encoder_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use one_hot::OneHot;
+    use ordinal::Ordinal;
+
+    fn generate_key_bindings() -> KeyBindings {
+        let mut keys = KeyBindings::fresh();
+        keys.add_column("squarefoot".to_string());
+        keys.add_column("colour".to_string());
+        keys.add_column("condition".to_string());
+        keys
+    }
+
+    #[test]
+    fn test_add_encoder() {
+        let keys = generate_key_bindings();
+        let mut encoders = EncoderMap::fresh();
+        encoders
+            .add_encoder(
+                EncoderType::OneHot(OneHot {
+                    vocabulary: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+                }),
+                "colour".to_string(),
+                &keys,
+            )
+            .unwrap();
+        encoders
+            .add_encoder(
+                EncoderType::Ordinal(Ordinal {
+                    categories: vec!["poor".to_string(), "good".to_string()],
+                    unknown: None,
+                }),
+                "condition".to_string(),
+                &keys,
+            )
+            .unwrap();
+        assert_eq!(encoders.input_width(&keys), 5);
+        assert!(encoders.get_encoder("squarefoot").is_none());
+        assert_eq!(
+            encoders.get_encoder("condition").unwrap().label(),
+            "ordinal"
+        );
+
+        let error = encoders
+            .add_encoder(
+                EncoderType::OneHot(OneHot { vocabulary: vec![] }),
+                "colour".to_string(),
+                &keys,
+            )
+            .unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert!(encoders
+            .add_encoder(
+                EncoderType::Hashing(hashing::Hashing { buckets: 4 }),
+                "missing".to_string(),
+                &keys,
+            )
+            .is_err());
+
+        let decoded = EncoderMap::from_field(&encoders.to_field().unwrap(), &keys).unwrap();
+        assert_eq!(decoded, encoders);
+    }
+}
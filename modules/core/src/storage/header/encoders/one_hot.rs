@@ -0,0 +1,54 @@
+//! The functionality and parameters around a one-hot encoder.
+use super::traits::Encoder;
+use crate::errors::error::SurrealError;
+
+/// A one-hot encoder that expands a category into one column per category in the vocabulary.
+///
+/// # Fields
+/// * `vocabulary` - The categories in the order of their columns. A category that is not in the vocabulary is
+///   encoded as all zeros.
+#[derive(Debug, PartialEq, Clone)]
+pub struct OneHot {
+    pub vocabulary: Vec<String>,
+}
+
+impl Encoder for OneHot {
+    fn width(&self) -> usize {
+        self.vocabulary.len()
+    }
+
+    /// Encodes a category.
+    ///
+    /// # Arguments
+    /// * `input` - The category to encode.
+    ///
+    /// # Returns
+    /// A `1.0` in the column of the category and `0.0` everywhere else.
+    fn encode(&self, input: &str) -> Result<Vec<f32>, SurrealError> {
+        Ok(self
+            .vocabulary
+            .iter()
+            .map(|category| if category == input { 1.0 } else { 0.0 })
+            .collect())
+    }
+
+    fn key() -> String {
+        "one_hot".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let encoder = OneHot {
+            vocabulary: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+        };
+        assert_eq!(encoder.width(), 3);
+        assert_eq!(encoder.encode("green").unwrap(), vec![0.0, 1.0, 0.0]);
+        assert_eq!(encoder.encode("purple").unwrap(), vec![0.0, 0.0, 0.0]);
+    }
+}
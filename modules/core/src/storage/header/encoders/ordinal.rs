@@ -0,0 +1,67 @@
+//! The functionality and parameters around an ordinal encoder.
+use super::traits::Encoder;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// An ordinal encoder that maps a category to its index.
+///
+/// # Fields
+/// * `categories` - The categories in the order of their index.
+/// * `unknown` - The value for a category that is not in `categories`. An unknown category is an error without it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ordinal {
+    pub categories: Vec<String>,
+    pub unknown: Option<f32>,
+}
+
+impl Encoder for Ordinal {
+    fn width(&self) -> usize {
+        1
+    }
+
+    /// Encodes a category.
+    ///
+    /// # Arguments
+    /// * `input` - The category to encode.
+    ///
+    /// # Returns
+    /// The index of the category.
+    fn encode(&self, input: &str) -> Result<Vec<f32>, SurrealError> {
+        match self
+            .categories
+            .iter()
+            .position(|category| category == input)
+        {
+            Some(index) => Ok(vec![index as f32]),
+            None => match self.unknown {
+                Some(unknown) => Ok(vec![unknown]),
+                None => Err(SurrealError::new(
+                    format!("Unknown category for ordinal encoder: {}", input),
+                    SurrealErrorStatus::BadRequest,
+                )),
+            },
+        }
+    }
+
+    fn key() -> String {
+        "ordinal".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let mut encoder = Ordinal {
+            categories: vec!["low".to_string(), "medium".to_string(), "high".to_string()],
+            unknown: None,
+        };
+        assert_eq!(encoder.encode("high").unwrap(), vec![2.0]);
+        assert!(encoder.encode("extreme").is_err());
+
+        encoder.unknown = Some(-1.0);
+        assert_eq!(encoder.encode("extreme").unwrap(), vec![-1.0]);
+    }
+}
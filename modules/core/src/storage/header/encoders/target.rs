@@ -0,0 +1,68 @@
+//! The functionality and parameters around a target encoder.
+use super::traits::Encoder;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// A target encoder that replaces a category with a value looked up from a table, usually the mean of the target for
+/// that category in the training data.
+///
+/// # Fields
+/// * `table` - The category and its value.
+/// * `default` - The value for a category that is not in `table`, usually the mean of the target over all the
+///   training data. An unknown category is an error without it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TargetEncoding {
+    pub table: Vec<(String, f32)>,
+    pub default: Option<f32>,
+}
+
+impl Encoder for TargetEncoding {
+    fn width(&self) -> usize {
+        1
+    }
+
+    /// Encodes a category.
+    ///
+    /// # Arguments
+    /// * `input` - The category to encode.
+    ///
+    /// # Returns
+    /// The value of the category in the table.
+    fn encode(&self, input: &str) -> Result<Vec<f32>, SurrealError> {
+        let value = self
+            .table
+            .iter()
+            .find(|(category, _)| category == input)
+            .map(|(_, value)| *value)
+            .or(self.default);
+        match value {
+            Some(value) => Ok(vec![value]),
+            None => Err(SurrealError::new(
+                format!("Unknown category for target encoder: {}", input),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+
+    fn key() -> String {
+        "target".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let mut encoder = TargetEncoding {
+            table: vec![("london".to_string(), 3.5), ("paris".to_string(), 2.25)],
+            default: None,
+        };
+        assert_eq!(encoder.encode("paris").unwrap(), vec![2.25]);
+        assert!(encoder.encode("rome").is_err());
+
+        encoder.default = Some(2.0);
+        assert_eq!(encoder.encode("rome").unwrap(), vec![2.0]);
+    }
+}
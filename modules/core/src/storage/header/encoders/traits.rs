@@ -0,0 +1,13 @@
+//! traits for the encoders module.
+use crate::errors::error::SurrealError;
+
+pub trait Encoder {
+    /// The number of numeric columns a value is expanded into.
+    fn width(&self) -> usize;
+
+    /// Encodes a value into `self.width()` numbers.
+    fn encode(&self, input: &str) -> Result<Vec<f32>, SurrealError>;
+
+    /// Returns the key of the encoder.
+    fn key() -> String;
+}
@@ -0,0 +1,193 @@
+//! Defines the constructing and storing of encoders.
+use super::hashing::Hashing;
+use super::one_hot::OneHot;
+use super::ordinal::Ordinal;
+use super::target::TargetEncoding;
+use super::traits::Encoder;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use crate::storage::header::encoding::{PayloadReader, PayloadWriter};
+
+/// A wrapper for all different types of encoders.
+///
+/// # Arguments
+/// * `OneHot` - A one-hot encoder with a vocabulary.
+/// * `Ordinal` - An ordinal encoder mapping categories to their index.
+/// * `Hashing` - A hashing trick encoder with a fixed number of buckets.
+/// * `Target` - A target encoder with a lookup table.
+#[derive(Debug, PartialEq, Clone)]
+pub enum EncoderType {
+    OneHot(OneHot),
+    Ordinal(Ordinal),
+    Hashing(Hashing),
+    Target(TargetEncoding),
+}
+
+impl EncoderType {
+    /// The label of the encoder such as `one_hot`.
+    pub fn label(&self) -> String {
+        match self {
+            EncoderType::OneHot(_) => OneHot::key(),
+            EncoderType::Ordinal(_) => Ordinal::key(),
+            EncoderType::Hashing(_) => Hashing::key(),
+            EncoderType::Target(_) => TargetEncoding::key(),
+        }
+    }
+
+    /// The number of numeric columns a value is expanded into.
+    pub fn width(&self) -> usize {
+        match self {
+            EncoderType::OneHot(encoder) => encoder.width(),
+            EncoderType::Ordinal(encoder) => encoder.width(),
+            EncoderType::Hashing(encoder) => encoder.width(),
+            EncoderType::Target(encoder) => encoder.width(),
+        }
+    }
+
+    /// Encodes a value.
+    ///
+    /// # Arguments
+    /// * `value` - The value to encode.
+    ///
+    /// # Returns
+    /// The `self.width()` numbers the value is expanded into.
+    pub fn encode(&self, value: &str) -> Result<Vec<f32>, SurrealError> {
+        match self {
+            EncoderType::OneHot(encoder) => encoder.encode(value),
+            EncoderType::Ordinal(encoder) => encoder.encode(value),
+            EncoderType::Hashing(encoder) => encoder.encode(value),
+            EncoderType::Target(encoder) => encoder.encode(value),
+        }
+    }
+
+    /// Writes the encoder to a payload as its label followed by its parameters.
+    pub(crate) fn write(&self, writer: &mut PayloadWriter) -> Result<(), SurrealError> {
+        writer.write_str(&self.label())?;
+        match self {
+            EncoderType::OneHot(encoder) => Self::write_categories(writer, &encoder.vocabulary)?,
+            EncoderType::Ordinal(encoder) => {
+                Self::write_categories(writer, &encoder.categories)?;
+                writer.write_optional_str(
+                    encoder.unknown.map(|value| value.to_string()).as_deref(),
+                )?;
+            }
+            EncoderType::Hashing(encoder) => writer.write_u32(encoder.buckets),
+            EncoderType::Target(encoder) => {
+                writer.write_u32(encoder.table.len() as u32);
+                for (category, value) in &encoder.table {
+                    writer.write_str(category)?;
+                    writer.write_str(&value.to_string())?;
+                }
+                writer.write_optional_str(
+                    encoder.default.map(|value| value.to_string()).as_deref(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads an encoder written by `EncoderType::write`.
+    pub(crate) fn read(reader: &mut PayloadReader) -> Result<Self, SurrealError> {
+        let label = reader.read_str()?;
+        let encoder = match label.as_str() {
+            "one_hot" => EncoderType::OneHot(OneHot {
+                vocabulary: Self::read_categories(reader)?,
+            }),
+            "ordinal" => EncoderType::Ordinal(Ordinal {
+                categories: Self::read_categories(reader)?,
+                unknown: Self::read_optional_number(reader)?,
+            }),
+            "hashing" => EncoderType::Hashing(Hashing {
+                buckets: reader.read_u32()?,
+            }),
+            "target" => {
+                let mut table = Vec::new();
+                for _ in 0..reader.read_u32()? {
+                    let category = reader.read_str()?;
+                    let value = Self::parse_number(&reader.read_str()?)?;
+                    table.push((category, value));
+                }
+                EncoderType::Target(TargetEncoding {
+                    table,
+                    default: Self::read_optional_number(reader)?,
+                })
+            }
+            _ => {
+                return Err(SurrealError::new(
+                    format!("Unknown encoder type: {}", label),
+                    SurrealErrorStatus::Unknown,
+                ))
+            }
+        };
+        Ok(encoder)
+    }
+
+    /// Writes a list of categories prefixed with their count.
+    fn write_categories(
+        writer: &mut PayloadWriter,
+        categories: &[String],
+    ) -> Result<(), SurrealError> {
+        writer.write_u32(categories.len() as u32);
+        for category in categories {
+            writer.write_str(category)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a list of categories written by `EncoderType::write_categories`.
+    fn read_categories(reader: &mut PayloadReader) -> Result<Vec<String>, SurrealError> {
+        (0..reader.read_u32()?).map(|_| reader.read_str()).collect()
+    }
+
+    /// Reads an optional number written as a string.
+    fn read_optional_number(reader: &mut PayloadReader) -> Result<Option<f32>, SurrealError> {
+        match reader.read_optional_str()? {
+            Some(value) => Ok(Some(Self::parse_number(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses a number of an encoder.
+    fn parse_number(value: &str) -> Result<f32, SurrealError> {
+        match value.parse::<f32>() {
+            Ok(value) => Ok(value),
+            Err(_) => Err(SurrealError::new(
+                format!("Invalid encoder value: {}", value),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_write_and_read() {
+        let encoders = vec![
+            EncoderType::OneHot(OneHot {
+                vocabulary: vec!["red".to_string(), "green".to_string()],
+            }),
+            EncoderType::Ordinal(Ordinal {
+                categories: vec!["low".to_string(), "high".to_string()],
+                unknown: Some(-1.0),
+            }),
+            EncoderType::Hashing(Hashing { buckets: 16 }),
+            EncoderType::Target(TargetEncoding {
+                table: vec![("london".to_string(), 3.5)],
+                default: None,
+            }),
+        ];
+        let mut writer = PayloadWriter::new();
+        for encoder in &encoders {
+            encoder.write(&mut writer).unwrap();
+        }
+        let data = writer.finish();
+        let mut reader = PayloadReader::new(&data);
+        for encoder in &encoders {
+            assert_eq!(&EncoderType::read(&mut reader).unwrap(), encoder);
+        }
+        assert!(reader.is_empty());
+    }
+}
@@ -0,0 +1,527 @@
+//! Defines the binary encoding of the header.
+//!
+//! The header starts with a magic number and a format version, followed by a list of fields. Each field is a
+//! `u16` tag, a `u32` payload length and the payload itself (all integers are big endian). Headers written before
+//! this format existed are a `//=>` delimited UTF-8 string and never start with the magic number.
+//!
+//! Readers skip tags they do not know about, so a field that only describes the model, such as its metadata, can be
+//! added without breaking older readers. A field that changes how the model is computed, such as its encoders, is
+//! written with the `tags::CRITICAL` bit set on its tag. A reader rejects a header with a critical field it does not
+//! know about instead of silently computing without it. Version 3 readers are the first to know about the critical
+//! bit, so the format version only has to be bumped again when the layout of the fields changes.
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// The bytes that every binary header starts with.
+pub const MAGIC: [u8; 4] = *b"SMLH";
+
+/// The version of the binary header format that is written by this crate. Version 2 allows chained normaliser
+/// pipelines and named-parameter normaliser definitions in the existing normaliser and output fields, which version
+/// 1 readers cannot parse, so they reject the header instead of misreading it. Version 3 marks the fields that change
+/// how the model is computed as critical, which version 2 readers would skip if they did not know them. Version 1
+/// and 2 headers are still read.
+pub const FORMAT_VERSION: u16 = 3;
+
+/// The tags of the fields in the binary header.
+pub mod tags {
+    /// Set on the tag of a field that a reader has to understand to compute the model.
+    pub const CRITICAL: u16 = 0x8000;
+
+    pub const KEYS: u16 = 1;
+    pub const NORMALISERS: u16 = 2;
+    pub const OUTPUT: u16 = 3;
+    pub const NAME: u16 = 4;
+    pub const VERSION: u16 = 5;
+    pub const DESCRIPTION: u16 = 6;
+    pub const ENGINE: u16 = 7;
+    pub const ORIGIN: u16 = 8;
+    pub const INPUT_DIMS: u16 = 9;
+    pub const DIGEST: u16 = 10;
+    pub const SIGNATURE: u16 = 11;
+    pub const COMPRESSION: u16 = 12;
+    pub const INPUT_BINDINGS: u16 = 13;
+    pub const GRAPH: u16 = 14;
+    pub const COLUMN_SPECS: u16 = 15;
+    pub const ENCODERS: u16 = 16;
+    pub const OUTPUTS: u16 = 17;
+    pub const METADATA: u16 = 18;
+    pub const EXPORTER: u16 = 19;
+}
+
+/// Encodes the length of a field or value as its big endian `u32` prefix. The whole header is read behind a `u32`
+/// length so a longer value can never be read back, and is rejected instead of silently truncating its length.
+///
+/// # Arguments
+/// * `length` - The length of the field or value in bytes.
+///
+/// # Returns
+/// The bytes of the length prefix, or an error with a `BadRequest` status if the length does not fit.
+fn length_prefix(length: usize) -> Result<[u8; 4], SurrealError> {
+    match u32::try_from(length) {
+        Ok(length) => Ok(length.to_be_bytes()),
+        Err(_) => Err(SurrealError::new(
+            format!(
+                "A header value of {} bytes is longer than its u32 length prefix allows",
+                length
+            ),
+            SurrealErrorStatus::BadRequest,
+        )),
+    }
+}
+
+/// Checks if the bytes are a binary header as opposed to the legacy string header.
+///
+/// # Arguments
+/// * `data` - The raw bytes of the header.
+///
+/// # Returns
+/// `true` if the bytes start with the magic number.
+pub fn is_binary(data: &[u8]) -> bool {
+    data.starts_with(&MAGIC)
+}
+
+/// Builds the binary header by appending fields after the magic number and format version.
+///
+/// # Fields
+/// * `buffer` - The bytes of the header written so far.
+pub struct HeaderWriter {
+    buffer: Vec<u8>,
+}
+
+impl HeaderWriter {
+    /// Creates a new writer with the magic number and format version already written.
+    ///
+    /// # Returns
+    /// A new `HeaderWriter`.
+    pub fn new() -> Self {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&MAGIC);
+        buffer.extend_from_slice(&FORMAT_VERSION.to_be_bytes());
+        HeaderWriter { buffer }
+    }
+
+    /// Continues a header that has already been written so more fields can be appended, for example the digest and
+    /// signature after the header has been compiled.
+    ///
+    /// # Arguments
+    /// * `buffer` - The bytes of the header written so far including the magic number.
+    ///
+    /// # Returns
+    /// A `HeaderWriter` that appends after the existing fields.
+    pub fn resume(buffer: Vec<u8>) -> Self {
+        HeaderWriter { buffer }
+    }
+
+    /// Appends a field that a reader has to understand to compute the model, so readers that do not know the tag
+    /// reject the header.
+    ///
+    /// # Arguments
+    /// * `tag` - The tag of the field.
+    /// * `payload` - The encoded value of the field.
+    ///
+    /// # Returns
+    /// An error with a `BadRequest` status if the payload is too long for its length prefix.
+    pub fn write_critical_field(&mut self, tag: u16, payload: &[u8]) -> Result<(), SurrealError> {
+        self.write_field(tag | tags::CRITICAL, payload)
+    }
+
+    /// Appends a field to the header that readers can skip if they do not know the tag.
+    ///
+    /// # Arguments
+    /// * `tag` - The tag of the field.
+    /// * `payload` - The encoded value of the field.
+    ///
+    /// # Returns
+    /// An error with a `BadRequest` status if the payload is too long for its length prefix.
+    pub fn write_field(&mut self, tag: u16, payload: &[u8]) -> Result<(), SurrealError> {
+        let length = length_prefix(payload.len())?;
+        self.buffer.extend_from_slice(&tag.to_be_bytes());
+        self.buffer.extend_from_slice(&length);
+        self.buffer.extend_from_slice(payload);
+        Ok(())
+    }
+
+    /// Consumes the writer returning the bytes of the header.
+    ///
+    /// # Returns
+    /// The bytes of the header.
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+impl Default for HeaderWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single field read from a binary header.
+///
+/// # Fields
+/// * `tag` - The tag of the field without the critical bit.
+/// * `critical` - Whether the field has to be understood to compute the model.
+/// * `payload` - The encoded value of the field.
+/// * `offset` - The position in the header bytes where the field starts.
+#[derive(Debug, PartialEq)]
+pub struct Field<'a> {
+    pub tag: u16,
+    pub critical: bool,
+    pub payload: &'a [u8],
+    pub offset: usize,
+}
+
+/// Splits a binary header into its fields. The digest and signature only cover the bytes before them, so a field
+/// after the signature, a field other than the signature after the digest or a second field with the same tag could
+/// change the decoded header without breaking them, and is rejected.
+///
+/// # Arguments
+/// * `data` - The raw bytes of the header including the magic number.
+///
+/// # Returns
+/// The format version and the fields in the order they were written.
+pub fn read_fields(data: &[u8]) -> Result<(u16, Vec<Field<'_>>), SurrealError> {
+    if !is_binary(data) {
+        return Err(SurrealError::new(
+            "Header does not start with the surml magic number".to_string(),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+    let mut reader = PayloadReader::new(&data[MAGIC.len()..]);
+    let version = reader.read_u16()?;
+    if version > FORMAT_VERSION {
+        return Err(SurrealError::new(
+            format!(
+                "Header format version {} is newer than the supported version {}",
+                version, FORMAT_VERSION
+            ),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+
+    let mut fields = Vec::new();
+    while !reader.is_empty() {
+        let offset = MAGIC.len() + reader.position();
+        let tag = reader.read_u16()?;
+        let length = reader.read_u32()? as usize;
+        let payload = reader.read_slice(length)?;
+        let tag_value = tag & !tags::CRITICAL;
+        if fields.iter().any(|field: &Field| field.tag == tag_value) {
+            return Err(SurrealError::new(
+                format!("Header field {} is written more than once", tag_value),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        if fields.iter().any(|field| field.tag == tags::SIGNATURE) {
+            return Err(SurrealError::new(
+                format!(
+                    "Header field {} is written after the signature so it is not covered by it",
+                    tag_value
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        if tag_value != tags::SIGNATURE && fields.iter().any(|field| field.tag == tags::DIGEST) {
+            return Err(SurrealError::new(
+                format!(
+                    "Header field {} is written after the digest so it is not covered by it",
+                    tag_value
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        fields.push(Field {
+            tag: tag_value,
+            critical: tag & tags::CRITICAL != 0,
+            payload,
+            offset,
+        });
+    }
+    Ok((version, fields))
+}
+
+/// Encodes the values inside a single field.
+///
+/// # Fields
+/// * `buffer` - The bytes written so far.
+pub struct PayloadWriter {
+    buffer: Vec<u8>,
+}
+
+impl PayloadWriter {
+    /// Creates a new empty writer.
+    ///
+    /// # Returns
+    /// A new `PayloadWriter`.
+    pub fn new() -> Self {
+        PayloadWriter { buffer: Vec::new() }
+    }
+
+    /// Writes a single byte.
+    pub fn write_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    /// Writes a big endian `u32`.
+    pub fn write_u32(&mut self, value: u32) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    /// Writes a length-prefixed byte slice, returning an error with a `BadRequest` status if it is too long for its
+    /// length prefix.
+    pub fn write_bytes(&mut self, value: &[u8]) -> Result<(), SurrealError> {
+        self.buffer.extend_from_slice(&length_prefix(value.len())?);
+        self.buffer.extend_from_slice(value);
+        Ok(())
+    }
+
+    /// Writes a length-prefixed UTF-8 string.
+    pub fn write_str(&mut self, value: &str) -> Result<(), SurrealError> {
+        self.write_bytes(value.as_bytes())
+    }
+
+    /// Writes a flag for whether the string is present followed by the string if it is.
+    pub fn write_optional_str(&mut self, value: Option<&str>) -> Result<(), SurrealError> {
+        match value {
+            Some(value) => {
+                self.write_u8(1);
+                self.write_str(value)
+            }
+            None => {
+                self.write_u8(0);
+                Ok(())
+            }
+        }
+    }
+
+    /// Consumes the writer returning the encoded bytes.
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+impl Default for PayloadWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decodes the values inside a single field.
+///
+/// # Fields
+/// * `data` - The bytes being read.
+/// * `cursor` - The position of the next byte to be read.
+pub struct PayloadReader<'a> {
+    data: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> PayloadReader<'a> {
+    /// Creates a new reader at the start of the data.
+    ///
+    /// # Arguments
+    /// * `data` - The bytes to be read.
+    ///
+    /// # Returns
+    /// A new `PayloadReader`.
+    pub fn new(data: &'a [u8]) -> Self {
+        PayloadReader { data, cursor: 0 }
+    }
+
+    /// Returns `true` if all the bytes have been read.
+    pub fn is_empty(&self) -> bool {
+        self.cursor >= self.data.len()
+    }
+
+    /// Returns the number of bytes that have been read.
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// Reads the next `length` bytes.
+    pub fn read_slice(&mut self, length: usize) -> Result<&'a [u8], SurrealError> {
+        let end = match self.cursor.checked_add(length) {
+            Some(end) if end <= self.data.len() => end,
+            _ => {
+                return Err(SurrealError::new(
+                    "Not enough bytes to read header field, the header is truncated".to_string(),
+                    SurrealErrorStatus::BadRequest,
+                ))
+            }
+        };
+        let slice = &self.data[self.cursor..end];
+        self.cursor = end;
+        Ok(slice)
+    }
+
+    /// Reads a single byte.
+    pub fn read_u8(&mut self) -> Result<u8, SurrealError> {
+        Ok(self.read_slice(1)?[0])
+    }
+
+    /// Reads a big endian `u16`.
+    pub fn read_u16(&mut self) -> Result<u16, SurrealError> {
+        let mut buffer = [0u8; 2];
+        buffer.copy_from_slice(self.read_slice(2)?);
+        Ok(u16::from_be_bytes(buffer))
+    }
+
+    /// Reads a big endian `u32`.
+    pub fn read_u32(&mut self) -> Result<u32, SurrealError> {
+        let mut buffer = [0u8; 4];
+        buffer.copy_from_slice(self.read_slice(4)?);
+        Ok(u32::from_be_bytes(buffer))
+    }
+
+    /// Reads a length-prefixed byte slice.
+    pub fn read_bytes(&mut self) -> Result<&'a [u8], SurrealError> {
+        let length = self.read_u32()? as usize;
+        self.read_slice(length)
+    }
+
+    /// Reads a length-prefixed UTF-8 string.
+    pub fn read_str(&mut self) -> Result<String, SurrealError> {
+        let bytes = self.read_bytes()?;
+        match std::str::from_utf8(bytes) {
+            Ok(value) => Ok(value.to_string()),
+            Err(error) => Err(SurrealError::new(
+                format!("Invalid UTF-8 string in header field: {}", error),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+
+    /// Reads a string written with `PayloadWriter::write_optional_str`.
+    pub fn read_optional_str(&mut self) -> Result<Option<String>, SurrealError> {
+        match self.read_u8()? {
+            0 => Ok(None),
+            _ => Ok(Some(self.read_str()?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_write_and_read_fields() {
+        let mut payload = PayloadWriter::new();
+        payload.write_str("a//=>b").unwrap();
+        payload.write_optional_str(None).unwrap();
+        payload.write_optional_str(Some("c=>d")).unwrap();
+
+        let mut writer = HeaderWriter::new();
+        writer.write_field(tags::NAME, &payload.finish()).unwrap();
+        writer.write_field(500, &[1, 2, 3]).unwrap();
+        let bytes = writer.finish();
+
+        assert!(is_binary(&bytes));
+        let (version, fields) = read_fields(&bytes).unwrap();
+        assert_eq!(version, FORMAT_VERSION);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].tag, tags::NAME);
+        assert_eq!(fields[0].offset, 6);
+        assert_eq!(fields[1].tag, 500);
+        assert_eq!(fields[1].payload, &[1, 2, 3]);
+
+        let mut reader = PayloadReader::new(fields[0].payload);
+        assert_eq!(reader.read_str().unwrap(), "a//=>b");
+        assert_eq!(reader.read_optional_str().unwrap(), None);
+        assert_eq!(
+            reader.read_optional_str().unwrap(),
+            Some("c=>d".to_string())
+        );
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_critical_field() {
+        let mut writer = HeaderWriter::new();
+        writer
+            .write_critical_field(tags::ENCODERS, &[1, 2, 3])
+            .unwrap();
+        writer.write_field(tags::METADATA, &[4]).unwrap();
+        let bytes = writer.finish();
+        assert_eq!(
+            &bytes[MAGIC.len() + 2..MAGIC.len() + 4],
+            &(tags::ENCODERS | tags::CRITICAL).to_be_bytes()
+        );
+
+        let (_, fields) = read_fields(&bytes).unwrap();
+        assert_eq!(fields[0].tag, tags::ENCODERS);
+        assert!(fields[0].critical);
+        assert_eq!(fields[0].payload, &[1, 2, 3]);
+        assert_eq!(fields[1].tag, tags::METADATA);
+        assert!(!fields[1].critical);
+    }
+
+    #[test]
+    fn test_length_prefix_overflow() {
+        assert_eq!(length_prefix(3).unwrap(), [0, 0, 0, 3]);
+        let error = length_prefix(u32::MAX as usize + 1).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert_eq!(
+            error.message,
+            "A header value of 4294967296 bytes is longer than its u32 length prefix allows"
+        );
+    }
+
+    #[test]
+    fn test_fields_after_signature_and_duplicates() {
+        let mut writer = HeaderWriter::new();
+        writer.write_field(tags::NAME, &[1]).unwrap();
+        writer.write_field(tags::SIGNATURE, &[2]).unwrap();
+        let signed = writer.finish();
+        read_fields(&signed).unwrap();
+
+        let mut bytes = signed.clone();
+        bytes.extend_from_slice(&(tags::KEYS | tags::CRITICAL).to_be_bytes());
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.push(3);
+        let error = read_fields(&bytes).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert_eq!(
+            error.message,
+            "Header field 1 is written after the signature so it is not covered by it"
+        );
+
+        let mut writer = HeaderWriter::new();
+        writer.write_field(tags::NAME, &[1]).unwrap();
+        writer.write_critical_field(tags::NAME, &[2]).unwrap();
+        let error = read_fields(&writer.finish()).unwrap_err();
+        assert_eq!(error.message, "Header field 4 is written more than once");
+    }
+
+    #[test]
+    fn test_truncated_field() {
+        let mut writer = HeaderWriter::new();
+        writer.write_field(tags::NAME, &[1, 2, 3, 4]).unwrap();
+        let mut bytes = writer.finish();
+        bytes.truncate(bytes.len() - 1);
+
+        let error = read_fields(&bytes).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_older_format_version() {
+        let mut writer = HeaderWriter::new();
+        writer.write_field(tags::NAME, &[1, 2, 3]).unwrap();
+        let mut bytes = writer.finish();
+        bytes[MAGIC.len()..MAGIC.len() + 2].copy_from_slice(&1u16.to_be_bytes());
+
+        let (version, fields) = read_fields(&bytes).unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(fields[0].payload, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_newer_format_version() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&(FORMAT_VERSION + 1).to_be_bytes());
+
+        let error = read_fields(&bytes).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+    }
+}
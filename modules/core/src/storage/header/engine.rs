@@ -1,16 +1,33 @@
-//! Defines the placeholder for the type of model engine in the header.
+//! Defines the type of model engine in the header and the exporter that converted the model to ONNX.
 use std::fmt;
 
+use super::encoding::{PayloadReader, PayloadWriter};
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
 /// Defines the type of engine being used to run the model.
 ///
 /// # Fields
 /// * `Native` - The native engine which will be native rust and linfa.
-/// * `PyTorch` - The PyTorch engine which will be PyTorch and tch-rs.
+/// * `PyTorch` - The PyTorch engine which will be PyTorch and ONNX.
+/// * `Sklearn` - The sklearn engine which will be sklearn and ONNX.
+/// * `TensorFlow` - The TensorFlow engine, including Keras models, which will be TensorFlow and ONNX.
+/// * `Onnx` - The ONNX engine where the model was already an ONNX model.
+/// * `XGBoost` - The XGBoost engine which will be XGBoost and ONNX.
+/// * `LightGBM` - The LightGBM engine which will be LightGBM and ONNX.
+/// * `Candle` - The candle engine where the weights are stored as safetensors.
+/// * `Unknown` - An engine written by a newer version that is kept as it was stored.
 /// * `Undefined` - The undefined engine which will be used when the engine is not defined.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Engine {
     Native,
     PyTorch,
+    Sklearn,
+    TensorFlow,
+    Onnx,
+    XGBoost,
+    LightGBM,
+    Candle,
+    Unknown(String),
     Undefined,
 }
 
@@ -23,18 +40,59 @@ impl Engine {
         Engine::Undefined
     }
 
-    /// Creates a new `Engine` struct from a string.
+    /// Creates a new `Engine` struct from a string. An empty string is the undefined engine written by older headers.
     ///
     /// # Arguments
-    /// * `engine` - The engine as a string.
+    /// * `engine` - The engine as a string such as `pytorch`, `keras` or `safetensors`.
     ///
     /// # Returns
-    /// A new `Engine` struct.
-    pub fn from_string(engine: String) -> Self {
-        match engine.as_str() {
-            "native" => Engine::Native,
-            "pytorch" => Engine::PyTorch,
-            _ => Engine::Undefined,
+    /// A new `Engine` struct or an error if the engine is not known.
+    pub fn from_string(engine: String) -> Result<Self, SurrealError> {
+        match engine.trim().to_lowercase().as_str() {
+            "" => Ok(Engine::Undefined),
+            "native" => Ok(Engine::Native),
+            "pytorch" | "torch" => Ok(Engine::PyTorch),
+            "sklearn" | "scikit-learn" => Ok(Engine::Sklearn),
+            "tensorflow" | "keras" => Ok(Engine::TensorFlow),
+            "onnx" => Ok(Engine::Onnx),
+            "xgboost" => Ok(Engine::XGBoost),
+            "lightgbm" => Ok(Engine::LightGBM),
+            "candle" | "safetensors" => Ok(Engine::Candle),
+            _ => Err(SurrealError::new(
+                format!("Unknown engine: {}", engine),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+
+    /// Creates a new `Engine` struct from the string stored in a header. An engine this version does not know is
+    /// kept as it was stored so headers from newer writers still load.
+    ///
+    /// # Arguments
+    /// * `engine` - The engine as it was stored.
+    ///
+    /// # Returns
+    /// The engine.
+    pub fn from_stored(engine: String) -> Self {
+        match Self::from_string(engine.clone()) {
+            Ok(engine) => engine,
+            Err(_) => Engine::Unknown(engine),
+        }
+    }
+
+    /// Infers the engine from the producer recorded in an ONNX graph by the tool that exported it.
+    ///
+    /// # Arguments
+    /// * `producer` - The producer name of the graph such as `skl2onnx` or `tf2onnx`.
+    ///
+    /// # Returns
+    /// The engine if the producer is a known exporter.
+    pub fn from_producer(producer: &str) -> Option<Self> {
+        match producer.to_lowercase().as_str() {
+            "pytorch" => Some(Engine::PyTorch),
+            "skl2onnx" => Some(Engine::Sklearn),
+            "tf2onnx" | "keras2onnx" => Some(Engine::TensorFlow),
+            _ => None,
         }
     }
 }
@@ -44,7 +102,154 @@ impl fmt::Display for Engine {
         match self {
             Engine::Native => write!(f, "native"),
             Engine::PyTorch => write!(f, "pytorch"),
+            Engine::Sklearn => write!(f, "sklearn"),
+            Engine::TensorFlow => write!(f, "tensorflow"),
+            Engine::Onnx => write!(f, "onnx"),
+            Engine::XGBoost => write!(f, "xgboost"),
+            Engine::LightGBM => write!(f, "lightgbm"),
+            Engine::Candle => write!(f, "candle"),
+            Engine::Unknown(engine) => write!(f, "{}", engine),
             Engine::Undefined => write!(f, ""),
         }
     }
 }
+
+/// The tool that converted the model to ONNX so models exported by a broken exporter can be found and re-exported.
+///
+/// # Fields
+/// * `name` - The name of the exporter such as `skl2onnx`, `torch.onnx` or `tf2onnx`.
+/// * `version` - The version of the exporter.
+/// * `opset` - The ONNX opset the model was exported with.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Exporter {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub opset: Option<u32>,
+}
+
+impl Exporter {
+    /// Creates a new `Exporter` with nothing recorded.
+    ///
+    /// # Returns
+    /// A new empty `Exporter`.
+    pub fn fresh() -> Self {
+        Exporter {
+            name: None,
+            version: None,
+            opset: None,
+        }
+    }
+
+    /// Returns `true` if nothing about the exporter has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.version.is_none() && self.opset.is_none()
+    }
+
+    /// Encodes the exporter as a field of the binary header.
+    ///
+    /// # Returns
+    /// The name, version and opset each written as an optional string.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_optional_str(self.name.as_deref())?;
+        writer.write_optional_str(self.version.as_deref())?;
+        writer.write_optional_str(self.opset.map(|opset| opset.to_string()).as_deref())?;
+        Ok(writer.finish())
+    }
+
+    /// Decodes the exporter from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    ///
+    /// # Returns
+    /// The exporter decoded from the field.
+    pub fn from_field(data: &[u8]) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let name = reader.read_optional_str()?;
+        let version = reader.read_optional_str()?;
+        let opset = match reader.read_optional_str()? {
+            Some(opset) => Some(opset.parse::<u32>().map_err(|_| {
+                SurrealError::new(
+                    format!("Invalid opset: {}", opset),
+                    SurrealErrorStatus::BadRequest,
+                )
+            })?),
+            None => None,
+        };
+        Ok(Exporter {
+            name,
+            version,
+            opset,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_from_string() {
+        for engine in [
+            Engine::Native,
+            Engine::PyTorch,
+            Engine::Sklearn,
+            Engine::TensorFlow,
+            Engine::Onnx,
+            Engine::XGBoost,
+            Engine::LightGBM,
+            Engine::Candle,
+            Engine::Undefined,
+        ] {
+            assert_eq!(Engine::from_string(engine.to_string()).unwrap(), engine);
+        }
+        assert_eq!(
+            Engine::from_string("Keras".to_string()).unwrap(),
+            Engine::TensorFlow
+        );
+        assert_eq!(
+            Engine::from_string("safetensors".to_string()).unwrap(),
+            Engine::Candle
+        );
+        assert_eq!(
+            Engine::from_string("caffe".to_string())
+                .unwrap_err()
+                .message,
+            "Unknown engine: caffe"
+        );
+    }
+
+    #[test]
+    fn test_from_stored() {
+        assert_eq!(Engine::from_stored("keras".to_string()), Engine::TensorFlow);
+        let engine = Engine::from_stored("caffe".to_string());
+        assert_eq!(engine, Engine::Unknown("caffe".to_string()));
+        assert_eq!(engine.to_string(), "caffe");
+    }
+
+    #[test]
+    fn test_from_producer() {
+        assert_eq!(Engine::from_producer("skl2onnx"), Some(Engine::Sklearn));
+        assert_eq!(Engine::from_producer("tf2onnx"), Some(Engine::TensorFlow));
+        assert_eq!(Engine::from_producer("onnxmltools"), None);
+    }
+
+    #[test]
+    fn test_exporter_field_round_trip() {
+        let exporter = Exporter {
+            name: Some("skl2onnx".to_string()),
+            version: Some("1.16.0".to_string()),
+            opset: Some(17),
+        };
+        assert_eq!(
+            Exporter::from_field(&exporter.to_field().unwrap()).unwrap(),
+            exporter
+        );
+        assert_eq!(
+            Exporter::from_field(&Exporter::fresh().to_field().unwrap()).unwrap(),
+            Exporter::fresh()
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Defines the description of the ONNX graph that is inferred from the model and stored in the header.
+use ort::session::Session;
+use ort::value::ValueType;
+
+use super::encoding::{PayloadReader, PayloadWriter};
+use super::input_dims::{Dimension, InputDims};
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use crate::safe_eject;
+
+/// The name, element type and shape of an input or output of the ONNX graph.
+///
+/// # Fields
+/// * `name` - The name of the input or output in the ONNX graph.
+/// * `dtype` - The element type of the tensor such as `f32` or `i64`, or the kind of value if it is not a tensor.
+/// * `shape` - The declared shape of the tensor with its named dynamic axes.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TensorSpec {
+    pub name: String,
+    pub dtype: String,
+    pub shape: InputDims,
+}
+
+impl TensorSpec {
+    /// Describes an input or output of the ONNX graph.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the input or output in the ONNX graph.
+    /// * `value_type` - The type of the input or output.
+    ///
+    /// # Returns
+    /// The description of the input or output.
+    fn from_value_type(name: &str, value_type: &ValueType) -> Self {
+        let (dtype, shape) = match value_type {
+            ValueType::Tensor {
+                ty,
+                dimensions,
+                dimension_symbols,
+            } => (
+                ty.to_string().to_lowercase(),
+                Self::shape(dimensions, dimension_symbols),
+            ),
+            ValueType::Sequence(_) => ("sequence".to_string(), InputDims::fresh()),
+            ValueType::Map { .. } => ("map".to_string(), InputDims::fresh()),
+            ValueType::Optional(_) => ("optional".to_string(), InputDims::fresh()),
+        };
+        TensorSpec {
+            name: name.to_string(),
+            dtype,
+            shape,
+        }
+    }
+
+    /// Builds the shape of a tensor naming the dynamic axes after their symbols in the graph.
+    ///
+    /// # Arguments
+    /// * `dimensions` - The dimensions of the tensor where dynamic dimensions are negative.
+    /// * `symbols` - The symbols of the dimensions.
+    ///
+    /// # Returns
+    /// The shape of the tensor.
+    fn shape(dimensions: &[i64], symbols: &[Option<String>]) -> InputDims {
+        let mut shape = InputDims::from_model_dims(dimensions);
+        for (dim, symbol) in shape.dims.iter_mut().zip(symbols) {
+            if let (Dimension::Dynamic(name), Some(symbol)) = (dim, symbol) {
+                // symbols that are not identifiers can not be written back so they stay unnamed
+                if InputDims::from_string(symbol.clone())
+                    .is_ok_and(|parsed| matches!(parsed.dims.as_slice(), [Dimension::Dynamic(_)]))
+                {
+                    *name = symbol.clone();
+                }
+            }
+        }
+        shape
+    }
+
+    /// The number of values in a single row of the tensor, ignoring the dynamic axes.
+    pub fn row_size(&self) -> usize {
+        self.shape
+            .dims
+            .iter()
+            .map(|dim| match dim {
+                Dimension::Fixed(size) => *size,
+                Dimension::Dynamic(_) => 1,
+            })
+            .product()
+    }
+
+    /// Writes the spec to a payload.
+    fn write(&self, writer: &mut PayloadWriter) -> Result<(), SurrealError> {
+        writer.write_str(&self.name)?;
+        writer.write_str(&self.dtype)?;
+        writer.write_str(&self.shape.to_string())?;
+        Ok(())
+    }
+
+    /// Reads a spec written by `TensorSpec::write`.
+    fn read(reader: &mut PayloadReader) -> Result<Self, SurrealError> {
+        Ok(TensorSpec {
+            name: reader.read_str()?,
+            dtype: reader.read_str()?,
+            shape: InputDims::from_string(reader.read_str()?)?,
+        })
+    }
+}
+
+/// Describes the ONNX graph of the model so the model can be inspected without building a session.
+///
+/// # Fields
+/// * `inputs` - The inputs of the graph in the order they are declared.
+/// * `outputs` - The outputs of the graph in the order they are declared.
+/// * `producer` - The name of the tool that produced the graph such as `skl2onnx` or `pytorch`.
+/// * `metadata` - The custom metadata_props of the graph as key value pairs.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Graph {
+    pub inputs: Vec<TensorSpec>,
+    pub outputs: Vec<TensorSpec>,
+    pub producer: Option<String>,
+    pub metadata: Vec<(String, String)>,
+}
+
+impl Graph {
+    /// Creates a new `Graph` with nothing inferred.
+    ///
+    /// # Returns
+    /// A new empty `Graph`.
+    pub fn fresh() -> Self {
+        Graph {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            producer: None,
+            metadata: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if nothing has been inferred from the graph.
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+            && self.outputs.is_empty()
+            && self.producer.is_none()
+            && self.metadata.is_empty()
+    }
+
+    /// Inspects the graph of a session.
+    ///
+    /// # Arguments
+    /// * `session` - The session built from the model.
+    ///
+    /// # Returns
+    /// The description of the graph.
+    pub fn from_session(session: &Session) -> Result<Self, SurrealError> {
+        let inputs = session
+            .inputs
+            .iter()
+            .map(|input| TensorSpec::from_value_type(&input.name, &input.input_type))
+            .collect();
+        let outputs = session
+            .outputs
+            .iter()
+            .map(|output| TensorSpec::from_value_type(&output.name, &output.output_type))
+            .collect();
+
+        let model_metadata = safe_eject!(session.metadata(), SurrealErrorStatus::BadRequest);
+        let producer = safe_eject!(model_metadata.producer(), SurrealErrorStatus::BadRequest);
+        let mut metadata = Vec::new();
+        for key in safe_eject!(model_metadata.custom_keys(), SurrealErrorStatus::BadRequest) {
+            if let Some(value) =
+                safe_eject!(model_metadata.custom(&key), SurrealErrorStatus::BadRequest)
+            {
+                metadata.push((key, value));
+            }
+        }
+        metadata.sort();
+        Ok(Graph {
+            inputs,
+            outputs,
+            producer: (!producer.is_empty()).then_some(producer),
+            metadata,
+        })
+    }
+
+    /// Gets a custom metadata value of the graph.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the metadata_props entry.
+    ///
+    /// # Returns
+    /// The value if the graph has the key.
+    pub fn get_metadata(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Encodes the graph as a field of the binary header.
+    ///
+    /// # Returns
+    /// The inputs, outputs, producer and metadata each prefixed with their count.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        for specs in [&self.inputs, &self.outputs] {
+            writer.write_u32(specs.len() as u32);
+            for spec in specs {
+                spec.write(&mut writer)?;
+            }
+        }
+        writer.write_optional_str(self.producer.as_deref())?;
+        writer.write_u32(self.metadata.len() as u32);
+        for (key, value) in &self.metadata {
+            writer.write_str(key)?;
+            writer.write_str(value)?;
+        }
+        Ok(writer.finish())
+    }
+
+    /// Decodes the graph from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    ///
+    /// # Returns
+    /// The graph decoded from the field.
+    pub fn from_field(data: &[u8]) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let mut graph = Graph::fresh();
+        for specs in [&mut graph.inputs, &mut graph.outputs] {
+            for _ in 0..reader.read_u32()? {
+                specs.push(TensorSpec::read(&mut reader)?);
+            }
+        }
+        graph.producer = reader.read_optional_str()?;
+        for _ in 0..reader.read_u32()? {
+            let key = reader.read_str()?;
+            let value = reader.read_str()?;
+            graph.metadata.push((key, value));
+        }
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_shape_names_dynamic_axes() {
+        let shape = TensorSpec::shape(
+            &[-1, -1, 8],
+            &[Some("batch".to_string()), Some("seq len".to_string()), None],
+        );
+        assert_eq!(shape.to_string(), "batch,?,8");
+    }
+
+    #[test]
+    fn test_field_round_trip() {
+        let graph = Graph {
+            inputs: vec![TensorSpec {
+                name: "float_input".to_string(),
+                dtype: "f32".to_string(),
+                shape: InputDims::from_string("batch,2".to_string()).unwrap(),
+            }],
+            outputs: vec![TensorSpec {
+                name: "variable".to_string(),
+                dtype: "f32".to_string(),
+                shape: InputDims::from_string("?,1".to_string()).unwrap(),
+            }],
+            producer: Some("skl2onnx".to_string()),
+            metadata: vec![("domain".to_string(), "housing".to_string())],
+        };
+        assert_eq!(graph.inputs[0].row_size(), 2);
+        assert_eq!(graph.get_metadata("domain"), Some("housing"));
+
+        let decoded = Graph::from_field(&graph.to_field().unwrap()).unwrap();
+        assert_eq!(decoded, graph);
+        assert!(Graph::from_field(&Graph::fresh().to_field().unwrap())
+            .unwrap()
+            .is_empty());
+    }
+}
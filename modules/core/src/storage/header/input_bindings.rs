@@ -0,0 +1,133 @@
+//! Defines which columns of the key bindings are fed to which named input of the model.
+use super::encoding::{PayloadReader, PayloadWriter};
+use crate::errors::error::SurrealError;
+
+/// The columns that make up a single named input of the model.
+///
+/// # Fields
+/// * `name` - The name of the input in the ONNX graph.
+/// * `columns` - The columns fed to the input in the order they are laid out in the input tensor.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InputBinding {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+/// Maps the columns of the key bindings to the named inputs of a model with several inputs. When there are no
+/// bindings every column is fed to the first input of the model.
+///
+/// # Fields
+/// * `store` - The bindings in the order they were added.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InputBindings {
+    pub store: Vec<InputBinding>,
+}
+
+impl InputBindings {
+    /// Creates a new `InputBindings` with no bindings.
+    ///
+    /// # Returns
+    /// A new `InputBindings` with no bindings.
+    pub fn fresh() -> Self {
+        InputBindings { store: Vec::new() }
+    }
+
+    /// Returns `true` if no columns are bound to named inputs.
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    /// Binds a column to a named input appending it after the columns already bound to that input.
+    ///
+    /// # Arguments
+    /// * `input_name` - The name of the input in the ONNX graph.
+    /// * `column_name` - The name of the column.
+    pub fn add(&mut self, input_name: String, column_name: String) {
+        match self
+            .store
+            .iter_mut()
+            .find(|binding| binding.name == input_name)
+        {
+            Some(binding) => binding.columns.push(column_name),
+            None => self.store.push(InputBinding {
+                name: input_name,
+                columns: vec![column_name],
+            }),
+        }
+    }
+
+    /// Gets the binding of a named input.
+    ///
+    /// # Arguments
+    /// * `input_name` - The name of the input in the ONNX graph.
+    ///
+    /// # Returns
+    /// The binding if any columns are bound to the input.
+    pub fn get(&self, input_name: &str) -> Option<&InputBinding> {
+        self.store.iter().find(|binding| binding.name == input_name)
+    }
+
+    /// Encodes the bindings as a field of the binary header.
+    ///
+    /// # Returns
+    /// The number of inputs followed by the name, number of columns and columns of each input.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_u32(self.store.len() as u32);
+        for binding in &self.store {
+            writer.write_str(&binding.name)?;
+            writer.write_u32(binding.columns.len() as u32);
+            for column in &binding.columns {
+                writer.write_str(column)?;
+            }
+        }
+        Ok(writer.finish())
+    }
+
+    /// Decodes the bindings from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    ///
+    /// # Returns
+    /// The bindings decoded from the field.
+    pub fn from_field(data: &[u8]) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let mut bindings = InputBindings::fresh();
+        for _ in 0..reader.read_u32()? {
+            let name = reader.read_str()?;
+            let count = reader.read_u32()?;
+            let mut columns = Vec::new();
+            for _ in 0..count {
+                columns.push(reader.read_str()?);
+            }
+            bindings.store.push(InputBinding { name, columns });
+        }
+        Ok(bindings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_add_and_field_round_trip() {
+        let mut bindings = InputBindings::fresh();
+        assert!(bindings.is_empty());
+        bindings.add("numeric".to_string(), "squarefoot".to_string());
+        bindings.add("embedding".to_string(), "e0".to_string());
+        bindings.add("numeric".to_string(), "num_floors".to_string());
+
+        assert_eq!(
+            bindings.get("numeric").unwrap().columns,
+            vec!["squarefoot", "num_floors"]
+        );
+        assert_eq!(bindings.store[1].name, "embedding");
+        assert!(bindings.get("missing").is_none());
+
+        let decoded = InputBindings::from_field(&bindings.to_field().unwrap()).unwrap();
+        assert_eq!(decoded, bindings);
+    }
+}
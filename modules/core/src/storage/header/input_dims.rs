@@ -1,50 +1,189 @@
-//! InputDims is a struct that holds the dimensions of the input tensors for the model.
+//! InputDims is a struct that holds the declared shape of the input tensor for the model.
 use std::fmt;
 
-/// InputDims is a struct that holds the dimensions of the input tensors for the model.
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// A single axis of the input tensor.
+///
+/// # Fields
+/// * `Fixed` - An axis with a fixed size.
+/// * `Dynamic` - An axis whose size is only known when the model is run such as `batch` or `seq_len`. The name is
+///   empty for an unnamed dynamic axis.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Dimension {
+    Fixed(usize),
+    Dynamic(String),
+}
+
+impl Dimension {
+    /// Parses a single axis where an integer is a fixed axis, `?` or `-1` is an unnamed dynamic axis and an identifier
+    /// is a named dynamic axis.
+    ///
+    /// # Arguments
+    /// * `data` - The axis as a string.
+    ///
+    /// # Returns
+    /// The parsed axis.
+    fn from_str(data: &str) -> Result<Self, SurrealError> {
+        let data = data.trim();
+        if data == "?" || data == "-1" {
+            return Ok(Dimension::Dynamic(String::new()));
+        }
+        if let Ok(size) = data.parse::<usize>() {
+            if size == 0 {
+                return Err(SurrealError::new(
+                    "Input dimensions can not have an axis of size 0".to_string(),
+                    SurrealErrorStatus::BadRequest,
+                ));
+            }
+            return Ok(Dimension::Fixed(size));
+        }
+        let mut characters = data.chars();
+        let is_identifier = match characters.next() {
+            Some(first) => {
+                (first.is_ascii_alphabetic() || first == '_')
+                    && characters.all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }
+            None => false,
+        };
+        if !is_identifier {
+            return Err(SurrealError::new(
+                format!("Invalid input dimension: '{}'", data),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(Dimension::Dynamic(data.to_string()))
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dimension::Fixed(size) => write!(f, "{}", size),
+            Dimension::Dynamic(name) if name.is_empty() => write!(f, "?"),
+            Dimension::Dynamic(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// InputDims is a struct that holds the declared shape of the input tensor for the model. The shape can be of any
+/// rank and can have named dynamic axes, for example `batch,seq_len,8`.
 ///
 /// # Fields
-/// * `dims` - The dimensions of the input tensors.
-#[derive(Debug, PartialEq)]
+/// * `dims` - The axes of the input tensor. Empty if the shape has not been declared.
+#[derive(Debug, PartialEq, Clone)]
 pub struct InputDims {
-    pub dims: [i32; 2],
+    pub dims: Vec<Dimension>,
 }
 
 impl InputDims {
-    /// Creates a new `InputDims` struct with all zeros.
+    /// Creates a new `InputDims` struct with no declared shape.
     ///
     /// # Returns
-    /// A new `InputDims` struct with all zeros.
+    /// A new `InputDims` struct with no axes.
     pub fn fresh() -> Self {
-        InputDims { dims: [0, 0] }
+        InputDims { dims: Vec::new() }
     }
 
-    /// Creates a new `InputDims` struct from a string.
+    /// Creates a new `InputDims` struct from a comma separated string such as `1,2` or `batch,seq_len,8`.
     ///
     /// # Arguments
     /// * `data` - The dimensions as a string.
     ///
     /// # Returns
-    /// A new `InputDims` struct.
-    pub fn from_string(data: String) -> InputDims {
-        if data == *"" {
-            return InputDims::fresh();
+    /// A new `InputDims` struct, or an error if an axis is malformed.
+    pub fn from_string(data: String) -> Result<InputDims, SurrealError> {
+        if data.trim().is_empty() {
+            return Ok(InputDims::fresh());
         }
-        let dims: Vec<&str> = data.split(",").collect();
-        let dims: Vec<i32> = dims.iter().map(|x| x.parse::<i32>().unwrap()).collect();
+        let dims = data
+            .split(',')
+            .map(Dimension::from_str)
+            .collect::<Result<Vec<Dimension>, SurrealError>>()?;
+        Ok(InputDims { dims })
+    }
+
+    /// Creates a new `InputDims` struct from the dimensions declared by an ONNX model where dynamic dimensions are
+    /// negative.
+    ///
+    /// # Arguments
+    /// * `dims` - The dimensions as declared by the model.
+    ///
+    /// # Returns
+    /// A new `InputDims` struct with an unnamed dynamic axis for every negative dimension.
+    pub fn from_model_dims(dims: &[i64]) -> InputDims {
         InputDims {
-            dims: [dims[0], dims[1]],
+            dims: dims
+                .iter()
+                .map(|dim| match usize::try_from(*dim) {
+                    Ok(size) if size > 0 => Dimension::Fixed(size),
+                    _ => Dimension::Dynamic(String::new()),
+                })
+                .collect(),
         }
     }
+
+    /// Returns `true` if no shape has been declared.
+    pub fn is_empty(&self) -> bool {
+        self.dims.is_empty()
+    }
+
+    /// Resolves the declared shape for an input with a number of values. Every dynamic axis but the last is set to 1
+    /// and the last dynamic axis takes the remaining values, so `batch,seq_len,8` with 24 values becomes `[1, 3, 8]`.
+    ///
+    /// # Arguments
+    /// * `length` - The number of values in the input.
+    ///
+    /// # Returns
+    /// The shape the input is reshaped into, or an error if the values do not fit the declared shape.
+    pub fn resolve(&self, length: usize) -> Result<Vec<usize>, SurrealError> {
+        let fixed: usize = self
+            .dims
+            .iter()
+            .map(|dim| match dim {
+                Dimension::Fixed(size) => *size,
+                Dimension::Dynamic(_) => 1,
+            })
+            .product();
+        let last_dynamic = self
+            .dims
+            .iter()
+            .rposition(|dim| matches!(dim, Dimension::Dynamic(_)));
+
+        let fits = match last_dynamic {
+            Some(_) => length > 0 && length.is_multiple_of(fixed),
+            None => length == fixed,
+        };
+        if !fits {
+            return Err(SurrealError::new(
+                format!(
+                    "An input of {} values does not fit the declared input dimensions {}",
+                    length, self
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(self
+            .dims
+            .iter()
+            .enumerate()
+            .map(|(index, dim)| match dim {
+                Dimension::Fixed(size) => *size,
+                Dimension::Dynamic(_) if Some(index) == last_dynamic => length / fixed,
+                Dimension::Dynamic(_) => 1,
+            })
+            .collect())
+    }
 }
 
 impl fmt::Display for InputDims {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.dims == [0, 0] {
-            write!(f, "")
-        } else {
-            write!(f, "{},{}", self.dims[0], self.dims[1])
-        }
+        let dims = self
+            .dims
+            .iter()
+            .map(|dim| dim.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "{}", dims.join(","))
     }
 }
 
@@ -56,20 +195,68 @@ pub mod tests {
     #[test]
     fn test_fresh() {
         let input_dims = InputDims::fresh();
-        assert_eq!(input_dims.dims[0], 0);
-        assert_eq!(input_dims.dims[1], 0);
+        assert!(input_dims.is_empty());
+        assert_eq!(input_dims.to_string(), "");
     }
 
     #[test]
     fn test_from_string() {
-        let input_dims = InputDims::from_string("1,2".to_string());
-        assert_eq!(input_dims.dims[0], 1);
-        assert_eq!(input_dims.dims[1], 2);
+        let input_dims = InputDims::from_string("1,2".to_string()).unwrap();
+        assert_eq!(
+            input_dims.dims,
+            vec![Dimension::Fixed(1), Dimension::Fixed(2)]
+        );
+
+        let input_dims = InputDims::from_string("batch, seq_len, 8".to_string()).unwrap();
+        assert_eq!(
+            input_dims.dims,
+            vec![
+                Dimension::Dynamic("batch".to_string()),
+                Dimension::Dynamic("seq_len".to_string()),
+                Dimension::Fixed(8)
+            ]
+        );
+
+        let input_dims = InputDims::from_string("?,3,224,224".to_string()).unwrap();
+        assert_eq!(input_dims.dims[0], Dimension::Dynamic(String::new()));
+        assert_eq!(input_dims.dims.len(), 4);
+    }
+
+    #[test]
+    fn test_from_string_malformed() {
+        for data in ["1,", "0,2", "1,2x", "a-b", "1,,2"] {
+            let error = InputDims::from_string(data.to_string()).unwrap_err();
+            assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        }
     }
 
     #[test]
     fn test_to_string() {
-        let input_dims = InputDims::from_string("1,2".to_string());
+        let input_dims = InputDims::from_string("1,2".to_string()).unwrap();
         assert_eq!(input_dims.to_string(), "1,2".to_string());
+
+        let input_dims = InputDims::from_string("batch,-1,8".to_string()).unwrap();
+        assert_eq!(input_dims.to_string(), "batch,?,8".to_string());
+    }
+
+    #[test]
+    fn test_from_model_dims() {
+        let input_dims = InputDims::from_model_dims(&[-1, 2]);
+        assert_eq!(input_dims.to_string(), "?,2");
+    }
+
+    #[test]
+    fn test_resolve() {
+        let input_dims = InputDims::from_string("1,2".to_string()).unwrap();
+        assert_eq!(input_dims.resolve(2).unwrap(), vec![1, 2]);
+        assert!(input_dims.resolve(3).is_err());
+
+        let input_dims = InputDims::from_string("batch,seq_len,8".to_string()).unwrap();
+        assert_eq!(input_dims.resolve(24).unwrap(), vec![1, 3, 8]);
+        assert!(input_dims.resolve(20).is_err());
+        assert!(input_dims.resolve(0).is_err());
+
+        let input_dims = InputDims::from_string("batch,3,2,2".to_string()).unwrap();
+        assert_eq!(input_dims.resolve(24).unwrap(), vec![2, 3, 2, 2]);
     }
 }
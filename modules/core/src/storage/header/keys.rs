@@ -2,6 +2,8 @@
 use std::collections::HashMap;
 use std::fmt;
 
+use super::column::{ColumnSpec, FeatureValue};
+use super::encoding::{PayloadReader, PayloadWriter};
 use crate::errors::error::{SurrealError, SurrealErrorStatus};
 use crate::safe_eject_internal;
 
@@ -11,10 +13,12 @@ use crate::safe_eject_internal;
 /// * `store` - A vector of strings that represent the column names. The order of this store is the same as the order
 ///   in which the columns are expected in the input data.
 /// * `reference` - A hashmap that maps the column names to their index in the `self.store` field.
-#[derive(Debug, PartialEq)]
+/// * `specs` - The declared type, default and allowed range of the columns that have one.
+#[derive(Debug, PartialEq, Clone)]
 pub struct KeyBindings {
     pub store: Vec<String>,
     pub reference: HashMap<String, usize>,
+    pub specs: HashMap<String, ColumnSpec>,
 }
 
 impl KeyBindings {
@@ -26,6 +30,7 @@ impl KeyBindings {
         KeyBindings {
             store: Vec::new(),
             reference: HashMap::new(),
+            specs: HashMap::new(),
         }
     }
 
@@ -40,6 +45,66 @@ impl KeyBindings {
         self.reference.insert(column_name, index);
     }
 
+    /// Declares the type, default and allowed range of a column that is already in the `self.store` field. The default
+    /// must be a valid value of the column.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column.
+    /// * `spec` - The declared type, default and allowed range of the column.
+    pub fn add_column_spec(
+        &mut self,
+        column_name: String,
+        spec: ColumnSpec,
+    ) -> Result<(), SurrealError> {
+        if !self.reference.contains_key(&column_name) {
+            return Err(SurrealError::new(
+                format!("Column {} is not in the key bindings", column_name),
+                SurrealErrorStatus::NotFound,
+            ));
+        }
+        spec.check_default(&column_name)?;
+        self.specs.insert(column_name, spec);
+        Ok(())
+    }
+
+    /// Coerces an input value of a column into the number fed to the model. Columns without a declared type accept
+    /// any number and have no default.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column.
+    /// * `value` - The input value if there is one.
+    ///
+    /// # Returns
+    /// The coerced value.
+    pub fn coerce(
+        &self,
+        column_name: &str,
+        value: Option<&FeatureValue>,
+    ) -> Result<f32, SurrealError> {
+        if let Some(spec) = self.specs.get(column_name) {
+            return spec.coerce(column_name, value);
+        }
+        match value {
+            Some(FeatureValue::Float(value)) => Ok(*value as f32),
+            Some(FeatureValue::Int(value)) => Ok(*value as f32),
+            Some(FeatureValue::Bool(value)) => Ok(*value as u8 as f32),
+            Some(FeatureValue::Text(value)) => Err(SurrealError::new(
+                format!(
+                    "Column {} expects a number but got '{}', declare a column type to pass strings",
+                    column_name, value
+                ),
+                SurrealErrorStatus::BadRequest,
+            )),
+            None => Err(SurrealError::new(
+                format!(
+                    "Column {} is missing from the input values and has no default",
+                    column_name
+                ),
+                SurrealErrorStatus::NotFound,
+            )),
+        }
+    }
+
     /// Constructs the key bindings from a string.
     ///
     /// # Arguments
@@ -64,7 +129,11 @@ impl KeyBindings {
             reference.insert(line.to_string(), count);
             count += 1;
         }
-        KeyBindings { store, reference }
+        KeyBindings {
+            store,
+            reference,
+            specs: HashMap::new(),
+        }
     }
 
     /// Constructs the key bindings from bytes.
@@ -86,6 +155,69 @@ impl KeyBindings {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.to_string().into_bytes()
     }
+
+    /// Encodes the key bindings as a field of the binary header.
+    ///
+    /// # Returns
+    /// The number of columns followed by each column name as a length-prefixed string.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_u32(self.store.len() as u32);
+        for column in &self.store {
+            writer.write_str(column)?;
+        }
+        Ok(writer.finish())
+    }
+
+    /// Decodes the key bindings from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    ///
+    /// # Returns
+    /// The key bindings decoded from the field.
+    pub fn from_field(data: &[u8]) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let count = reader.read_u32()?;
+        let mut bindings = KeyBindings::fresh();
+        for _ in 0..count {
+            bindings.add_column(reader.read_str()?);
+        }
+        Ok(bindings)
+    }
+
+    /// Encodes the declared column types as a field of the binary header. They are kept apart from the column names
+    /// so older readers still get the columns.
+    ///
+    /// # Returns
+    /// The number of declared columns followed by each column name and its spec.
+    pub fn specs_to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_u32(self.specs.len() as u32);
+        // written in column order so the same header always gives the same bytes, each spec once even if the column
+        // name is repeated in the store
+        let mut columns: Vec<(&String, &ColumnSpec)> = self.specs.iter().collect();
+        columns.sort_by_key(|(column, _)| (self.reference.get(*column), *column));
+        for (column, spec) in columns {
+            writer.write_str(column)?;
+            spec.write(&mut writer)?;
+        }
+        Ok(writer.finish())
+    }
+
+    /// Decodes the declared column types from a field of the binary header into the key bindings.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    pub fn specs_from_field(&mut self, data: &[u8]) -> Result<(), SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        for _ in 0..reader.read_u32()? {
+            let column = reader.read_str()?;
+            let spec = ColumnSpec::read(&mut reader)?;
+            self.add_column_spec(column, spec)?;
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for KeyBindings {
@@ -98,6 +230,7 @@ impl fmt::Display for KeyBindings {
 pub mod tests {
 
     use super::*;
+    use crate::storage::header::column::ColumnType;
 
     pub fn generate_string() -> String {
         "a=>b=>c=>d=>e=>f".to_string()
@@ -123,7 +256,11 @@ pub mod tests {
         reference.insert("d".to_string(), 3);
         reference.insert("e".to_string(), 4);
         reference.insert("f".to_string(), 5);
-        KeyBindings { store, reference }
+        KeyBindings {
+            store,
+            reference,
+            specs: HashMap::new(),
+        }
     }
 
     #[test]
@@ -202,4 +339,65 @@ pub mod tests {
         assert_eq!(bindings.store[1], "b");
         assert_eq!(bindings.reference["b"], 1);
     }
+
+    #[test]
+    fn test_field_round_trip() {
+        let mut bindings = KeyBindings::fresh();
+        bindings.add_column("a=>b".to_string());
+        bindings.add_column("c//=>d".to_string());
+
+        let decoded = KeyBindings::from_field(&bindings.to_field().unwrap()).unwrap();
+        assert_eq!(decoded, bindings);
+    }
+
+    #[test]
+    fn test_column_specs() {
+        let mut bindings = generate_struct();
+        let mut spec = ColumnSpec::new(ColumnType::Float);
+        spec.default = Some(FeatureValue::Float(2.5));
+        spec.min = Some(0.0);
+        bindings.add_column_spec("b".to_string(), spec).unwrap();
+        assert!(bindings
+            .add_column_spec("z".to_string(), ColumnSpec::new(ColumnType::Int))
+            .is_err());
+        let mut mismatched = ColumnSpec::new(ColumnType::Bool);
+        mismatched.default = Some(FeatureValue::Float(2.5));
+        let error = bindings
+            .add_column_spec("c".to_string(), mismatched)
+            .unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert!(!bindings.specs.contains_key("c"));
+
+        assert_eq!(bindings.coerce("b", None).unwrap(), 2.5);
+        assert!(bindings
+            .coerce("b", Some(&FeatureValue::Float(-1.0)))
+            .is_err());
+        assert_eq!(
+            bindings.coerce("a", Some(&FeatureValue::Int(3))).unwrap(),
+            3.0
+        );
+        assert!(bindings.coerce("a", Some(&"three".into())).is_err());
+        let error = bindings.coerce("a", None).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::NotFound);
+        assert_eq!(
+            error.message,
+            "Column a is missing from the input values and has no default"
+        );
+
+        let mut decoded = KeyBindings::from_field(&bindings.to_field().unwrap()).unwrap();
+        decoded
+            .specs_from_field(&bindings.specs_to_field().unwrap())
+            .unwrap();
+        assert_eq!(decoded, bindings);
+
+        // a repeated column name still writes its spec once
+        let field = bindings.specs_to_field().unwrap();
+        bindings.add_column("b".to_string());
+        assert_eq!(bindings.specs_to_field().unwrap(), field);
+        let mut decoded = KeyBindings::from_field(&bindings.to_field().unwrap()).unwrap();
+        decoded
+            .specs_from_field(&bindings.specs_to_field().unwrap())
+            .unwrap();
+        assert_eq!(decoded, bindings);
+    }
 }
@@ -0,0 +1,531 @@
+//! Defines the free form metadata of the model such as metrics, licenses, tags and the hash of the training data.
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use super::encoding::{PayloadReader, PayloadWriter};
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// How deep lists and maps can be nested so a malicious header or definition cannot overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// A typed value of the metadata. Values are passed over the C API as JSON.
+///
+/// # Fields
+/// * `Text` - A string such as a license or the hash of the training data.
+/// * `Number` - A finite number such as a metric.
+/// * `Bool` - A flag.
+/// * `List` - A list of values such as tags.
+/// * `Map` - Values keyed by name such as the importance of each feature.
+#[derive(Debug, PartialEq, Clone)]
+pub enum MetadataValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    List(Vec<MetadataValue>),
+    Map(BTreeMap<String, MetadataValue>),
+}
+
+impl MetadataValue {
+    /// Parses a value from JSON. `null` is rejected as metadata is removed rather than set to nothing.
+    ///
+    /// # Arguments
+    /// * `data` - The value as JSON such as `{"rmse": 0.4, "tags": ["housing"]}`.
+    ///
+    /// # Returns
+    /// The parsed value.
+    pub fn from_json(data: &str) -> Result<Self, SurrealError> {
+        let value: MetadataValue = serde_json::from_str(data).map_err(|error| {
+            SurrealError::new(
+                format!("Invalid metadata JSON: {}", error),
+                SurrealErrorStatus::BadRequest,
+            )
+        })?;
+        value.check(0)?;
+        Ok(value)
+    }
+
+    /// Gets a nested value by a path of map keys and list indexes.
+    ///
+    /// # Arguments
+    /// * `path` - The keys and indexes such as `["metrics", "rmse"]` or `["tags", "0"]`.
+    ///
+    /// # Returns
+    /// The value at the end of the path if there is one.
+    pub fn query<'a>(&self, path: impl IntoIterator<Item = &'a str>) -> Option<&MetadataValue> {
+        let mut value = self;
+        for segment in path {
+            value = match value {
+                MetadataValue::Map(map) => map.get(segment)?,
+                MetadataValue::List(list) => list.get(segment.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+
+    /// Checks that every number in the value is finite as NaN and infinity cannot be written as JSON.
+    fn check(&self, depth: usize) -> Result<(), SurrealError> {
+        if depth > MAX_DEPTH {
+            return Err(SurrealError::new(
+                format!(
+                    "Metadata cannot be nested more than {} levels deep",
+                    MAX_DEPTH
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        match self {
+            MetadataValue::Number(number) if !number.is_finite() => Err(SurrealError::new(
+                format!("Metadata numbers must be finite, received {}", number),
+                SurrealErrorStatus::BadRequest,
+            )),
+            MetadataValue::List(list) => list.iter().try_for_each(|value| value.check(depth + 1)),
+            MetadataValue::Map(map) => map.values().try_for_each(|value| value.check(depth + 1)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes the value to a payload as a kind byte followed by the value.
+    fn write(&self, writer: &mut PayloadWriter) -> Result<(), SurrealError> {
+        match self {
+            MetadataValue::Text(text) => {
+                writer.write_u8(0);
+                writer.write_str(text)?;
+            }
+            MetadataValue::Number(number) => {
+                writer.write_u8(1);
+                writer.write_str(&number.to_string())?;
+            }
+            MetadataValue::Bool(flag) => {
+                writer.write_u8(2);
+                writer.write_u8(*flag as u8);
+            }
+            MetadataValue::List(list) => {
+                writer.write_u8(3);
+                writer.write_u32(list.len() as u32);
+                for value in list {
+                    value.write(writer)?;
+                }
+            }
+            MetadataValue::Map(map) => {
+                writer.write_u8(4);
+                writer.write_u32(map.len() as u32);
+                for (key, value) in map {
+                    writer.write_str(key)?;
+                    value.write(writer)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a value written by `MetadataValue::write`.
+    fn read(reader: &mut PayloadReader, depth: usize) -> Result<Self, SurrealError> {
+        if depth > MAX_DEPTH {
+            return Err(SurrealError::new(
+                format!(
+                    "Metadata cannot be nested more than {} levels deep",
+                    MAX_DEPTH
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(match reader.read_u8()? {
+            0 => MetadataValue::Text(reader.read_str()?),
+            1 => {
+                let number = reader.read_str()?;
+                match number.parse::<f64>() {
+                    Ok(parsed) if parsed.is_finite() => MetadataValue::Number(parsed),
+                    _ => {
+                        return Err(SurrealError::new(
+                            format!("Invalid metadata number: {}", number),
+                            SurrealErrorStatus::BadRequest,
+                        ))
+                    }
+                }
+            }
+            2 => MetadataValue::Bool(reader.read_u8()? != 0),
+            3 => {
+                let mut list = Vec::new();
+                for _ in 0..reader.read_u32()? {
+                    list.push(MetadataValue::read(reader, depth + 1)?);
+                }
+                MetadataValue::List(list)
+            }
+            4 => {
+                let mut map = BTreeMap::new();
+                for _ in 0..reader.read_u32()? {
+                    let key = reader.read_str()?;
+                    map.insert(key, MetadataValue::read(reader, depth + 1)?);
+                }
+                MetadataValue::Map(map)
+            }
+            kind => {
+                return Err(SurrealError::new(
+                    format!("Unknown metadata value kind: {}", kind),
+                    SurrealErrorStatus::BadRequest,
+                ))
+            }
+        })
+    }
+}
+
+impl From<&str> for MetadataValue {
+    fn from(value: &str) -> Self {
+        MetadataValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for MetadataValue {
+    fn from(value: String) -> Self {
+        MetadataValue::Text(value)
+    }
+}
+
+impl From<f64> for MetadataValue {
+    fn from(value: f64) -> Self {
+        MetadataValue::Number(value)
+    }
+}
+
+impl From<bool> for MetadataValue {
+    fn from(value: bool) -> Self {
+        MetadataValue::Bool(value)
+    }
+}
+
+impl From<Vec<MetadataValue>> for MetadataValue {
+    fn from(value: Vec<MetadataValue>) -> Self {
+        MetadataValue::List(value)
+    }
+}
+
+impl Serialize for MetadataValue {
+    /// Serializes the value as the matching JSON type. Whole numbers are written without a fraction.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MetadataValue::Text(text) => serializer.serialize_str(text),
+            MetadataValue::Number(number)
+                if number.fract() == 0.0 && number.abs() < i64::MAX as f64 =>
+            {
+                serializer.serialize_i64(*number as i64)
+            }
+            MetadataValue::Number(number) => serializer.serialize_f64(*number),
+            MetadataValue::Bool(flag) => serializer.serialize_bool(*flag),
+            MetadataValue::List(list) => serializer.collect_seq(list),
+            MetadataValue::Map(map) => serializer.collect_map(map),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MetadataValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(MetadataValueVisitor)
+    }
+}
+
+/// Builds a `MetadataValue` from the JSON types, rejecting `null` and duplicate keys.
+struct MetadataValueVisitor;
+
+impl<'de> Visitor<'de> for MetadataValueVisitor {
+    type Value = MetadataValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a string, number, bool, list or map")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<MetadataValue, E> {
+        Ok(MetadataValue::Text(value.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<MetadataValue, E> {
+        Ok(MetadataValue::Text(value))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<MetadataValue, E> {
+        Ok(MetadataValue::Number(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<MetadataValue, E> {
+        Ok(MetadataValue::Number(value as f64))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<MetadataValue, E> {
+        Ok(MetadataValue::Number(value as f64))
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<MetadataValue, E> {
+        Ok(MetadataValue::Bool(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<MetadataValue, E> {
+        Err(E::custom(
+            "metadata values cannot be null, remove the key instead",
+        ))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<MetadataValue, A::Error> {
+        let mut list = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            list.push(value);
+        }
+        Ok(MetadataValue::List(list))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<MetadataValue, A::Error> {
+        let mut map = BTreeMap::new();
+        while let Some((key, value)) = access.next_entry::<String, MetadataValue>()? {
+            if map.contains_key(&key) {
+                return Err(de::Error::custom(format!("duplicate key {:?}", key)));
+            }
+            map.insert(key, value);
+        }
+        Ok(MetadataValue::Map(map))
+    }
+}
+
+impl fmt::Display for MetadataValue {
+    /// Writes the value as JSON.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}", json)
+    }
+}
+
+/// The metadata of the model keyed by name. Keys are kept sorted so the header bytes and digest are stable.
+///
+/// # Fields
+/// * `store` - The values keyed by name.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Metadata {
+    pub store: BTreeMap<String, MetadataValue>,
+}
+
+impl Metadata {
+    /// Creates a new `Metadata` with no values.
+    ///
+    /// # Returns
+    /// A new empty `Metadata`.
+    pub fn fresh() -> Self {
+        Metadata {
+            store: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `true` if there is no metadata.
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    /// Sets a value replacing any value already under the key.
+    ///
+    /// # Arguments
+    /// * `key` - The name of the value which cannot be empty.
+    /// * `value` - The value to be set.
+    pub fn set(&mut self, key: String, value: MetadataValue) -> Result<(), SurrealError> {
+        if key.is_empty() {
+            return Err(SurrealError::new(
+                "Metadata key cannot be empty".to_string(),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        value.check(0)?;
+        self.store.insert(key, value);
+        Ok(())
+    }
+
+    /// Gets a value by its key.
+    ///
+    /// # Arguments
+    /// * `key` - The name of the value.
+    ///
+    /// # Returns
+    /// The value if there is one under the key.
+    pub fn get(&self, key: &str) -> Option<&MetadataValue> {
+        self.store.get(key)
+    }
+
+    /// Removes a value by its key.
+    ///
+    /// # Arguments
+    /// * `key` - The name of the value.
+    ///
+    /// # Returns
+    /// The removed value if there was one under the key.
+    pub fn remove(&mut self, key: &str) -> Option<MetadataValue> {
+        self.store.remove(key)
+    }
+
+    /// Gets a value by a dot separated path where the first segment is the key and the rest index into nested maps
+    /// and lists, for example `metrics.rmse` or `tags.0`. A key that contains dots is matched as a whole first.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the value.
+    ///
+    /// # Returns
+    /// The value at the path if there is one.
+    pub fn query(&self, path: &str) -> Option<&MetadataValue> {
+        if let Some(value) = self.get(path) {
+            return Some(value);
+        }
+        let mut segments = path.split('.');
+        self.get(segments.next()?)?.query(segments)
+    }
+
+    /// Encodes the metadata as a field of the binary header.
+    ///
+    /// # Returns
+    /// The number of values followed by each key and value.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_u32(self.store.len() as u32);
+        for (key, value) in &self.store {
+            writer.write_str(key)?;
+            value.write(&mut writer)?;
+        }
+        Ok(writer.finish())
+    }
+
+    /// Decodes the metadata from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    ///
+    /// # Returns
+    /// The metadata decoded from the field.
+    pub fn from_field(data: &[u8]) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let mut metadata = Metadata::fresh();
+        for _ in 0..reader.read_u32()? {
+            let key = reader.read_str()?;
+            let value = MetadataValue::read(&mut reader, 0)?;
+            metadata.store.insert(key, value);
+        }
+        Ok(metadata)
+    }
+}
+
+impl fmt::Display for Metadata {
+    /// Writes all of the metadata as a JSON object.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", MetadataValue::Map(self.store.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn model_card() -> Metadata {
+        let mut metadata = Metadata::fresh();
+        metadata
+            .set(
+                "metrics".to_string(),
+                MetadataValue::from_json(r#"{"rmse": 0.25, "auc": 9.1e-1}"#).unwrap(),
+            )
+            .unwrap();
+        metadata
+            .set("license".to_string(), "MIT \"0\"\n".into())
+            .unwrap();
+        metadata
+            .set(
+                "tags".to_string(),
+                vec!["housing".into(), MetadataValue::Bool(true)].into(),
+            )
+            .unwrap();
+        metadata
+            .set("trained_at".to_string(), 1_700_000_000.0.into())
+            .unwrap();
+        metadata
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let data = r#"{"a":[1,-2.5,true,"é\u00e9\ud83d\ude00"],"b":{"c":{}},"d":[]}"#;
+        let value = MetadataValue::from_json(data).unwrap();
+        assert_eq!(
+            value.query(["a", "3"]),
+            Some(&MetadataValue::Text("éé😀".to_string()))
+        );
+        assert_eq!(MetadataValue::from_json(&value.to_string()).unwrap(), value);
+
+        let metadata = model_card();
+        assert_eq!(
+            metadata.to_string(),
+            r#"{"license":"MIT \"0\"\n","metrics":{"auc":0.91,"rmse":0.25},"tags":["housing",true],"trained_at":1700000000}"#
+        );
+    }
+
+    #[test]
+    fn test_json_errors() {
+        for data in [
+            "",
+            "null",
+            "[1,]",
+            "{\"a\":1,\"a\":2}",
+            "{a:1}",
+            "1e999",
+            "\"\\x\"",
+            "\"abc",
+            "[1] 2",
+            &"[".repeat(100),
+        ] {
+            assert!(
+                MetadataValue::from_json(data).is_err(),
+                "{} should not parse",
+                data
+            );
+        }
+        let error = MetadataValue::from_json(r#"{"a": null}"#).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert!(
+            error.message.contains("cannot be null"),
+            "{}",
+            error.message
+        );
+    }
+
+    #[test]
+    fn test_query() {
+        let metadata = model_card();
+        assert_eq!(
+            metadata.query("metrics.rmse"),
+            Some(&MetadataValue::Number(0.25))
+        );
+        assert_eq!(metadata.query("tags.1"), Some(&MetadataValue::Bool(true)));
+        assert_eq!(metadata.query("tags.2"), None);
+        assert_eq!(metadata.query("license.text"), None);
+        assert_eq!(metadata.query("missing"), None);
+    }
+
+    #[test]
+    fn test_set_errors() {
+        let mut metadata = Metadata::fresh();
+        assert!(metadata.set(String::new(), true.into()).is_err());
+        assert_eq!(
+            metadata
+                .set("loss".to_string(), f64::NAN.into())
+                .unwrap_err()
+                .message,
+            "Metadata numbers must be finite, received NaN"
+        );
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn test_field_round_trip() {
+        let metadata = model_card();
+        assert_eq!(
+            Metadata::from_field(&metadata.to_field().unwrap()).unwrap(),
+            metadata
+        );
+        assert_eq!(
+            Metadata::from_field(&Metadata::fresh().to_field().unwrap()).unwrap(),
+            Metadata::fresh()
+        );
+    }
+}
@@ -1,22 +1,43 @@
 //! Handles the loading, saving, and utilisation of all the data in the header of the model file.
+pub mod column;
+pub mod compression;
+pub mod encoders;
+pub mod encoding;
 pub mod engine;
+pub mod graph;
+pub mod input_bindings;
 pub mod input_dims;
 pub mod keys;
+pub mod metadata;
 pub mod normalisers;
 pub mod origin;
 pub mod output;
 pub mod string_value;
 pub mod version;
 
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
 use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use crate::execution::session::get_session;
 use crate::safe_eject;
-use engine::Engine;
+use column::{ColumnSpec, ColumnType, FeatureValue};
+use compression::Compression;
+use encoders::wrapper::EncoderType;
+use encoders::EncoderMap;
+use encoding::{tags, HeaderWriter, PayloadReader, PayloadWriter};
+use engine::{Engine, Exporter};
+use graph::Graph;
+use input_bindings::InputBindings;
 use input_dims::InputDims;
 use keys::KeyBindings;
+use metadata::{Metadata, MetadataValue};
+use normalisers::fit::FitKind;
 use normalisers::wrapper::NormaliserType;
 use normalisers::NormaliserMap;
-use origin::Origin;
-use output::Output;
+use origin::{Origin, Provenance};
+use output::{Output, Outputs};
 use string_value::StringValue;
 use version::Version;
 
@@ -25,23 +46,36 @@ use version::Version;
 /// # Fields
 /// * `keys` - The key bindings where the order of the input columns is stored.
 /// * `normalisers` - The normalisers where the normalisation functions are stored per column if there are any.
-/// * `output` - The output where the output column name and normaliser are stored if there are any.
+/// * `outputs` - The outputs where the name and normaliser of each value the model returns are stored.
 /// * `name` - The name of the model.
 /// * `version` - The version of the model.
 /// * `description` - The description of the model.
-/// * `engine` - The engine of the model (could be native or pytorch).
+/// * `engine` - The engine of the model such as pytorch, sklearn or tensorflow.
+/// * `exporter` - The tool, version and opset the model was exported to ONNX with.
 /// * `origin` - The origin of the model which is where the model was created and who the author is.
-#[derive(Debug, PartialEq)]
+/// * `input_dims` - The dimensions of the input tensor.
+/// * `compression` - The codec the model bytes are compressed with in the file.
+/// * `inputs` - The columns bound to each named input of a model with several inputs.
+/// * `graph` - The inputs, outputs and metadata inferred from the ONNX graph if the header was inferred.
+/// * `encoders` - The encoders that expand string columns into numeric columns if there are any.
+/// * `metadata` - The free form metadata of the model such as metrics, licenses and tags.
+#[derive(Debug, PartialEq, Clone)]
 pub struct Header {
     pub keys: KeyBindings,
     pub normalisers: NormaliserMap,
-    pub output: Output,
+    pub outputs: Outputs,
     pub name: StringValue,
     pub version: Version,
     pub description: StringValue,
     pub engine: Engine,
+    pub exporter: Exporter,
     pub origin: Origin,
     pub input_dims: InputDims,
+    pub compression: Compression,
+    pub inputs: InputBindings,
+    pub graph: Graph,
+    pub encoders: EncoderMap,
+    pub metadata: Metadata,
 }
 
 impl Header {
@@ -53,13 +87,19 @@ impl Header {
         Header {
             keys: KeyBindings::fresh(),
             normalisers: NormaliserMap::fresh(),
-            output: Output::fresh(),
+            outputs: Outputs::fresh(),
             name: StringValue::fresh(),
             version: Version::fresh(),
             description: StringValue::fresh(),
             engine: Engine::fresh(),
+            exporter: Exporter::fresh(),
             origin: Origin::fresh(),
             input_dims: InputDims::fresh(),
+            compression: Compression::fresh(),
+            inputs: InputBindings::fresh(),
+            graph: Graph::fresh(),
+            encoders: EncoderMap::fresh(),
+            metadata: Metadata::fresh(),
         }
     }
 
@@ -88,6 +128,37 @@ impl Header {
         self.description = StringValue::from_string(description);
     }
 
+    /// Sets a metadata value such as a metric or license, replacing any value already under the key.
+    ///
+    /// # Arguments
+    /// * `key` - The name of the value.
+    /// * `value` - The value to be set.
+    pub fn set_metadata(&mut self, key: String, value: MetadataValue) -> Result<(), SurrealError> {
+        self.metadata.set(key, value)
+    }
+
+    /// Gets a metadata value by its key or by a dot separated path into nested values such as `metrics.rmse`.
+    ///
+    /// # Arguments
+    /// * `path` - The key or path of the value.
+    ///
+    /// # Returns
+    /// The value if there is one.
+    pub fn get_metadata(&self, path: &str) -> Option<&MetadataValue> {
+        self.metadata.query(path)
+    }
+
+    /// Removes a metadata value by its key.
+    ///
+    /// # Arguments
+    /// * `key` - The name of the value.
+    ///
+    /// # Returns
+    /// The removed value if there was one.
+    pub fn remove_metadata(&mut self, key: &str) -> Option<MetadataValue> {
+        self.metadata.remove(key)
+    }
+
     /// Adds a column name to the `self.keys` field. It must be noted that the order in which the columns are added is
     /// the order in which they will be expected in the input data. We can do this with the followng example:
     ///
@@ -97,6 +168,52 @@ impl Header {
         self.keys.add_column(column_name);
     }
 
+    /// Declares the type, default and allowed range of a column so buffered computations can coerce its values, fill
+    /// it in when it is missing and reject values outside of the range.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column which must already be in the key bindings.
+    /// * `spec` - The declared type, default and allowed range of the column.
+    pub fn add_column_spec(
+        &mut self,
+        column_name: String,
+        spec: ColumnSpec,
+    ) -> Result<(), SurrealError> {
+        self.keys.add_column_spec(column_name, spec)
+    }
+
+    /// Appends a category to a categorical column. The index of the category is the number fed to the model, so a
+    /// category that is already in the column is rejected rather than given a second index.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column which must already be declared as categorical.
+    /// * `category` - The category to be added.
+    pub fn add_column_category(
+        &mut self,
+        column_name: String,
+        category: String,
+    ) -> Result<(), SurrealError> {
+        match self.keys.specs.get_mut(&column_name) {
+            Some(spec) if spec.column_type == ColumnType::Categorical => {
+                if spec.categories.contains(&category) {
+                    return Err(SurrealError::new(
+                        format!(
+                            "Column {} already has the category '{}'",
+                            column_name, category
+                        ),
+                        SurrealErrorStatus::BadRequest,
+                    ));
+                }
+                spec.categories.push(category);
+                Ok(())
+            }
+            _ => Err(SurrealError::new(
+                format!("Column {} is not declared as categorical", column_name),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+
     /// Adds a normaliser to the `self.normalisers` field.
     ///
     /// # Arguments
@@ -112,6 +229,83 @@ impl Header {
         Ok(())
     }
 
+    /// Appends a normaliser to the pipeline of a column so it is applied after the normalisers already on the
+    /// column, for example clipping, then log scaling, then a z-score.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column to which the normaliser will be applied.
+    /// * `normaliser` - The normaliser to be appended.
+    pub fn add_normaliser_step(
+        &mut self,
+        column_name: String,
+        normaliser: NormaliserType,
+    ) -> Result<(), SurrealError> {
+        self.normalisers
+            .add_normaliser_step(normaliser, column_name, &self.keys)
+    }
+
+    /// Appends a normaliser to the pipeline of the first output. The inverse of the pipeline is applied to the
+    /// outputs of the model in reverse order.
+    ///
+    /// # Arguments
+    /// * `normaliser` - The normaliser to be appended.
+    pub fn add_output_normaliser_step(&mut self, normaliser: NormaliserType) {
+        self.outputs.first_mut().add_normaliser_step(normaliser);
+    }
+
+    /// Appends a normaliser to the pipeline of a named output of a model with several outputs.
+    ///
+    /// # Arguments
+    /// * `output_name` - The name of the output.
+    /// * `normaliser` - The normaliser to be appended.
+    pub fn add_named_output_normaliser_step(
+        &mut self,
+        output_name: &str,
+        normaliser: NormaliserType,
+    ) -> Result<(), SurrealError> {
+        match self.outputs.get_mut(output_name) {
+            Some(output) => {
+                output.add_normaliser_step(normaliser);
+                Ok(())
+            }
+            None => Err(SurrealError::new(
+                format!("Output {} is not in the header", output_name),
+                SurrealErrorStatus::NotFound,
+            )),
+        }
+    }
+
+    /// Fits normalisers to the values of columns and adds them, replacing any normaliser already on those columns.
+    ///
+    /// # Arguments
+    /// * `data` - The values of each column keyed by column name.
+    /// * `kinds` - The kind of normaliser to fit for each column.
+    pub fn fit_normalisers(
+        &mut self,
+        data: &HashMap<String, Vec<f32>>,
+        kinds: &HashMap<String, FitKind>,
+    ) -> Result<(), SurrealError> {
+        let fitted = NormaliserMap::fit_columns(data, kinds, &self.keys)?;
+        for (normaliser, column_name) in fitted.store.into_iter().zip(fitted.store_ref) {
+            self.add_normaliser(column_name, normaliser)?;
+        }
+        Ok(())
+    }
+
+    /// Fits the normaliser of the first output to the values of the training targets.
+    ///
+    /// # Arguments
+    /// * `values` - The values of the training targets.
+    /// * `kind` - The kind of normaliser to fit.
+    pub fn fit_output_normaliser(
+        &mut self,
+        values: &[f32],
+        kind: FitKind,
+    ) -> Result<(), SurrealError> {
+        self.outputs.first_mut().add_normaliser(kind.fit(values)?);
+        Ok(())
+    }
+
     /// Gets the normaliser for a given column name.
     ///
     /// # Arguments
@@ -127,22 +321,109 @@ impl Header {
             .get_normaliser(column_name.to_string(), &self.keys)
     }
 
-    /// Adds an output column to the `self.output` field.
+    /// Adds an encoder to the `self.encoders` field so the string values of the column are expanded into numeric
+    /// columns before the model is run.
     ///
     /// # Arguments
-    /// * `column_name` - The name of the column to be added.
-    /// * `normaliser` - The normaliser to be applied to the column.
+    /// * `column_name` - The name of the column which must already be in the key bindings.
+    /// * `encoder` - The encoder to be applied to the column.
+    pub fn add_encoder(
+        &mut self,
+        column_name: String,
+        encoder: EncoderType,
+    ) -> Result<(), SurrealError> {
+        self.encoders.add_encoder(encoder, column_name, &self.keys)
+    }
+
+    /// The number of values fed to the model for a single row once every encoded column is expanded.
+    pub fn input_width(&self) -> usize {
+        self.encoders.input_width(&self.keys)
+    }
+
+    /// Parses an input value of a column passed as a string such as over the C API. Encoded columns keep the string,
+    /// columns with a declared type are parsed as that type and every other column is parsed as a number.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column.
+    /// * `value` - The value as a string.
+    ///
+    /// # Returns
+    /// The parsed value.
+    pub fn parse_value(
+        &self,
+        column_name: &str,
+        value: &str,
+    ) -> Result<FeatureValue, SurrealError> {
+        if self.encoders.get_encoder(column_name).is_some() {
+            return Ok(FeatureValue::Text(value.to_string()));
+        }
+        if let Some(spec) = self.keys.specs.get(column_name) {
+            return spec.column_type.parse_value(value);
+        }
+        match value.parse::<f64>() {
+            Ok(number) => Ok(FeatureValue::Float(number)),
+            Err(_) => Err(SurrealError::new(
+                format!(
+                    "Column {} expects a number but got '{}'",
+                    column_name, value
+                ),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+
+    /// Declares an output after the outputs already declared, or replaces the normaliser of the output with the same
+    /// name. Each value the model returns is matched to an output in the order they are declared.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the output to be added.
+    /// * `normaliser` - The normaliser to be applied to the output.
     pub fn add_output(&mut self, column_name: String, normaliser: Option<NormaliserType>) {
-        self.output.name = Some(column_name);
-        self.output.normaliser = normaliser;
+        self.outputs.add(column_name, normaliser);
+    }
+
+    /// Binds a column to a named input of the model. Columns bound to the same input are laid out in the order they
+    /// are bound.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column which must already be in the key bindings.
+    /// * `input_name` - The name of the input in the ONNX graph.
+    pub fn add_input_binding(
+        &mut self,
+        column_name: String,
+        input_name: String,
+    ) -> Result<(), SurrealError> {
+        if !self.keys.reference.contains_key(&column_name) {
+            return Err(SurrealError::new(
+                format!("Column {} is not in the key bindings", column_name),
+                SurrealErrorStatus::NotFound,
+            ));
+        }
+        self.inputs.add(input_name, column_name);
+        Ok(())
     }
 
     /// Adds an engine to the `self.engine` field.
     ///
     /// # Arguments
     /// * `engine` - The engine to be added.
-    pub fn add_engine(&mut self, engine: String) {
-        self.engine = Engine::from_string(engine);
+    pub fn add_engine(&mut self, engine: String) -> Result<(), SurrealError> {
+        self.engine = Engine::from_string(engine)?;
+        Ok(())
+    }
+
+    /// Records the tool that exported the model to ONNX in the `self.exporter` field.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the exporter such as `skl2onnx`.
+    /// * `version` - The version of the exporter if it is known.
+    /// * `opset` - The ONNX opset the model was exported with if it is known.
+    pub fn add_exporter(&mut self, name: String, version: Option<String>, opset: Option<u32>) {
+        self.exporter = Exporter {
+            name: Some(name),
+            version,
+            opset,
+        };
     }
 
     /// Adds an author to the `self.origin` field.
@@ -156,17 +437,88 @@ impl Header {
     /// Adds an origin to the `self.origin` field.
     ///
     /// # Arguments
-    /// * `origin` - The origin to be added.
+    /// * `origin` - The origin to be added which is `local`, `surreal_db` or a URI of the source system.
     pub fn add_origin(&mut self, origin: String) -> Result<(), SurrealError> {
         self.origin.add_origin(origin)
     }
 
+    /// Records how the model was trained in the `self.origin` field.
+    ///
+    /// # Arguments
+    /// * `provenance` - The training run, commit, parent model and time of training.
+    pub fn add_provenance(&mut self, provenance: Provenance) -> Result<(), SurrealError> {
+        self.origin.add_provenance(provenance)
+    }
+
+    /// Sets the codec that the model bytes are compressed with when the file is written.
+    ///
+    /// # Arguments
+    /// * `codec` - The name of the codec (`deflate` or `zstd`, an empty string for no compression).
+    pub fn add_compression(&mut self, codec: String) -> Result<(), SurrealError> {
+        self.compression = Compression::from_string(codec)?;
+        Ok(())
+    }
+
+    /// Declares the shape of the input tensor that inputs are reshaped into before the model is run.
+    ///
+    /// # Arguments
+    /// * `dims` - The comma separated axes of the input where identifiers such as `batch` or `seq_len` are dynamic.
+    pub fn add_input_dims(&mut self, dims: String) -> Result<(), SurrealError> {
+        self.input_dims = InputDims::from_string(dims)?;
+        Ok(())
+    }
+
+    /// Infers a header from a raw ONNX model by inspecting its graph. The names, element types and shapes of the
+    /// inputs and outputs and the metadata_props of the graph are stored in `self.graph`. The shape of a single input
+    /// becomes the input dimensions, the first output becomes the output, and the doc string becomes the description.
+    /// When the model has several inputs that each take a single value, every input becomes a column bound to it.
+    ///
+    /// # Arguments
+    /// * `model` - The bytes of the ONNX model.
+    ///
+    /// # Returns
+    /// The inferred header.
+    pub fn infer_from_onnx(model: &[u8]) -> Result<Self, SurrealError> {
+        let session = get_session(model)?;
+        let mut header = Header::fresh();
+        header.graph = Graph::from_session(&session)?;
+
+        let metadata = safe_eject!(session.metadata(), SurrealErrorStatus::BadRequest);
+        let name = safe_eject!(metadata.name(), SurrealErrorStatus::BadRequest);
+        if !name.is_empty() {
+            header.add_name(name);
+        }
+        if let Some(producer) = header.graph.producer.clone() {
+            header.engine = Engine::from_producer(&producer).unwrap_or(Engine::Onnx);
+            header.exporter.name = Some(producer);
+        }
+        let doc_string = safe_eject!(metadata.description(), SurrealErrorStatus::BadRequest);
+        if !doc_string.is_empty() {
+            header.add_description(doc_string);
+        }
+
+        match header.graph.inputs.as_slice() {
+            [input] => header.input_dims = input.shape.clone(),
+            inputs if inputs.iter().all(|input| input.row_size() == 1) => {
+                for input in inputs {
+                    header.keys.add_column(input.name.clone());
+                    header.inputs.add(input.name.clone(), input.name.clone());
+                }
+            }
+            _ => {}
+        }
+        if let Some(output) = header.graph.outputs.first() {
+            header.add_output(output.name.clone(), None);
+        }
+        Ok(header)
+    }
+
     /// The standard delimiter used to seperate each field in the header.
     fn delimiter() -> &'static str {
         "//=>"
     }
 
-    /// Constructs the `Header` struct from bytes.
+    /// Constructs the `Header` struct from bytes. Both the binary format and the legacy string format are supported.
     ///
     /// # Arguments
     /// * `data` - The bytes to be converted into a `Header` struct.
@@ -174,6 +526,155 @@ impl Header {
     /// # Returns
     /// The `Header` struct.
     pub fn from_bytes(data: Vec<u8>) -> Result<Self, SurrealError> {
+        if encoding::is_binary(&data) {
+            return Self::from_binary(&data);
+        }
+        Self::from_legacy_bytes(data)
+    }
+
+    /// Reads only the header of a `surml` file leaving the model bytes on disk so large catalogs of models can be
+    /// listed cheaply.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path to the `surml` file.
+    ///
+    /// # Returns
+    /// The `Header` struct.
+    pub fn read_from_path(file_path: &str) -> Result<Self, SurrealError> {
+        let file = safe_eject!(File::open(file_path), SurrealErrorStatus::NotFound);
+        Self::read_from_reader(file)
+    }
+
+    /// Reads the length prefix and the header section from the start of a `surml` file without reading the model.
+    ///
+    /// # Arguments
+    /// * `reader` - A reader positioned at the start of the `surml` file.
+    ///
+    /// # Returns
+    /// The `Header` struct.
+    pub fn read_from_reader<R: Read>(reader: R) -> Result<Self, SurrealError> {
+        Self::from_bytes(Self::read_section(reader)?)
+    }
+
+    /// Reads the raw header section from the start of a `surml` file.
+    ///
+    /// # Arguments
+    /// * `reader` - A reader positioned at the start of the `surml` file.
+    ///
+    /// # Returns
+    /// The bytes of the header without the length prefix.
+    pub(crate) fn read_section<R: Read>(mut reader: R) -> Result<Vec<u8>, SurrealError> {
+        // extract the first 4 bytes as an integer to get the length of the header
+        let mut buffer = [0u8; 4];
+        safe_eject!(
+            reader.read_exact(&mut buffer),
+            SurrealErrorStatus::BadRequest
+        );
+        let integer_value = u32::from_be_bytes(buffer);
+
+        // take the header bytes so a corrupt length does not allocate more than the file holds
+        let mut header_buffer = Vec::new();
+        safe_eject!(
+            reader
+                .take(integer_value as u64)
+                .read_to_end(&mut header_buffer),
+            SurrealErrorStatus::BadRequest
+        );
+        if header_buffer.len() != integer_value as usize {
+            return Err(SurrealError::new(
+                "Not enough bytes to read for header, maybe the file format is not correct"
+                    .to_string(),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(header_buffer)
+    }
+
+    /// Constructs the `Header` struct from the binary format.
+    ///
+    /// # Arguments
+    /// * `data` - The bytes of the header starting with the magic number.
+    ///
+    /// # Returns
+    /// The `Header` struct.
+    fn from_binary(data: &[u8]) -> Result<Self, SurrealError> {
+        let (_version, fields) = encoding::read_fields(data)?;
+        let mut header = Header::fresh();
+
+        // the normalisers reference the keys so the keys have to be decoded first
+        if let Some(field) = fields.iter().rev().find(|field| field.tag == tags::KEYS) {
+            header.keys = KeyBindings::from_field(field.payload)?;
+        }
+        for field in fields.iter() {
+            match field.tag {
+                tags::NORMALISERS => {
+                    header.normalisers = NormaliserMap::from_field(field.payload, &header.keys)?
+                }
+                // the first output is also written on its own so older readers can read it
+                tags::OUTPUT => {
+                    let output = Output::from_field(field.payload)?;
+                    if header.outputs.is_empty() && output != Output::fresh() {
+                        header.outputs.store.push(output);
+                    }
+                }
+                tags::OUTPUTS => header.outputs = Outputs::from_field(field.payload)?,
+                tags::NAME => {
+                    header.name = StringValue::from_string(Self::read_string_field(field.payload)?)
+                }
+                tags::VERSION => {
+                    header.version = Version::from_string(Self::read_string_field(field.payload)?)?
+                }
+                tags::DESCRIPTION => {
+                    header.description =
+                        StringValue::from_string(Self::read_string_field(field.payload)?)
+                }
+                tags::ENGINE => {
+                    header.engine = Engine::from_stored(Self::read_string_field(field.payload)?)
+                }
+                tags::EXPORTER => header.exporter = Exporter::from_field(field.payload)?,
+                tags::ORIGIN => header.origin = Origin::from_field(field.payload)?,
+                tags::INPUT_DIMS => {
+                    header.input_dims =
+                        InputDims::from_string(Self::read_string_field(field.payload)?)?
+                }
+                tags::INPUT_BINDINGS => header.inputs = InputBindings::from_field(field.payload)?,
+                tags::GRAPH => header.graph = Graph::from_field(field.payload)?,
+                tags::COLUMN_SPECS => header.keys.specs_from_field(field.payload)?,
+                tags::ENCODERS => {
+                    header.encoders = EncoderMap::from_field(field.payload, &header.keys)?
+                }
+                tags::METADATA => header.metadata = Metadata::from_field(field.payload)?,
+                tags::COMPRESSION => {
+                    header.compression =
+                        Compression::from_string(Self::read_string_field(field.payload)?)?
+                }
+                // the keys are decoded before the other fields
+                tags::KEYS => {}
+                // a newer writer marks the fields it cannot be computed without as critical
+                _ if field.critical => {
+                    return Err(SurrealError::new(
+                        format!(
+                            "Header field {} is needed to compute the model but is not supported by this version",
+                            field.tag
+                        ),
+                        SurrealErrorStatus::BadRequest,
+                    ))
+                }
+                // unknown fields are skipped so newer files can still be read
+                _ => {}
+            }
+        }
+        Ok(header)
+    }
+
+    /// Constructs the `Header` struct from the legacy `//=>` delimited string format.
+    ///
+    /// # Arguments
+    /// * `data` - The bytes to be converted into a `Header` struct.
+    ///
+    /// # Returns
+    /// The `Header` struct.
+    fn from_legacy_bytes(data: Vec<u8>) -> Result<Self, SurrealError> {
         let string_data = safe_eject!(String::from_utf8(data), SurrealErrorStatus::BadRequest);
 
         let buffer = string_data.split(Self::delimiter()).collect::<Vec<&str>>();
@@ -182,45 +683,110 @@ impl Header {
         let normalisers =
             NormaliserMap::from_string(buffer.get(2).unwrap_or(&"").to_string(), &keys)?;
         let output = Output::from_string(buffer.get(3).unwrap_or(&"").to_string())?;
+        let mut outputs = Outputs::fresh();
+        if output != Output::fresh() {
+            outputs.store.push(output);
+        }
         let name = StringValue::from_string(buffer.get(4).unwrap_or(&"").to_string());
         let version = Version::from_string(buffer.get(5).unwrap_or(&"").to_string())?;
         let description = StringValue::from_string(buffer.get(6).unwrap_or(&"").to_string());
-        let engine = Engine::from_string(buffer.get(7).unwrap_or(&"").to_string());
+        let engine = Engine::from_stored(buffer.get(7).unwrap_or(&"").to_string());
         let origin = Origin::from_string(buffer.get(8).unwrap_or(&"").to_string())?;
-        let input_dims = InputDims::from_string(buffer.get(9).unwrap_or(&"").to_string());
+        let input_dims = InputDims::from_string(buffer.get(9).unwrap_or(&"").to_string())?;
         Ok(Header {
             keys,
             normalisers,
-            output,
+            outputs,
             name,
             version,
             description,
             engine,
+            exporter: Exporter::fresh(),
             origin,
             input_dims,
+            compression: Compression::None,
+            inputs: InputBindings::fresh(),
+            graph: Graph::fresh(),
+            encoders: EncoderMap::fresh(),
+            metadata: Metadata::fresh(),
         })
     }
 
-    /// Converts the `Header` struct into bytes.
+    /// Encodes a single string as the payload of a header field.
+    fn string_field(value: &str) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_str(value)?;
+        Ok(writer.finish())
+    }
+
+    /// Decodes a payload written by `Header::string_field`.
+    fn read_string_field(data: &[u8]) -> Result<String, SurrealError> {
+        PayloadReader::new(data).read_str()
+    }
+
+    /// Converts the `Header` struct into bytes using the binary format.
     ///
     /// # Returns
-    /// A tuple containing the number of bytes in the header and the bytes themselves.
-    pub fn to_bytes(&self) -> (i32, Vec<u8>) {
-        let buffer = vec![
-            "".to_string(),
-            self.keys.to_string(),
-            self.normalisers.to_string(),
-            self.output.to_string(),
-            self.name.to_string(),
-            self.version.to_string(),
-            self.description.to_string(),
-            self.engine.to_string(),
-            self.origin.to_string(),
-            self.input_dims.to_string(),
-            "".to_string(),
-        ];
-        let buffer = buffer.join(Self::delimiter()).into_bytes();
-        (buffer.len() as i32, buffer)
+    /// A tuple containing the number of bytes in the header and the bytes themselves, or an error with a
+    /// `BadRequest` status if a value or the whole header is too long to be written.
+    pub fn to_bytes(&self) -> Result<(i32, Vec<u8>), SurrealError> {
+        let mut writer = HeaderWriter::new();
+        writer.write_critical_field(tags::KEYS, &self.keys.to_field()?)?;
+        writer.write_critical_field(tags::NORMALISERS, &self.normalisers.to_field()?)?;
+        let first_output = match self.outputs.first() {
+            Some(output) => output.to_field()?,
+            None => Output::fresh().to_field()?,
+        };
+        writer.write_critical_field(tags::OUTPUT, &first_output)?;
+        writer.write_field(tags::NAME, &Self::string_field(&self.name.to_string())?)?;
+        writer.write_field(
+            tags::VERSION,
+            &Self::string_field(&self.version.to_string())?,
+        )?;
+        writer.write_field(
+            tags::DESCRIPTION,
+            &Self::string_field(&self.description.to_string())?,
+        )?;
+        writer.write_field(tags::ENGINE, &Self::string_field(&self.engine.to_string())?)?;
+        writer.write_field(tags::ORIGIN, &self.origin.to_field()?)?;
+        writer.write_critical_field(
+            tags::INPUT_DIMS,
+            &Self::string_field(&self.input_dims.to_string())?,
+        )?;
+        writer.write_critical_field(
+            tags::COMPRESSION,
+            &Self::string_field(&self.compression.to_string())?,
+        )?;
+        writer.write_critical_field(tags::INPUT_BINDINGS, &self.inputs.to_field()?)?;
+        if !self.graph.is_empty() {
+            writer.write_field(tags::GRAPH, &self.graph.to_field()?)?;
+        }
+        if !self.keys.specs.is_empty() {
+            writer.write_critical_field(tags::COLUMN_SPECS, &self.keys.specs_to_field()?)?;
+        }
+        if !self.encoders.is_empty() {
+            writer.write_critical_field(tags::ENCODERS, &self.encoders.to_field()?)?;
+        }
+        if self.outputs.store.len() > 1 {
+            writer.write_critical_field(tags::OUTPUTS, &self.outputs.to_field()?)?;
+        }
+        if !self.metadata.is_empty() {
+            writer.write_field(tags::METADATA, &self.metadata.to_field()?)?;
+        }
+        if !self.exporter.is_empty() {
+            writer.write_field(tags::EXPORTER, &self.exporter.to_field()?)?;
+        }
+        let buffer = writer.finish();
+        match i32::try_from(buffer.len()) {
+            Ok(length) => Ok((length, buffer)),
+            Err(_) => Err(SurrealError::new(
+                format!(
+                    "The header of {} bytes is too long to be written",
+                    buffer.len()
+                ),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
     }
 }
 
@@ -256,12 +822,12 @@ mod tests {
             Engine::PyTorch,
             Header::delimiter(),
             Origin::from_string("author=>local".to_string()).unwrap(),
-// =======
-//             Origin::from_string("author=>local".to_string())
-//                 .unwrap(),
-// >>>>>>> origin/main
+            // =======
+            //             Origin::from_string("author=>local".to_string())
+            //                 .unwrap(),
+            // >>>>>>> origin/main
             Header::delimiter(),
-            InputDims::from_string("1,2".to_string()),
+            InputDims::from_string("1,2".to_string()).unwrap(),
             Header::delimiter(),
         )
     }
@@ -286,6 +852,42 @@ mod tests {
         assert_eq!(header.keys.store[5], "f");
     }
 
+    #[test]
+    fn test_legacy_header_with_constant_columns() {
+        let data = generate_string().replace(
+            &generate_normaliser_string(),
+            "a=>linear_scaling(3.0,3.0)//e=>z_score(2.0,0.0)",
+        );
+        let header = Header::from_bytes(data.into_bytes()).unwrap();
+        assert_eq!(
+            header.normalisers.to_string(),
+            "a=>linear_scaling(3,3)//e=>z_score(2,0)"
+        );
+
+        // the stored header keeps loading once it has been written in the binary format
+        let (_, bytes) = header.to_bytes().unwrap();
+        assert_eq!(Header::from_bytes(bytes).unwrap(), header);
+
+        let z_score = header.get_normaliser(&"e".to_string()).unwrap().unwrap();
+        assert_eq!(
+            z_score.normalise(2.0).unwrap_err().message,
+            "The std_dev of z_score cannot be zero"
+        );
+        assert_eq!(z_score.inverse_normalise(5.0).unwrap(), 2.0);
+
+        let mut header = header;
+        let error = header
+            .add_normaliser(
+                "b".to_string(),
+                NormaliserType::LinearScaling(LinearScaling { min: 3.0, max: 3.0 }),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.message,
+            "The min and max of linear_scaling cannot be equal"
+        );
+    }
+
     #[test]
     fn test_empty_header() {
         let string = "//=>//=>//=>//=>//=>//=>//=>//=>//=>".to_string();
@@ -304,23 +906,344 @@ mod tests {
     #[test]
     fn test_to_bytes() {
         let header = Header::from_bytes(generate_bytes()).unwrap();
-        let (bytes_num, bytes) = header.to_bytes();
-        let string = String::from_utf8(bytes).unwrap();
-
-        // below the integers are correct but there is a difference with the decimal point representation in the string, we can alter this
-        // fairly easy and will investigate it
-        let expected_string = "//=>a=>b=>c=>d=>e=>f//=>a=>linear_scaling(0,1)//b=>clipping(0,1.5)//c=>log_scaling(10,0)//e=>z_score(0,1)//=>g=>linear_scaling(0,1)//=>test model name//=>0.0.1//=>test description//=>pytorch//=>author=>local//=>1,2//=>".to_string();
+        let (bytes_num, bytes) = header.to_bytes().unwrap();
 
-        assert_eq!(string, expected_string);
-        assert_eq!(bytes_num, expected_string.len() as i32);
+        assert!(encoding::is_binary(&bytes));
+        assert_eq!(bytes_num, bytes.len() as i32);
+        assert_eq!(Header::from_bytes(bytes).unwrap(), header);
 
         let empty_header = Header::fresh();
-        let (bytes_num, bytes) = empty_header.to_bytes();
-        let string = String::from_utf8(bytes).unwrap();
-        let expected_string = "//=>//=>//=>//=>//=>//=>//=>//=>//=>//=>".to_string();
+        let (bytes_num, bytes) = empty_header.to_bytes().unwrap();
+
+        assert_eq!(bytes_num, bytes.len() as i32);
+        assert_eq!(Header::from_bytes(bytes).unwrap(), Header::fresh());
+    }
+
+    #[test]
+    fn test_version_round_trip() {
+        let header = Header::from_bytes(generate_bytes()).unwrap();
+        assert_eq!(header.version, Version::new(0, 0, 1));
+
+        let mut header = Header::fresh();
+        header
+            .add_version("1.300.0-rc.1+build.5".to_string())
+            .unwrap();
+        let (_, bytes) = header.to_bytes().unwrap();
+        assert_eq!(
+            Header::from_bytes(bytes).unwrap().version.to_string(),
+            "1.300.0-rc.1+build.5"
+        );
+        assert!(header.add_version("1.2".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let mut header = Header::from_bytes(generate_bytes()).unwrap();
+        assert!(header.metadata.is_empty());
+        header
+            .set_metadata(
+                "metrics".to_string(),
+                MetadataValue::from_json(r#"{"rmse": 0.4, "auc": 0.9}"#).unwrap(),
+            )
+            .unwrap();
+        header
+            .set_metadata("license".to_string(), "Apache-2.0".into())
+            .unwrap();
+
+        let (_, bytes) = header.to_bytes().unwrap();
+        let decoded = Header::from_bytes(bytes).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(
+            decoded.get_metadata("metrics.rmse"),
+            Some(&MetadataValue::Number(0.4))
+        );
 
-        assert_eq!(string, expected_string);
-        assert_eq!(bytes_num, expected_string.len() as i32);
+        header.remove_metadata("metrics");
+        header.remove_metadata("license");
+        assert_eq!(
+            header.to_bytes().unwrap(),
+            Header::from_bytes(generate_bytes())
+                .unwrap()
+                .to_bytes()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_engine_and_exporter_round_trip() {
+        let mut header = Header::from_bytes(generate_bytes()).unwrap();
+        assert_eq!(header.engine, Engine::PyTorch);
+        assert!(header.exporter.is_empty());
+
+        header.add_engine("sklearn".to_string()).unwrap();
+        header.add_exporter("skl2onnx".to_string(), Some("1.16.0".to_string()), Some(17));
+        let (_, bytes) = header.to_bytes().unwrap();
+        let decoded = Header::from_bytes(bytes).unwrap();
+        assert_eq!(decoded.engine, Engine::Sklearn);
+        assert_eq!(decoded.exporter.opset, Some(17));
+        assert_eq!(decoded, header);
+
+        assert!(header.add_engine("caffe".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_unknown_engine_is_kept() {
+        let mut header = Header::from_bytes(generate_bytes()).unwrap();
+        header.engine = Engine::Unknown("caffe".to_string());
+        let (_, bytes) = header.to_bytes().unwrap();
+        let decoded = Header::from_bytes(bytes).unwrap();
+        assert_eq!(decoded.engine, Engine::Unknown("caffe".to_string()));
+        assert_eq!(decoded, header);
+
+        let legacy = String::from_utf8(generate_bytes())
+            .unwrap()
+            .replace("pytorch", "caffe");
+        let decoded = Header::from_bytes(legacy.into_bytes()).unwrap();
+        assert_eq!(decoded.engine, Engine::Unknown("caffe".to_string()));
+    }
+
+    #[test]
+    fn test_provenance_round_trip() {
+        let mut header = Header::from_bytes(generate_bytes()).unwrap();
+        header
+            .add_origin("https://mlflow.example.com/runs/7".to_string())
+            .unwrap();
+        header
+            .add_provenance(Provenance {
+                run_id: Some("7".to_string()),
+                commit: Some("9edbc98".to_string()),
+                trained_at: Some("2024-01-31".to_string()),
+                ..Provenance::fresh()
+            })
+            .unwrap();
+        let (_, bytes) = header.to_bytes().unwrap();
+        assert_eq!(Header::from_bytes(bytes).unwrap(), header);
+    }
+
+    #[test]
+    fn test_compression_round_trip() {
+        let mut header = Header::fresh();
+        header.add_compression("zstd".to_string()).unwrap();
+        let (_, bytes) = header.to_bytes().unwrap();
+        assert_eq!(
+            Header::from_bytes(bytes).unwrap().compression,
+            Compression::Zstd
+        );
+        assert!(header.add_compression("brotli".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_encoders_and_column_specs_round_trip() {
+        use encoders::{hashing::Hashing, target::TargetEncoding};
+
+        let mut header = Header::fresh();
+        header.add_column("city".to_string());
+        header.add_column("street".to_string());
+        header.add_column("built".to_string());
+        header
+            .add_encoder(
+                "city".to_string(),
+                EncoderType::Target(TargetEncoding {
+                    table: vec![("london".to_string(), 3.5)],
+                    default: Some(1.0),
+                }),
+            )
+            .unwrap();
+        header
+            .add_encoder(
+                "street".to_string(),
+                EncoderType::Hashing(Hashing { buckets: 8 }),
+            )
+            .unwrap();
+        header
+            .add_column_spec("built".to_string(), ColumnSpec::new(ColumnType::Timestamp))
+            .unwrap();
+        assert_eq!(header.input_width(), 10);
+        assert_eq!(
+            header.parse_value("city", "1.5").unwrap(),
+            FeatureValue::Text("1.5".to_string())
+        );
+        assert!(header.parse_value("missing", "one").is_err());
+
+        let (_, bytes) = header.to_bytes().unwrap();
+        assert_eq!(Header::from_bytes(bytes).unwrap(), header);
+    }
+
+    #[test]
+    fn test_add_column_category() {
+        let mut header = Header::fresh();
+        header.add_column("colour".to_string());
+        header.add_column("size".to_string());
+        header
+            .add_column_spec(
+                "colour".to_string(),
+                ColumnSpec::new(ColumnType::Categorical),
+            )
+            .unwrap();
+        header
+            .add_column_category("colour".to_string(), "red".to_string())
+            .unwrap();
+        header
+            .add_column_category("colour".to_string(), "blue".to_string())
+            .unwrap();
+
+        let error = header
+            .add_column_category("colour".to_string(), "red".to_string())
+            .unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert_eq!(
+            error.message,
+            "Column colour already has the category 'red'"
+        );
+        assert_eq!(
+            header.keys.specs["colour"].categories,
+            vec!["red".to_string(), "blue".to_string()]
+        );
+        assert!(header
+            .add_column_category("size".to_string(), "large".to_string())
+            .is_err());
+    }
+
+    #[cfg(feature = "onnx-tests")]
+    #[test]
+    fn test_infer_from_onnx() {
+        crate::execution::session::set_environment().unwrap();
+        let model = std::fs::read("./model_stash/onnx/onnx/linear.onnx").unwrap();
+        let header = Header::infer_from_onnx(&model).unwrap();
+
+        assert_eq!(header.graph.inputs.len(), 1);
+        assert_eq!(header.graph.inputs[0].dtype, "f32");
+        assert_eq!(header.input_dims, header.graph.inputs[0].shape);
+        assert_eq!(
+            header
+                .outputs
+                .first()
+                .and_then(|output| output.name.as_deref()),
+            Some(header.graph.outputs[0].name.as_str())
+        );
+        assert_eq!(header.exporter.name, header.graph.producer);
+
+        let (_, bytes) = header.to_bytes().unwrap();
+        assert_eq!(Header::from_bytes(bytes).unwrap(), header);
+    }
+
+    #[test]
+    fn test_add_input_dims() {
+        let mut header = Header::fresh();
+        header
+            .add_input_dims("batch,seq_len,8".to_string())
+            .unwrap();
+        assert!(header.add_input_dims("batch,,8".to_string()).is_err());
+
+        let (_, bytes) = header.to_bytes().unwrap();
+        let decoded = Header::from_bytes(bytes).unwrap();
+        assert_eq!(decoded.input_dims.to_string(), "batch,seq_len,8");
+    }
+
+    #[test]
+    fn test_add_input_binding() {
+        let mut header = Header::fresh();
+        header.add_column("squarefoot".to_string());
+        header
+            .add_input_binding("squarefoot".to_string(), "numeric".to_string())
+            .unwrap();
+        let error = header
+            .add_input_binding("missing".to_string(), "numeric".to_string())
+            .unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::NotFound);
+
+        let (_, bytes) = header.to_bytes().unwrap();
+        assert_eq!(Header::from_bytes(bytes).unwrap(), header);
+    }
+
+    #[test]
+    fn test_delimiters_in_values() {
+        let mut header = Header::fresh();
+        header.add_column("a//=>b".to_string());
+        header.add_column("c=>d".to_string());
+        header.add_name("model//=>name".to_string());
+        header.add_description("uses => and //=> freely".to_string());
+        header.add_author("first=>last".to_string());
+        header.add_output("price=>usd".to_string(), None);
+        header
+            .add_normaliser(
+                "c=>d".to_string(),
+                NormaliserType::ZScore(ZScore {
+                    mean: 1.0,
+                    std_dev: 2.0,
+                }),
+            )
+            .unwrap();
+
+        let (_, bytes) = header.to_bytes().unwrap();
+        let decoded = Header::from_bytes(bytes).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(decoded.keys.store[0], "a//=>b");
+        assert_eq!(decoded.keys.store[1], "c=>d");
+    }
+
+    #[test]
+    fn test_unknown_fields_are_skipped() {
+        let header = Header::from_bytes(generate_bytes()).unwrap();
+        let (_, mut bytes) = header.to_bytes().unwrap();
+
+        // append a field that a future version of the format might write
+        let mut unknown = bytes.clone();
+        unknown.extend_from_slice(&500u16.to_be_bytes());
+        unknown.extend_from_slice(&3u32.to_be_bytes());
+        unknown.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(Header::from_bytes(unknown).unwrap(), header);
+
+        // a field the model cannot be computed without is not skipped
+        bytes.extend_from_slice(&(500u16 | tags::CRITICAL).to_be_bytes());
+        bytes.extend_from_slice(&3u32.to_be_bytes());
+        bytes.extend_from_slice(&[1, 2, 3]);
+        let error = Header::from_bytes(bytes).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert_eq!(
+            error.message,
+            "Header field 500 is needed to compute the model but is not supported by this version"
+        );
+    }
+
+    #[test]
+    fn test_version_2_header_is_read() {
+        let header = Header::from_bytes(generate_bytes()).unwrap();
+        let (_, bytes) = header.to_bytes().unwrap();
+
+        // version 2 wrote every field without the critical bit
+        let (_, fields) = encoding::read_fields(&bytes).unwrap();
+        let mut writer = HeaderWriter::new();
+        for field in fields {
+            writer.write_field(field.tag, field.payload).unwrap();
+        }
+        let mut bytes = writer.finish();
+        bytes[encoding::MAGIC.len()..encoding::MAGIC.len() + 2]
+            .copy_from_slice(&2u16.to_be_bytes());
+
+        assert_eq!(Header::from_bytes(bytes).unwrap(), header);
+    }
+
+    #[test]
+    fn test_read_from_reader() {
+        let (length, header_bytes) = Header::from_bytes(generate_bytes())
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        let mut bytes = length.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&header_bytes);
+        let header = Header::read_from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(header, Header::from_bytes(header_bytes).unwrap());
+
+        // a length prefix pointing past the end of the file is an error rather than a huge allocation
+        let mut bytes = u32::MAX.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&[1, 2, 3]);
+        let error = Header::read_from_reader(bytes.as_slice()).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Not enough bytes to read for header, maybe the file format is not correct"
+        );
+        assert!(Header::read_from_reader([0u8, 1].as_slice()).is_err());
     }
 
     #[test]
@@ -344,6 +1267,87 @@ mod tests {
         assert_eq!(header.keys.store[5], "f");
     }
 
+    #[test]
+    fn test_multiple_outputs_round_trip() {
+        let mut header = Header::fresh();
+        header.add_column("squarefoot".to_string());
+        header.add_output(
+            "price".to_string(),
+            Some(NormaliserType::ZScore(ZScore {
+                mean: 100.0,
+                std_dev: 10.0,
+            })),
+        );
+        header.add_output("days_on_market".to_string(), None);
+        header
+            .add_named_output_normaliser_step(
+                "days_on_market",
+                NormaliserType::LogScaling(LogScaling {
+                    base: 10.0,
+                    min: 1.0,
+                }),
+            )
+            .unwrap();
+        assert_eq!(
+            header.outputs.names(2).unwrap(),
+            vec!["price", "days_on_market"]
+        );
+
+        let decoded = Header::from_bytes(header.to_bytes().unwrap().1).unwrap();
+        assert_eq!(decoded, header);
+
+        let error = header
+            .add_named_output_normaliser_step(
+                "missing",
+                NormaliserType::ZScore(ZScore {
+                    mean: 0.0,
+                    std_dev: 1.0,
+                }),
+            )
+            .unwrap_err();
+        assert_eq!(error.message, "Output missing is not in the header");
+    }
+
+    #[test]
+    fn test_fit_normalisers() {
+        let mut header = Header::fresh();
+        header.add_column("squarefoot".to_string());
+        header.add_column("num_floors".to_string());
+        header
+            .add_normaliser(
+                "num_floors".to_string(),
+                NormaliserType::ZScore(ZScore {
+                    mean: 0.0,
+                    std_dev: 1.0,
+                }),
+            )
+            .unwrap();
+
+        let data = HashMap::from([
+            ("squarefoot".to_string(), vec![500.0, 1000.0, 1500.0]),
+            ("num_floors".to_string(), vec![1.0, 2.0, 3.0, 4.0, 5.0]),
+        ]);
+        let kinds = HashMap::from([
+            ("squarefoot".to_string(), FitKind::LinearScaling),
+            ("num_floors".to_string(), FitKind::RobustScaling),
+        ]);
+        header.fit_normalisers(&data, &kinds).unwrap();
+        header
+            .fit_output_normaliser(&[9.0, 99.0, 999.0], FitKind::LogScaling { base: 10.0 })
+            .unwrap();
+
+        assert_eq!(
+            header.normalisers.to_string(),
+            "num_floors=>robust_scaling(median=3,iqr=2)//squarefoot=>linear_scaling(500,1500)"
+        );
+        let output_normaliser = header.outputs.first().unwrap().normaliser.as_ref().unwrap();
+        assert_eq!(output_normaliser.to_string(), "log_scaling(10,0)");
+        assert_eq!(output_normaliser.inverse_normalise(2.0).unwrap(), 100.0);
+
+        let decoded = Header::from_bytes(header.to_bytes().unwrap().1).unwrap();
+        assert_eq!(decoded, header);
+    }
+
     #[test]
     fn test_add_normalizer() {
         let mut header = Header::fresh();
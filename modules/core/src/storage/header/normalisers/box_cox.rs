@@ -0,0 +1,116 @@
+//! The functionality and parameters around a Box-Cox power transform.
+use super::parameters::NormaliserParameters;
+use super::traits::Normaliser;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// A Box-Cox power transform for strictly positive values. Chain a z-score after it for a standardised power
+/// transform.
+///
+/// # Fields
+/// * `lambda` - The power of the transform where zero is a natural log.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoxCox {
+    pub lambda: f32,
+}
+
+impl BoxCox {
+    /// Constructs the normaliser from the parameter `lambda`.
+    ///
+    /// # Arguments
+    /// * `parameters` - The parameters of the definition.
+    ///
+    /// # Returns
+    /// The Box-Cox normaliser.
+    pub fn from_parameters(parameters: &NormaliserParameters) -> Result<Self, SurrealError> {
+        parameters.accept_only(&["lambda"])?;
+        let lambda = parameters.require("lambda", 0)?;
+        Ok(BoxCox { lambda })
+    }
+}
+
+impl Normaliser for BoxCox {
+    /// Normalises a value.
+    ///
+    /// # Arguments
+    /// * `input` - The value to normalise.
+    ///
+    /// # Returns
+    /// The normalised value.
+    fn normalise(&self, input: f32) -> f32 {
+        if self.lambda == 0.0 {
+            input.ln()
+        } else {
+            (input.powf(self.lambda) - 1.0) / self.lambda
+        }
+    }
+
+    /// Applies the inverse of the value for the normaliser.
+    ///
+    /// # Arguments
+    /// * `input` - The value to inverse normalise.
+    ///
+    /// # Returns
+    /// The inverse normalised value.
+    fn inverse_normalise(&self, input: f32) -> f32 {
+        if self.lambda == 0.0 {
+            input.exp()
+        } else {
+            (self.lambda * input + 1.0).powf(1.0 / self.lambda)
+        }
+    }
+
+    /// Checks that the value is strictly positive.
+    ///
+    /// # Arguments
+    /// * `input` - The value to check.
+    fn check_domain(&self, input: f32) -> Result<(), SurrealError> {
+        if input > 0.0 {
+            return Ok(());
+        }
+        Err(SurrealError::new(
+            format!(
+                "box_cox is only defined for positive values, received {}",
+                input
+            ),
+            SurrealErrorStatus::BadRequest,
+        ))
+    }
+
+    /// Checks that the value is in the range of the transform so the root of the inverse is defined.
+    ///
+    /// # Arguments
+    /// * `input` - The value to check.
+    fn check_inverse_domain(&self, input: f32) -> Result<(), SurrealError> {
+        if self.lambda == 0.0 || self.lambda * input + 1.0 > 0.0 {
+            return Ok(());
+        }
+        Err(SurrealError::new(
+            format!(
+                "{} is outside of the range of box_cox with a lambda of {}",
+                input, self.lambda
+            ),
+            SurrealErrorStatus::BadRequest,
+        ))
+    }
+
+    fn key() -> String {
+        "box_cox".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_normalise_and_inverse() {
+        let normaliser = BoxCox { lambda: 0.5 };
+        assert_eq!(normaliser.normalise(9.0), 4.0);
+        assert_eq!(normaliser.inverse_normalise(4.0), 9.0);
+
+        let normaliser = BoxCox { lambda: 0.0 };
+        assert_eq!(normaliser.normalise(1.0), 0.0);
+        assert!((normaliser.inverse_normalise(normaliser.normalise(5.0)) - 5.0).abs() < 1e-5);
+    }
+}
@@ -1,17 +1,37 @@
 //! The functionality and parameters around a clipping normaliser.
+use super::parameters::NormaliserParameters;
 use super::traits::Normaliser;
+use crate::errors::error::SurrealError;
 
 /// A clipping normaliser.
 ///
 /// # Fields
 /// * `min` - The minimum value to clip to.
 /// * `max` - The maximum value to clip to.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Clipping {
     pub min: Option<f32>,
     pub max: Option<f32>,
 }
 
+impl Clipping {
+    /// Constructs the normaliser from the parameters `min` and `max` where a missing parameter leaves that bound
+    /// open such as `clipping(min=0)`.
+    ///
+    /// # Arguments
+    /// * `parameters` - The parameters of the definition.
+    ///
+    /// # Returns
+    /// The clipping normaliser.
+    pub fn from_parameters(parameters: &NormaliserParameters) -> Result<Self, SurrealError> {
+        parameters.accept_only(&["min", "max"])?;
+        Ok(Clipping {
+            min: parameters.get("min", 0),
+            max: parameters.get("max", 1),
+        })
+    }
+}
+
 impl Normaliser for Clipping {
     /// Normalises a value.
     ///
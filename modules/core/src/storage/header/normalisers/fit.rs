@@ -0,0 +1,478 @@
+//! Defines the fitting of normalisers to the values of a column, either from a slice of values or online one value at
+//! a time for tables that are too large to hold in memory.
+use std::collections::HashMap;
+
+use super::linear_scaling::LinearScaling;
+use super::log_scale::LogScaling;
+use super::parameters::NormaliserParameters;
+use super::quantile::Quantile;
+use super::robust_scaling::RobustScaling;
+use super::wrapper::NormaliserType;
+use super::z_score::ZScore;
+use super::NormaliserMap;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use crate::storage::header::keys::KeyBindings;
+
+/// Rejects values that would poison the fitted parameters.
+fn check_finite(label: &str, value: f32) -> Result<(), SurrealError> {
+    if value.is_finite() {
+        return Ok(());
+    }
+    Err(SurrealError::new(
+        format!("Cannot fit {} to the non-finite value {}", label, value),
+        SurrealErrorStatus::BadRequest,
+    ))
+}
+
+/// The error returned when a normaliser is fitted without any values.
+fn no_values(label: &str) -> SurrealError {
+    SurrealError::new(
+        format!("Cannot fit {} without any values", label),
+        SurrealErrorStatus::BadRequest,
+    )
+}
+
+/// The spread of a column, or one for a constant column so the fitted normaliser does not divide by zero.
+pub(crate) fn unit_if_zero(spread: f32) -> f32 {
+    if spread == 0.0 {
+        1.0
+    } else {
+        spread
+    }
+}
+
+/// Sorts the values of a column once they have been checked so quantiles can be read from them.
+///
+/// # Arguments
+/// * `label` - The label of the normaliser being fitted for error messages.
+/// * `values` - The values of the column.
+///
+/// # Returns
+/// The values in ascending order.
+pub(crate) fn sorted_values(label: &str, values: &[f32]) -> Result<Vec<f32>, SurrealError> {
+    if values.is_empty() {
+        return Err(no_values(label));
+    }
+    for value in values {
+        check_finite(label, *value)?;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    Ok(sorted)
+}
+
+/// Reads a quantile from sorted values, interpolating linearly between the two closest values.
+///
+/// # Arguments
+/// * `sorted` - The values in ascending order which must not be empty.
+/// * `quantile` - The quantile to read in `[0, 1]`.
+///
+/// # Returns
+/// The value at the quantile.
+pub(crate) fn quantile_of_sorted(sorted: &[f32], quantile: f32) -> f32 {
+    let position = quantile.clamp(0.0, 1.0) * (sorted.len() - 1) as f32;
+    let lower = position.floor() as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    let fraction = position - lower as f32;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
+
+/// Fits a z-score online with Welford's algorithm so the variance stays accurate over long streams.
+///
+/// # Fields
+/// * `count` - The number of values seen.
+/// * `mean` - The running mean.
+/// * `m2` - The running sum of squared differences from the mean.
+#[derive(Debug, Default, Clone)]
+pub struct ZScoreFit {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl ZScoreFit {
+    /// Adds a value to the fit.
+    ///
+    /// # Arguments
+    /// * `value` - The value to add.
+    pub fn update(&mut self, value: f32) -> Result<(), SurrealError> {
+        check_finite("z_score", value)?;
+        self.count += 1;
+        let delta = value as f64 - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value as f64 - self.mean);
+        Ok(())
+    }
+
+    /// Builds the normaliser from the values seen so far using the population standard deviation. A constant column
+    /// has no spread so it is scaled by one instead, like sklearn's `StandardScaler`.
+    ///
+    /// # Returns
+    /// The fitted z-score normaliser.
+    pub fn finish(&self) -> Result<ZScore, SurrealError> {
+        if self.count == 0 {
+            return Err(no_values("z_score"));
+        }
+        let std_dev = unit_if_zero((self.m2 / self.count as f64).sqrt() as f32);
+        ZScore::from_parameters(
+            &NormaliserParameters::new("z_score")
+                .with_value(self.mean as f32)
+                .with_value(std_dev),
+        )
+    }
+}
+
+/// Fits a linear scaling online by tracking the smallest and largest values.
+///
+/// # Fields
+/// * `min` - The smallest value seen.
+/// * `max` - The largest value seen.
+#[derive(Debug, Default, Clone)]
+pub struct LinearScalingFit {
+    min: Option<f32>,
+    max: Option<f32>,
+}
+
+impl LinearScalingFit {
+    /// Adds a value to the fit.
+    ///
+    /// # Arguments
+    /// * `value` - The value to add.
+    pub fn update(&mut self, value: f32) -> Result<(), SurrealError> {
+        check_finite("linear_scaling", value)?;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        Ok(())
+    }
+
+    /// Builds the normaliser from the values seen so far. A constant column has no range so it is scaled over a range
+    /// of one from its value instead, like sklearn's `MinMaxScaler`.
+    ///
+    /// # Returns
+    /// The fitted linear scaling normaliser.
+    pub fn finish(&self) -> Result<LinearScaling, SurrealError> {
+        let (min, max) = match (self.min, self.max) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return Err(no_values("linear_scaling")),
+        };
+        LinearScaling::from_parameters(
+            &NormaliserParameters::new("linear_scaling")
+                .with_value(min)
+                .with_value(min + unit_if_zero(max - min)),
+        )
+    }
+}
+
+/// Fits a log scaling online by tracking the smallest value so every value can be shifted into the domain of the
+/// logarithm.
+///
+/// # Fields
+/// * `base` - The base of the logarithm.
+/// * `min` - The smallest value seen.
+#[derive(Debug, Clone)]
+pub struct LogScalingFit {
+    base: f32,
+    min: Option<f32>,
+}
+
+impl LogScalingFit {
+    /// Constructs an empty fit.
+    ///
+    /// # Arguments
+    /// * `base` - The base of the logarithm.
+    ///
+    /// # Returns
+    /// A fit that has not seen any values.
+    pub fn new(base: f32) -> Self {
+        LogScalingFit { base, min: None }
+    }
+
+    /// Adds a value to the fit.
+    ///
+    /// # Arguments
+    /// * `value` - The value to add.
+    pub fn update(&mut self, value: f32) -> Result<(), SurrealError> {
+        check_finite("log_scaling", value)?;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        Ok(())
+    }
+
+    /// Builds the normaliser from the values seen so far. Positive values are not shifted, otherwise the smallest
+    /// value is shifted to one so it scales to zero.
+    ///
+    /// # Returns
+    /// The fitted log scaling normaliser.
+    pub fn finish(&self) -> Result<LogScaling, SurrealError> {
+        let min = self.min.ok_or_else(|| no_values("log_scaling"))?;
+        let shift = if min > 0.0 { 0.0 } else { 1.0 - min };
+        LogScaling::from_parameters(
+            &NormaliserParameters::new("log_scaling")
+                .with_value(self.base)
+                .with_value(shift),
+        )
+    }
+}
+
+/// The kind of normaliser to fit to the values of a column.
+///
+/// # Arguments
+/// * `ZScore` - A z-score using the population standard deviation.
+/// * `LinearScaling` - A linear scaling between the smallest and largest values.
+/// * `LogScaling` - A log scaling with the given base, shifted so every value is in its domain.
+/// * `RobustScaling` - A robust scaling with the median and interquartile range.
+/// * `Quantile` - A quantile normaliser with the given number of evenly spaced references.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FitKind {
+    ZScore,
+    LinearScaling,
+    LogScaling { base: f32 },
+    RobustScaling,
+    Quantile { references: usize },
+}
+
+impl FitKind {
+    /// Fits a normaliser to the values of a column.
+    ///
+    /// # Arguments
+    /// * `values` - The values of the column.
+    ///
+    /// # Returns
+    /// The fitted normaliser ready to be stored in the header.
+    pub fn fit(&self, values: &[f32]) -> Result<NormaliserType, SurrealError> {
+        let normaliser = match self {
+            FitKind::ZScore => NormaliserType::ZScore(ZScore::fit(values)?),
+            FitKind::LinearScaling => NormaliserType::LinearScaling(LinearScaling::fit(values)?),
+            FitKind::LogScaling { base } => {
+                NormaliserType::LogScaling(LogScaling::fit(values, *base)?)
+            }
+            FitKind::RobustScaling => NormaliserType::RobustScaling(RobustScaling::fit(values)?),
+            FitKind::Quantile { references } => {
+                NormaliserType::Quantile(Quantile::fit(values, *references)?)
+            }
+        };
+        Ok(normaliser)
+    }
+
+    /// Constructs an online fit for the kind of normaliser. Normalisers fitted from quantiles need every value at
+    /// once so they cannot be fitted online.
+    ///
+    /// # Returns
+    /// An online fit that has not seen any values.
+    pub fn online(&self) -> Result<OnlineFit, SurrealError> {
+        match self {
+            FitKind::ZScore => Ok(OnlineFit::ZScore(ZScoreFit::default())),
+            FitKind::LinearScaling => Ok(OnlineFit::LinearScaling(LinearScalingFit::default())),
+            FitKind::LogScaling { base } => Ok(OnlineFit::LogScaling(LogScalingFit::new(*base))),
+            FitKind::RobustScaling => Err(SurrealError::new(
+                "robust_scaling cannot be fitted online".to_string(),
+                SurrealErrorStatus::BadRequest,
+            )),
+            FitKind::Quantile { .. } => Err(SurrealError::new(
+                "quantile cannot be fitted online".to_string(),
+                SurrealErrorStatus::BadRequest,
+            )),
+        }
+    }
+}
+
+/// An online fit of any normaliser that can be fitted one value at a time.
+#[derive(Debug, Clone)]
+pub enum OnlineFit {
+    ZScore(ZScoreFit),
+    LinearScaling(LinearScalingFit),
+    LogScaling(LogScalingFit),
+}
+
+impl OnlineFit {
+    /// Adds a value to the fit.
+    ///
+    /// # Arguments
+    /// * `value` - The value to add.
+    pub fn update(&mut self, value: f32) -> Result<(), SurrealError> {
+        match self {
+            OnlineFit::ZScore(fit) => fit.update(value),
+            OnlineFit::LinearScaling(fit) => fit.update(value),
+            OnlineFit::LogScaling(fit) => fit.update(value),
+        }
+    }
+
+    /// Builds the normaliser from the values seen so far.
+    ///
+    /// # Returns
+    /// The fitted normaliser ready to be stored in the header.
+    pub fn finish(&self) -> Result<NormaliserType, SurrealError> {
+        let normaliser = match self {
+            OnlineFit::ZScore(fit) => NormaliserType::ZScore(fit.finish()?),
+            OnlineFit::LinearScaling(fit) => NormaliserType::LinearScaling(fit.finish()?),
+            OnlineFit::LogScaling(fit) => NormaliserType::LogScaling(fit.finish()?),
+        };
+        Ok(normaliser)
+    }
+}
+
+/// Fits the normalisers of several columns online from rows of a table.
+///
+/// # Fields
+/// * `fits` - The column names and their online fits in the order they were declared.
+#[derive(Debug, Clone)]
+pub struct TableFit {
+    fits: Vec<(String, OnlineFit)>,
+}
+
+impl TableFit {
+    /// Constructs an online fit for each column.
+    ///
+    /// # Arguments
+    /// * `kinds` - The kind of normaliser to fit for each column.
+    ///
+    /// # Returns
+    /// A fit that has not seen any rows.
+    pub fn new(kinds: &HashMap<String, FitKind>) -> Result<Self, SurrealError> {
+        let mut fits = Vec::with_capacity(kinds.len());
+        for (column, kind) in kinds {
+            fits.push((column.clone(), kind.online()?));
+        }
+        fits.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(TableFit { fits })
+    }
+
+    /// Adds a row of the table to the fit. Columns missing from the row are skipped so sparse rows can be streamed.
+    ///
+    /// # Arguments
+    /// * `row` - The values of the row keyed by column name.
+    pub fn update(&mut self, row: &HashMap<String, f32>) -> Result<(), SurrealError> {
+        for (column, fit) in &mut self.fits {
+            if let Some(value) = row.get(column) {
+                fit.update(*value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the normalisers from the rows seen so far.
+    ///
+    /// # Arguments
+    /// * `keys_reference` - A reference to the key bindings to extract the index of each column.
+    ///
+    /// # Returns
+    /// A `NormaliserMap` holding the fitted normalisers.
+    pub fn finish(&self, keys_reference: &KeyBindings) -> Result<NormaliserMap, SurrealError> {
+        let mut normaliser_map = NormaliserMap::fresh();
+        for (column, fit) in &self.fits {
+            normaliser_map.add_normaliser(fit.finish()?, column.clone(), keys_reference)?;
+        }
+        Ok(normaliser_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_online_fit_matches_batch_fit() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let mut online = FitKind::ZScore.online().unwrap();
+        for value in values {
+            online.update(value).unwrap();
+        }
+        let fitted = online.finish().unwrap();
+        assert_eq!(
+            fitted,
+            NormaliserType::ZScore(ZScore {
+                mean: 5.0,
+                std_dev: 2.0
+            })
+        );
+        assert_eq!(FitKind::ZScore.fit(&values).unwrap(), fitted);
+
+        let mut online = FitKind::LogScaling { base: 10.0 }.online().unwrap();
+        for value in [-9.0, 0.0, 90.0] {
+            online.update(value).unwrap();
+        }
+        assert_eq!(
+            online.finish().unwrap(),
+            NormaliserType::LogScaling(LogScaling {
+                base: 10.0,
+                min: 10.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_fit_errors() {
+        assert_eq!(
+            FitKind::ZScore.fit(&[]).unwrap_err().message,
+            "Cannot fit z_score without any values"
+        );
+        assert_eq!(
+            FitKind::RobustScaling
+                .fit(&[1.0, f32::NAN])
+                .unwrap_err()
+                .message,
+            "Cannot fit robust_scaling to the non-finite value NaN"
+        );
+        assert_eq!(
+            FitKind::Quantile { references: 3 }
+                .online()
+                .unwrap_err()
+                .message,
+            "quantile cannot be fitted online"
+        );
+    }
+
+    #[test]
+    fn test_constant_column_is_scaled_by_one() {
+        let values = [3.0, 3.0, 3.0];
+        assert_eq!(
+            FitKind::ZScore.fit(&values).unwrap(),
+            NormaliserType::ZScore(ZScore {
+                mean: 3.0,
+                std_dev: 1.0
+            })
+        );
+        assert_eq!(
+            FitKind::LinearScaling.fit(&values).unwrap(),
+            NormaliserType::LinearScaling(LinearScaling { min: 3.0, max: 4.0 })
+        );
+        assert_eq!(
+            FitKind::RobustScaling.fit(&values).unwrap(),
+            NormaliserType::RobustScaling(RobustScaling {
+                median: 3.0,
+                iqr: 1.0
+            })
+        );
+
+        let mut keys = KeyBindings::fresh();
+        keys.add_column("constant".to_string());
+        keys.add_column("varied".to_string());
+        let data = HashMap::from([
+            ("constant".to_string(), vec![3.0, 3.0, 3.0]),
+            ("varied".to_string(), vec![1.0, 2.0, 3.0]),
+        ]);
+        let kinds = HashMap::from([
+            ("constant".to_string(), FitKind::ZScore),
+            ("varied".to_string(), FitKind::LinearScaling),
+        ]);
+        let normaliser_map = NormaliserMap::fit_columns(&data, &kinds, &keys).unwrap();
+        assert_eq!(
+            normaliser_map.to_string(),
+            "constant=>z_score(3,1)//varied=>linear_scaling(1,3)"
+        );
+        let constant = normaliser_map
+            .get_normaliser("constant".to_string(), &keys)
+            .unwrap()
+            .unwrap();
+        assert_eq!(constant.normalise(3.0).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_quantile_of_sorted() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(quantile_of_sorted(&sorted, 0.0), 1.0);
+        assert_eq!(quantile_of_sorted(&sorted, 0.5), 3.0);
+        assert_eq!(quantile_of_sorted(&sorted, 0.125), 1.5);
+        assert_eq!(quantile_of_sorted(&sorted, 1.0), 5.0);
+    }
+}
@@ -1,18 +1,71 @@
 //! The functionality and parameters around a linear scaling normaliser.
+use super::fit::LinearScalingFit;
+use super::parameters::NormaliserParameters;
 use super::traits::Normaliser;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
 
 /// A linear scaling normaliser.
 ///
 /// # Fields
 /// * `min` - The minimum value to scale to.
 /// * `max` - The maximum value to scale to.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct LinearScaling {
     pub min: f32,
     pub max: f32,
 }
 
+impl LinearScaling {
+    /// Constructs the normaliser from the parameters `min` and `max`.
+    ///
+    /// # Arguments
+    /// * `parameters` - The parameters of the definition.
+    ///
+    /// # Returns
+    /// The linear scaling normaliser.
+    pub fn from_parameters(parameters: &NormaliserParameters) -> Result<Self, SurrealError> {
+        parameters.accept_only(&["min", "max"])?;
+        let min = parameters.require("min", 0)?;
+        let max = parameters.require("max", 1)?;
+        Ok(LinearScaling { min, max })
+    }
+
+    /// Fits the normaliser to the smallest and largest values of a column.
+    ///
+    /// # Arguments
+    /// * `values` - The values of the column.
+    ///
+    /// # Returns
+    /// The fitted linear scaling normaliser.
+    pub fn fit(values: &[f32]) -> Result<Self, SurrealError> {
+        let mut fit = LinearScaling::online();
+        for value in values {
+            fit.update(*value)?;
+        }
+        fit.finish()
+    }
+
+    /// Constructs an online fit for tables too large to hold in memory.
+    ///
+    /// # Returns
+    /// A fit that has not seen any values.
+    pub fn online() -> LinearScalingFit {
+        LinearScalingFit::default()
+    }
+}
+
 impl Normaliser for LinearScaling {
+    /// Checks that the normaliser does not divide by zero.
+    fn check_parameters(&self) -> Result<(), SurrealError> {
+        if self.min == self.max {
+            return Err(SurrealError::new(
+                "The min and max of linear_scaling cannot be equal".to_string(),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(())
+    }
+
     /// Normalises a value.
     ///
     /// # Arguments
@@ -1,18 +1,80 @@
 //! The functionality and parameters around a log scaling normaliser.
+use super::fit::LogScalingFit;
+use super::parameters::NormaliserParameters;
 use super::traits::Normaliser;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
 
 /// A log scaling normaliser.
 ///
 /// # Fields
 /// * `base` - The base of the logarithm.
 /// * `min` - The minimum value to scale to.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct LogScaling {
     pub base: f32,
     pub min: f32,
 }
 
+impl LogScaling {
+    /// Constructs the normaliser from the parameters `base` and `min`.
+    ///
+    /// # Arguments
+    /// * `parameters` - The parameters of the definition.
+    ///
+    /// # Returns
+    /// The log scaling normaliser.
+    pub fn from_parameters(parameters: &NormaliserParameters) -> Result<Self, SurrealError> {
+        parameters.accept_only(&["base", "min"])?;
+        let base = parameters.require("base", 0)?;
+        let min = parameters.require("min", 1)?;
+        let normaliser = LogScaling { base, min };
+        normaliser.check_parameters()?;
+        Ok(normaliser)
+    }
+
+    /// Fits the normaliser to the values of a column so every value is in the domain of the logarithm.
+    ///
+    /// # Arguments
+    /// * `values` - The values of the column.
+    /// * `base` - The base of the logarithm.
+    ///
+    /// # Returns
+    /// The fitted log scaling normaliser.
+    pub fn fit(values: &[f32], base: f32) -> Result<Self, SurrealError> {
+        let mut fit = LogScaling::online(base);
+        for value in values {
+            fit.update(*value)?;
+        }
+        fit.finish()
+    }
+
+    /// Constructs an online fit for tables too large to hold in memory.
+    ///
+    /// # Arguments
+    /// * `base` - The base of the logarithm.
+    ///
+    /// # Returns
+    /// A fit that has not seen any values.
+    pub fn online(base: f32) -> LogScalingFit {
+        LogScalingFit::new(base)
+    }
+}
+
 impl Normaliser for LogScaling {
+    /// Checks that the base gives a logarithm that is defined.
+    fn check_parameters(&self) -> Result<(), SurrealError> {
+        if self.base.is_nan() || self.base <= 0.0 || self.base == 1.0 {
+            return Err(SurrealError::new(
+                format!(
+                    "The base of log_scaling must be positive and not 1, received {}",
+                    self.base
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(())
+    }
+
     /// Normalises a value.
     ///
     /// # Arguments
@@ -32,7 +94,24 @@ impl Normaliser for LogScaling {
     /// # Returns
     /// The inverse normalised value.
     fn inverse_normalise(&self, input: f32) -> f32 {
-        (input.powf(self.base)) - self.min
+        self.base.powf(input) - self.min
+    }
+
+    /// Checks that the shifted value is positive as the logarithm of anything else is NaN.
+    ///
+    /// # Arguments
+    /// * `input` - The value to check.
+    fn check_domain(&self, input: f32) -> Result<(), SurrealError> {
+        if input + self.min > 0.0 {
+            return Ok(());
+        }
+        Err(SurrealError::new(
+            format!(
+                "log_scaling is only defined for values above {}, received {}",
+                -self.min, input
+            ),
+            SurrealErrorStatus::BadRequest,
+        ))
     }
 
     /// The key of the normaliser.
@@ -60,4 +139,51 @@ mod tests {
         let actual = normaliser.normalise(input);
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_inverse_normalise() {
+        let normaliser = LogScaling {
+            base: 10.0,
+            min: 1.0,
+        };
+        assert_eq!(normaliser.normalise(99.0), 2.0);
+        assert_eq!(normaliser.inverse_normalise(2.0), 99.0);
+    }
+
+    #[test]
+    fn test_check_parameters() {
+        let normaliser = LogScaling {
+            base: 1.0,
+            min: 0.0,
+        };
+        assert_eq!(
+            normaliser.check_parameters().unwrap_err().message,
+            "The base of log_scaling must be positive and not 1, received 1"
+        );
+        assert!(LogScaling {
+            base: -2.0,
+            min: 0.0
+        }
+        .check_parameters()
+        .is_err());
+        assert!(LogScaling {
+            base: 10.0,
+            min: 0.0
+        }
+        .check_parameters()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_check_domain() {
+        let normaliser = LogScaling {
+            base: 10.0,
+            min: 1.0,
+        };
+        assert!(normaliser.check_domain(-0.5).is_ok());
+        assert_eq!(
+            normaliser.check_domain(-1.0).unwrap_err().message,
+            "log_scaling is only defined for values above -1, received -1"
+        );
+    }
 }
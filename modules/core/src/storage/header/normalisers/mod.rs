@@ -2,17 +2,28 @@
 use std::collections::HashMap;
 use std::fmt;
 
+pub mod box_cox;
 pub mod clipping;
+pub mod fit;
 pub mod linear_scaling;
 pub mod log_scale;
+pub mod parameters;
+pub mod pipeline;
+pub mod quantile;
+pub mod registry;
+pub mod robust_scaling;
 pub mod traits;
 pub mod utils;
 pub mod wrapper;
+pub mod yeo_johnson;
 pub mod z_score;
 
+use super::encoding::{PayloadReader, PayloadWriter};
 use super::keys::KeyBindings;
 use crate::errors::error::{SurrealError, SurrealErrorStatus};
 use crate::safe_eject_option;
+use fit::FitKind;
+use pipeline::Pipeline;
 use utils::{extract_label, extract_two_numbers};
 use wrapper::NormaliserType;
 
@@ -22,7 +33,7 @@ use wrapper::NormaliserType;
 /// * `store` - A vector of normalisers.
 /// * `store_ref` - A vector of column names to correlate with the normalisers in the store.
 /// * `reference` - A map of the index of the column in the key bindings to the index of the normaliser in the store.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct NormaliserMap {
     pub store: Vec<NormaliserType>,
     pub store_ref: Vec<String>,
@@ -53,16 +64,105 @@ impl NormaliserMap {
         normaliser: NormaliserType,
         column_name: String,
         keys_reference: &KeyBindings,
+    ) -> Result<(), SurrealError> {
+        normaliser.check_parameters()?;
+        self.insert(normaliser, column_name, keys_reference)
+    }
+
+    /// Adds a normaliser to the map without checking its parameters so stored headers keep loading.
+    ///
+    /// # Arguments
+    /// * `normaliser` - The normaliser to add.
+    /// * `column_name` - The name of the column to which the normaliser is applied.
+    /// * `keys_reference` - A reference to the key bindings to extract the index.
+    fn insert(
+        &mut self,
+        normaliser: NormaliserType,
+        column_name: String,
+        keys_reference: &KeyBindings,
     ) -> Result<(), SurrealError> {
         let counter = self.store.len();
         let column_input_index =
             safe_eject_option!(keys_reference.reference.get(column_name.as_str()));
+        // a column has a single normaliser so adding another one replaces it
+        if let Some(index) = self.reference.get(column_input_index) {
+            self.store[*index] = normaliser;
+            return Ok(());
+        }
         self.reference.insert(*column_input_index as usize, counter);
         self.store.push(normaliser);
         self.store_ref.push(column_name);
         Ok(())
     }
 
+    /// Appends a normaliser to the pipeline of a column so it is applied after the normalisers already on the column.
+    ///
+    /// # Arguments
+    /// * `normaliser` - The normaliser to append.
+    /// * `column_name` - The name of the column to which the normaliser is applied.
+    /// * `keys_reference` - A reference to the key bindings to extract the index.
+    pub fn add_normaliser_step(
+        &mut self,
+        normaliser: NormaliserType,
+        column_name: String,
+        keys_reference: &KeyBindings,
+    ) -> Result<(), SurrealError> {
+        normaliser.check_parameters()?;
+        let column_input_index =
+            safe_eject_option!(keys_reference.reference.get(column_name.as_str()));
+        match self.reference.get(column_input_index) {
+            Some(index) => {
+                let current = std::mem::replace(
+                    &mut self.store[*index],
+                    NormaliserType::Pipeline(Pipeline { steps: Vec::new() }),
+                );
+                self.store[*index] = current.then(normaliser);
+                Ok(())
+            }
+            None => self.add_normaliser(normaliser, column_name, keys_reference),
+        }
+    }
+
+    /// Fits a normaliser to the values of each column in `kinds`.
+    ///
+    /// # Arguments
+    /// * `data` - The values of each column keyed by column name.
+    /// * `kinds` - The kind of normaliser to fit for each column.
+    /// * `keys_reference` - A reference to the key bindings to extract the index.
+    ///
+    /// # Returns
+    /// A `NormaliserMap` holding the fitted normalisers in the order of the key bindings.
+    pub fn fit_columns(
+        data: &HashMap<String, Vec<f32>>,
+        kinds: &HashMap<String, FitKind>,
+        keys_reference: &KeyBindings,
+    ) -> Result<Self, SurrealError> {
+        if let Some(column) = kinds
+            .keys()
+            .find(|column| !keys_reference.reference.contains_key(*column))
+        {
+            return Err(SurrealError::new(
+                format!("Column {} is not in the key bindings", column),
+                SurrealErrorStatus::NotFound,
+            ));
+        }
+        let mut normaliser_map = NormaliserMap::fresh();
+        for column in &keys_reference.store {
+            let kind = match kinds.get(column) {
+                Some(kind) => kind,
+                None => continue,
+            };
+            let values = data.get(column).ok_or_else(|| {
+                SurrealError::new(
+                    format!("Column {} has no values to fit a normaliser to", column),
+                    SurrealErrorStatus::NotFound,
+                )
+            })?;
+            normaliser_map.add_normaliser(kind.fit(values)?, column.clone(), keys_reference)?;
+        }
+        Ok(normaliser_map)
+    }
+
     /// Gets a normaliser from the map.
     ///
     /// # Arguments
@@ -142,6 +242,39 @@ impl NormaliserMap {
             store_ref,
         })
     }
+    /// Encodes the normalisers as a field of the binary header.
+    ///
+    /// # Returns
+    /// The number of normalisers followed by the column name and definition of each normaliser.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_u32(self.store.len() as u32);
+        for (index, normaliser) in self.store.iter().enumerate() {
+            writer.write_str(&self.store_ref[index])?;
+            writer.write_str(&normaliser.to_string())?;
+        }
+        Ok(writer.finish())
+    }
+
+    /// Decodes the normalisers from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    /// * `keys_reference` - A reference to the key bindings to extract the index.
+    ///
+    /// # Returns
+    /// A `NormaliserMap` containing the normalisers.
+    pub fn from_field(data: &[u8], keys_reference: &KeyBindings) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let count = reader.read_u32()?;
+        let mut normaliser_map = NormaliserMap::fresh();
+        for _ in 0..count {
+            let column_name = reader.read_str()?;
+            let normaliser = NormaliserType::from_definition(&reader.read_str()?)?;
+            normaliser_map.insert(normaliser, column_name, keys_reference)?;
+        }
+        Ok(normaliser_map)
+    }
 }
 
 impl fmt::Display for NormaliserMap {
@@ -252,4 +385,89 @@ pub mod tests {
             _ => panic!("Wrong normaliser type"),
         }
     }
+
+    #[test]
+    fn test_field_round_trip() {
+        let key_bindings = generate_key_bindings();
+        let data = generate_string();
+        let normaliser_map = NormaliserMap::from_string(data, &key_bindings).unwrap();
+
+        let decoded =
+            NormaliserMap::from_field(&normaliser_map.to_field().unwrap(), &key_bindings).unwrap();
+        assert_eq!(decoded, normaliser_map);
+    }
+
+    #[test]
+    fn test_add_normaliser_step() {
+        let key_bindings = generate_key_bindings();
+        let mut normaliser_map =
+            NormaliserMap::from_string(generate_string(), &key_bindings).unwrap();
+
+        normaliser_map
+            .add_normaliser_step(
+                NormaliserType::ZScore(z_score::ZScore {
+                    mean: 0.5,
+                    std_dev: 0.25,
+                }),
+                "a".to_string(),
+                &key_bindings,
+            )
+            .unwrap();
+        normaliser_map
+            .add_normaliser_step(
+                NormaliserType::ZScore(z_score::ZScore {
+                    mean: 0.0,
+                    std_dev: 1.0,
+                }),
+                "d".to_string(),
+                &key_bindings,
+            )
+            .unwrap();
+        assert_eq!(normaliser_map.store.len(), 5);
+        assert_eq!(
+            normaliser_map.to_string(),
+            "a=>linear_scaling(0,1)|z_score(0.5,0.25)//b=>clipping(0,1.5)//c=>log_scaling(10,0)//e=>z_score(0,1)//d=>z_score(0,1)"
+        );
+
+        let normaliser = normaliser_map
+            .get_normaliser("a".to_string(), &key_bindings)
+            .unwrap()
+            .unwrap();
+        assert_eq!(normaliser.normalise(0.75).unwrap(), 1.0);
+        assert_eq!(normaliser.inverse_normalise(1.0).unwrap(), 0.75);
+
+        let decoded =
+            NormaliserMap::from_field(&normaliser_map.to_field().unwrap(), &key_bindings).unwrap();
+        assert_eq!(decoded, normaliser_map);
+        let decoded =
+            NormaliserMap::from_string(normaliser_map.to_string(), &key_bindings).unwrap();
+        assert_eq!(decoded, normaliser_map);
+    }
+
+    #[test]
+    fn test_fit_columns() {
+        let key_bindings = generate_key_bindings();
+        let data = HashMap::from([
+            ("a".to_string(), vec![0.0, 5.0, 10.0]),
+            ("b".to_string(), vec![1.0, 3.0]),
+            ("c".to_string(), vec![9.0]),
+        ]);
+        let kinds = HashMap::from([
+            ("a".to_string(), FitKind::LinearScaling),
+            ("b".to_string(), FitKind::ZScore),
+            ("c".to_string(), FitKind::LogScaling { base: 10.0 }),
+        ]);
+        let normaliser_map = NormaliserMap::fit_columns(&data, &kinds, &key_bindings).unwrap();
+        assert_eq!(
+            normaliser_map.to_string(),
+            "a=>linear_scaling(0,10)//b=>z_score(2,1)//c=>log_scaling(10,0)"
+        );
+
+        let kinds = HashMap::from([("d".to_string(), FitKind::ZScore)]);
+        let error = NormaliserMap::fit_columns(&data, &kinds, &key_bindings).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Column d has no values to fit a normaliser to"
+        );
+    }
 }
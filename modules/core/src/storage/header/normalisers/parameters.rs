@@ -0,0 +1,261 @@
+//! Defines the parsing of a single normaliser definition such as `linear_scaling(0,1)` or `clipping(min=0)`.
+use std::fmt;
+
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// The label and parameters of a single normaliser definition. Parameters can be passed by position such as
+/// `linear_scaling(0,1)`, by name such as `robust_scaling(median=3,iqr=2)`, or a mix of both.
+///
+/// # Fields
+/// * `label` - The label of the normaliser which is used to look it up in the registry.
+/// * `positional` - The parameters passed without a name in the order they were passed.
+/// * `named` - The parameters passed with a name in the order they were passed.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NormaliserParameters {
+    pub label: String,
+    pub positional: Vec<f32>,
+    pub named: Vec<(String, f32)>,
+}
+
+impl NormaliserParameters {
+    /// Constructs parameters for a normaliser with no arguments.
+    ///
+    /// # Arguments
+    /// * `label` - The label of the normaliser.
+    ///
+    /// # Returns
+    /// The parameters with nothing passed.
+    pub fn new(label: &str) -> Self {
+        NormaliserParameters {
+            label: label.to_string(),
+            positional: Vec::new(),
+            named: Vec::new(),
+        }
+    }
+
+    /// Adds a parameter by position.
+    ///
+    /// # Arguments
+    /// * `value` - The value of the parameter.
+    ///
+    /// # Returns
+    /// The parameters with the value appended.
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.positional.push(value);
+        self
+    }
+
+    /// Adds a parameter by name.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the parameter.
+    /// * `value` - The value of the parameter.
+    ///
+    /// # Returns
+    /// The parameters with the named value appended.
+    pub fn with_named(mut self, name: &str, value: f32) -> Self {
+        self.named.push((name.to_string(), value));
+        self
+    }
+
+    /// Parses a definition such as `clipping(min=0, max=1.5)`.
+    ///
+    /// # Arguments
+    /// * `definition` - The definition of a single normaliser.
+    ///
+    /// # Returns
+    /// The label and parameters of the definition.
+    pub fn from_definition(definition: &str) -> Result<Self, SurrealError> {
+        let definition = definition.trim();
+        let malformed = || {
+            SurrealError::new(
+                format!("Malformed normaliser definition: {}", definition),
+                SurrealErrorStatus::BadRequest,
+            )
+        };
+        let (label, arguments) = definition.split_once('(').ok_or_else(malformed)?;
+        let arguments = arguments.strip_suffix(')').ok_or_else(malformed)?;
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(malformed());
+        }
+
+        let mut parameters = NormaliserParameters::new(label);
+        for argument in arguments.split(',').map(str::trim) {
+            if argument.is_empty() {
+                continue;
+            }
+            match argument.split_once('=') {
+                Some((name, value)) => {
+                    let name = name.trim();
+                    if parameters
+                        .named
+                        .iter()
+                        .any(|(existing, _)| existing == name)
+                    {
+                        return Err(SurrealError::new(
+                            format!("Parameter {} is passed more than once to {}", name, label),
+                            SurrealErrorStatus::BadRequest,
+                        ));
+                    }
+                    let value = Self::parse_number(label, value.trim())?;
+                    parameters = parameters.with_named(name, value);
+                }
+                None => {
+                    let value = Self::parse_number(label, argument)?;
+                    parameters = parameters.with_value(value);
+                }
+            }
+        }
+        Ok(parameters)
+    }
+
+    /// Parses the value of a parameter.
+    fn parse_number(label: &str, value: &str) -> Result<f32, SurrealError> {
+        value.parse::<f32>().map_err(|_| {
+            SurrealError::new(
+                format!("Invalid parameter {} for normaliser {}", value, label),
+                SurrealErrorStatus::BadRequest,
+            )
+        })
+    }
+
+    /// Gets a parameter by its name, falling back to its position for definitions that do not name their parameters.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the parameter.
+    /// * `position` - The position of the parameter when it is passed without a name.
+    ///
+    /// # Returns
+    /// The value of the parameter if it was passed.
+    pub fn get(&self, name: &str, position: usize) -> Option<f32> {
+        self.named
+            .iter()
+            .find(|(existing, _)| existing == name)
+            .map(|(_, value)| *value)
+            .or_else(|| self.positional.get(position).copied())
+    }
+
+    /// Gets a parameter that the normaliser cannot be constructed without.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the parameter.
+    /// * `position` - The position of the parameter when it is passed without a name.
+    ///
+    /// # Returns
+    /// The value of the parameter.
+    pub fn require(&self, name: &str, position: usize) -> Result<f32, SurrealError> {
+        self.get(name, position).ok_or_else(|| {
+            SurrealError::new(
+                format!(
+                    "Normaliser {} is missing the parameter {}",
+                    self.label, name
+                ),
+                SurrealErrorStatus::BadRequest,
+            )
+        })
+    }
+
+    /// Checks that every named parameter is one the normaliser accepts and that no more than `names.len()`
+    /// parameters are passed by position.
+    ///
+    /// # Arguments
+    /// * `names` - The names of the parameters the normaliser accepts in positional order.
+    pub fn accept_only(&self, names: &[&str]) -> Result<(), SurrealError> {
+        if let Some((name, _)) = self
+            .named
+            .iter()
+            .find(|(name, _)| !names.contains(&name.as_str()))
+        {
+            return Err(SurrealError::new(
+                format!(
+                    "Normaliser {} does not accept the parameter {}",
+                    self.label, name
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        if self.positional.len() > names.len() {
+            return Err(SurrealError::new(
+                format!(
+                    "Normaliser {} accepts at most {} parameters but {} were passed",
+                    self.label,
+                    names.len(),
+                    self.positional.len()
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for NormaliserParameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arguments = self
+            .positional
+            .iter()
+            .map(|value| value.to_string())
+            .chain(
+                self.named
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value)),
+            )
+            .collect::<Vec<String>>();
+        write!(f, "{}({})", self.label, arguments.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_from_definition() {
+        let parameters = NormaliserParameters::from_definition("linear_scaling(0.0, 1)").unwrap();
+        assert_eq!(parameters.label, "linear_scaling");
+        assert_eq!(parameters.positional, vec![0.0, 1.0]);
+        assert_eq!(parameters.get("max", 1), Some(1.0));
+
+        let parameters =
+            NormaliserParameters::from_definition("robust_scaling(iqr=2, median=-3.5e1)").unwrap();
+        assert_eq!(parameters.require("median", 0).unwrap(), -35.0);
+        assert_eq!(parameters.require("iqr", 1).unwrap(), 2.0);
+        assert_eq!(parameters.to_string(), "robust_scaling(iqr=2,median=-35)");
+
+        let parameters = NormaliserParameters::from_definition("clipping()").unwrap();
+        assert_eq!(parameters.get("min", 0), None);
+        assert_eq!(
+            parameters.require("min", 0).unwrap_err().message,
+            "Normaliser clipping is missing the parameter min"
+        );
+    }
+
+    #[test]
+    fn test_from_definition_errors() {
+        assert_eq!(
+            NormaliserParameters::from_definition("clipping")
+                .unwrap_err()
+                .message,
+            "Malformed normaliser definition: clipping"
+        );
+        assert_eq!(
+            NormaliserParameters::from_definition("clipping(min=a)")
+                .unwrap_err()
+                .message,
+            "Invalid parameter a for normaliser clipping"
+        );
+        assert_eq!(
+            NormaliserParameters::from_definition("clipping(min=1,min=2)")
+                .unwrap_err()
+                .message,
+            "Parameter min is passed more than once to clipping"
+        );
+        let parameters = NormaliserParameters::from_definition("clipping(low=1)").unwrap();
+        assert_eq!(
+            parameters.accept_only(&["min", "max"]).unwrap_err().message,
+            "Normaliser clipping does not accept the parameter low"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! The functionality around an ordered pipeline of normalisers applied to a single column.
+use super::wrapper::NormaliserType;
+use crate::errors::error::SurrealError;
+
+/// An ordered pipeline of normalisers such as clipping, then log scaling, then a z-score.
+///
+/// # Fields
+/// * `steps` - The normalisers in the order they are applied. A step is never a pipeline itself.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pipeline {
+    pub steps: Vec<NormaliserType>,
+}
+
+impl Pipeline {
+    /// Normalises a value by applying every step in order, checking the domain of each step against the value it
+    /// receives.
+    ///
+    /// # Arguments
+    /// * `input` - The value to normalise.
+    ///
+    /// # Returns
+    /// The normalised value.
+    pub fn normalise(&self, input: f32) -> Result<f32, SurrealError> {
+        self.steps
+            .iter()
+            .try_fold(input, |value, step| step.normalise(value))
+    }
+
+    /// Applies the inverse of every step in reverse order.
+    ///
+    /// # Arguments
+    /// * `input` - The value to inverse normalise.
+    ///
+    /// # Returns
+    /// The inverse normalised value.
+    pub fn inverse_normalise(&self, input: f32) -> Result<f32, SurrealError> {
+        self.steps
+            .iter()
+            .rev()
+            .try_fold(input, |value, step| step.inverse_normalise(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::{
+        clipping::Clipping, linear_scaling::LinearScaling, log_scale::LogScaling, z_score::ZScore,
+    };
+    use super::*;
+
+    #[test]
+    fn test_normalise_in_order_and_inverse_in_reverse() {
+        let pipeline = Pipeline {
+            steps: vec![
+                NormaliserType::LinearScaling(LinearScaling {
+                    min: 0.0,
+                    max: 10.0,
+                }),
+                NormaliserType::ZScore(ZScore {
+                    mean: 0.5,
+                    std_dev: 0.25,
+                }),
+            ],
+        };
+        assert_eq!(pipeline.normalise(5.0).unwrap(), 0.0);
+        assert_eq!(pipeline.normalise(7.5).unwrap(), 1.0);
+        assert_eq!(pipeline.inverse_normalise(1.0).unwrap(), 7.5);
+
+        let clipped = Pipeline {
+            steps: vec![
+                NormaliserType::Clipping(Clipping {
+                    min: Some(0.0),
+                    max: Some(10.0),
+                }),
+                NormaliserType::LinearScaling(LinearScaling {
+                    min: 0.0,
+                    max: 10.0,
+                }),
+            ],
+        };
+        assert_eq!(clipped.normalise(20.0).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_domain_is_checked_for_each_step() {
+        let pipeline = Pipeline {
+            steps: vec![
+                NormaliserType::ZScore(ZScore {
+                    mean: 5.0,
+                    std_dev: 1.0,
+                }),
+                NormaliserType::LogScaling(LogScaling {
+                    base: 10.0,
+                    min: 0.0,
+                }),
+            ],
+        };
+        assert_eq!(pipeline.normalise(15.0).unwrap(), 1.0);
+        assert_eq!(
+            pipeline.normalise(4.0).unwrap_err().message,
+            "log_scaling is only defined for values above -0, received -1"
+        );
+    }
+}
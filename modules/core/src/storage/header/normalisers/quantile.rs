@@ -0,0 +1,136 @@
+//! The functionality and parameters around a quantile normaliser.
+use super::fit::{quantile_of_sorted, sorted_values};
+use super::parameters::NormaliserParameters;
+use super::traits::Normaliser;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// A quantile normaliser which maps a value onto `[0, 1]` by interpolating between the values of the column at
+/// evenly spaced quantiles.
+///
+/// # Fields
+/// * `references` - The values of the column at evenly spaced quantiles from the minimum to the maximum.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Quantile {
+    pub references: Vec<f32>,
+}
+
+impl Quantile {
+    /// Constructs the normaliser from at least two non-decreasing parameters passed by position such as
+    /// `quantile(0,2.5,4,10)`.
+    ///
+    /// # Arguments
+    /// * `parameters` - The parameters of the definition.
+    ///
+    /// # Returns
+    /// The quantile normaliser.
+    pub fn from_parameters(parameters: &NormaliserParameters) -> Result<Self, SurrealError> {
+        if let Some((name, _)) = parameters.named.first() {
+            return Err(SurrealError::new(
+                format!("Normaliser quantile does not accept the parameter {}", name),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        let references = parameters.positional.clone();
+        if references.len() < 2 {
+            return Err(SurrealError::new(
+                "Normaliser quantile needs at least two references".to_string(),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        if references.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(SurrealError::new(
+                "The references of quantile must be in ascending order".to_string(),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(Quantile { references })
+    }
+
+    /// Fits the normaliser to the values of a column at evenly spaced quantiles.
+    ///
+    /// # Arguments
+    /// * `values` - The values of the column.
+    /// * `references` - The number of quantiles to keep which must be at least two.
+    ///
+    /// # Returns
+    /// The fitted quantile normaliser.
+    pub fn fit(values: &[f32], references: usize) -> Result<Self, SurrealError> {
+        let sorted = sorted_values("quantile", values)?;
+        let last = references.max(1) - 1;
+        let parameters = (0..references).fold(
+            NormaliserParameters::new("quantile"),
+            |parameters, index| {
+                let quantile = index as f32 / last.max(1) as f32;
+                parameters.with_value(quantile_of_sorted(&sorted, quantile))
+            },
+        );
+        Quantile::from_parameters(&parameters)
+    }
+}
+
+impl Normaliser for Quantile {
+    /// Normalises a value.
+    ///
+    /// # Arguments
+    /// * `input` - The value to normalise.
+    ///
+    /// # Returns
+    /// The quantile of the value, clipped to `[0, 1]`.
+    fn normalise(&self, input: f32) -> f32 {
+        let last = self.references.len() - 1;
+        if input <= self.references[0] {
+            return 0.0;
+        }
+        if input >= self.references[last] {
+            return 1.0;
+        }
+        let upper = self
+            .references
+            .partition_point(|reference| *reference <= input);
+        let low = self.references[upper - 1];
+        let high = self.references[upper];
+        let fraction = (input - low) / (high - low);
+        ((upper - 1) as f32 + fraction) / last as f32
+    }
+
+    /// Applies the inverse of the value for the normaliser.
+    ///
+    /// # Arguments
+    /// * `input` - The quantile to inverse normalise.
+    ///
+    /// # Returns
+    /// The value of the column at the quantile.
+    fn inverse_normalise(&self, input: f32) -> f32 {
+        let last = self.references.len() - 1;
+        let position = input.clamp(0.0, 1.0) * last as f32;
+        let lower = (position.floor() as usize).min(last - 1);
+        let fraction = position - lower as f32;
+        let low = self.references[lower];
+        let high = self.references[lower + 1];
+        low + (high - low) * fraction
+    }
+
+    fn key() -> String {
+        "quantile".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_normalise_and_inverse() {
+        let normaliser = Quantile {
+            references: vec![0.0, 10.0, 100.0],
+        };
+        assert_eq!(normaliser.normalise(-5.0), 0.0);
+        assert_eq!(normaliser.normalise(5.0), 0.25);
+        assert_eq!(normaliser.normalise(55.0), 0.75);
+        assert_eq!(normaliser.normalise(500.0), 1.0);
+        assert_eq!(normaliser.inverse_normalise(0.25), 5.0);
+        assert_eq!(normaliser.inverse_normalise(0.75), 55.0);
+        assert_eq!(normaliser.inverse_normalise(1.0), 100.0);
+    }
+}
@@ -0,0 +1,123 @@
+//! Defines the registry that maps the label of a normaliser definition to its constructor.
+use super::box_cox::BoxCox;
+use super::clipping::Clipping;
+use super::linear_scaling::LinearScaling;
+use super::log_scale::LogScaling;
+use super::parameters::NormaliserParameters;
+use super::quantile::Quantile;
+use super::robust_scaling::RobustScaling;
+use super::wrapper::NormaliserType;
+use super::yeo_johnson::YeoJohnson;
+use super::z_score::ZScore;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// Constructs a normaliser from the parameters of its definition.
+pub type NormaliserConstructor = fn(&NormaliserParameters) -> Result<NormaliserType, SurrealError>;
+
+/// The normalisers that can be constructed from a definition, keyed by their label. A new normaliser only needs an
+/// entry here for its definitions to be parsed.
+const REGISTRY: &[(&str, NormaliserConstructor)] = &[
+    ("linear_scaling", |parameters| {
+        Ok(NormaliserType::LinearScaling(
+            LinearScaling::from_parameters(parameters)?,
+        ))
+    }),
+    ("clipping", |parameters| {
+        Ok(NormaliserType::Clipping(Clipping::from_parameters(
+            parameters,
+        )?))
+    }),
+    ("log_scaling", |parameters| {
+        Ok(NormaliserType::LogScaling(LogScaling::from_parameters(
+            parameters,
+        )?))
+    }),
+    ("z_score", |parameters| {
+        Ok(NormaliserType::ZScore(ZScore::from_parameters(parameters)?))
+    }),
+    ("robust_scaling", |parameters| {
+        Ok(NormaliserType::RobustScaling(
+            RobustScaling::from_parameters(parameters)?,
+        ))
+    }),
+    ("box_cox", |parameters| {
+        Ok(NormaliserType::BoxCox(BoxCox::from_parameters(parameters)?))
+    }),
+    ("yeo_johnson", |parameters| {
+        Ok(NormaliserType::YeoJohnson(YeoJohnson::from_parameters(
+            parameters,
+        )?))
+    }),
+    ("quantile", |parameters| {
+        Ok(NormaliserType::Quantile(Quantile::from_parameters(
+            parameters,
+        )?))
+    }),
+];
+
+/// Looks up the constructor of a normaliser.
+///
+/// # Arguments
+/// * `label` - The label of the normaliser.
+///
+/// # Returns
+/// The constructor if the label is registered.
+pub fn lookup(label: &str) -> Option<NormaliserConstructor> {
+    REGISTRY
+        .iter()
+        .find(|(registered, _)| *registered == label)
+        .map(|(_, constructor)| *constructor)
+}
+
+/// The labels of every registered normaliser.
+///
+/// # Returns
+/// The labels in the order they were registered.
+pub fn labels() -> Vec<&'static str> {
+    REGISTRY.iter().map(|(label, _)| *label).collect()
+}
+
+/// Constructs a normaliser from the parameters of its definition.
+///
+/// # Arguments
+/// * `parameters` - The label and parameters of the definition.
+///
+/// # Returns
+/// The normaliser or an error if the label is not registered or the parameters are invalid.
+pub fn build(parameters: &NormaliserParameters) -> Result<NormaliserType, SurrealError> {
+    match lookup(&parameters.label) {
+        Some(constructor) => constructor(parameters),
+        None => Err(SurrealError::new(
+            format!("Unknown normaliser type: {}", parameters.label),
+            SurrealErrorStatus::BadRequest,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let parameters = NormaliserParameters::from_definition("clipping(max=10)").unwrap();
+        assert_eq!(
+            build(&parameters).unwrap(),
+            NormaliserType::Clipping(Clipping {
+                min: None,
+                max: Some(10.0)
+            })
+        );
+        let parameters = NormaliserParameters::from_definition("unknown(1,2)").unwrap();
+        let error = build(&parameters).unwrap_err();
+        assert_eq!(error.message, "Unknown normaliser type: unknown");
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        let parameters = NormaliserParameters::from_definition("z_score(mean=1)").unwrap();
+        assert_eq!(
+            build(&parameters).unwrap_err().message,
+            "Normaliser z_score is missing the parameter std_dev"
+        );
+        assert_eq!(labels().len(), 8);
+    }
+}
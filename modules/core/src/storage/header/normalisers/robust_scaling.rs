@@ -0,0 +1,106 @@
+//! The functionality and parameters around a robust scaling normaliser.
+use super::fit::{quantile_of_sorted, sorted_values, unit_if_zero};
+use super::parameters::NormaliserParameters;
+use super::traits::Normaliser;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// A robust scaling normaliser which centres on the median and scales by the interquartile range so it is not
+/// skewed by outliers.
+///
+/// # Fields
+/// * `median` - The median of the column.
+/// * `iqr` - The interquartile range of the column.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RobustScaling {
+    pub median: f32,
+    pub iqr: f32,
+}
+
+impl RobustScaling {
+    /// Constructs the normaliser from the parameters `median` and `iqr`.
+    ///
+    /// # Arguments
+    /// * `parameters` - The parameters of the definition.
+    ///
+    /// # Returns
+    /// The robust scaling normaliser.
+    pub fn from_parameters(parameters: &NormaliserParameters) -> Result<Self, SurrealError> {
+        parameters.accept_only(&["median", "iqr"])?;
+        let median = parameters.require("median", 0)?;
+        let iqr = parameters.require("iqr", 1)?;
+        Ok(RobustScaling { median, iqr })
+    }
+
+    /// Fits the normaliser to the median and interquartile range of the values of a column. A column without an
+    /// interquartile range is scaled by one instead, like sklearn's `RobustScaler`.
+    ///
+    /// # Arguments
+    /// * `values` - The values of the column.
+    ///
+    /// # Returns
+    /// The fitted robust scaling normaliser.
+    pub fn fit(values: &[f32]) -> Result<Self, SurrealError> {
+        let sorted = sorted_values("robust_scaling", values)?;
+        let iqr = quantile_of_sorted(&sorted, 0.75) - quantile_of_sorted(&sorted, 0.25);
+        RobustScaling::from_parameters(
+            &NormaliserParameters::new("robust_scaling")
+                .with_named("median", quantile_of_sorted(&sorted, 0.5))
+                .with_named("iqr", unit_if_zero(iqr)),
+        )
+    }
+}
+
+impl Normaliser for RobustScaling {
+    /// Checks that the normaliser does not divide by zero.
+    fn check_parameters(&self) -> Result<(), SurrealError> {
+        if self.iqr == 0.0 {
+            return Err(SurrealError::new(
+                "The iqr of robust_scaling cannot be zero".to_string(),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Normalises a value.
+    ///
+    /// # Arguments
+    /// * `input` - The value to normalise.
+    ///
+    /// # Returns
+    /// The normalised value.
+    fn normalise(&self, input: f32) -> f32 {
+        (input - self.median) / self.iqr
+    }
+
+    /// Applies the inverse of the value for the normaliser.
+    ///
+    /// # Arguments
+    /// * `input` - The value to inverse normalise.
+    ///
+    /// # Returns
+    /// The inverse normalised value.
+    fn inverse_normalise(&self, input: f32) -> f32 {
+        (input * self.iqr) + self.median
+    }
+
+    fn key() -> String {
+        "robust_scaling".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_normalise_and_inverse() {
+        let normaliser = RobustScaling {
+            median: 10.0,
+            iqr: 4.0,
+        };
+        assert_eq!(normaliser.normalise(12.0), 0.5);
+        assert_eq!(normaliser.inverse_normalise(0.5), 12.0);
+    }
+}
@@ -1,4 +1,5 @@
 //! traits for the normalisers module.
+use crate::errors::error::SurrealError;
 
 pub trait Normaliser {
     /// Normalises a value.
@@ -8,6 +9,139 @@ pub trait Normaliser {
         input
     }
 
+    /// Checks that the parameters of the normaliser do not divide by zero. Stored headers are not checked so files
+    /// fitted to a constant column keep loading, instead the check is made where a normaliser is added and before a
+    /// value is normalised.
+    fn check_parameters(&self) -> Result<(), SurrealError> {
+        Ok(())
+    }
+
+    /// Checks that a value is in the domain of the normaliser so it does not normalise to NaN.
+    fn check_domain(&self, _input: f32) -> Result<(), SurrealError> {
+        Ok(())
+    }
+
+    /// Checks that a normalised value is in the domain of the inverse of the normaliser.
+    fn check_inverse_domain(&self, _input: f32) -> Result<(), SurrealError> {
+        Ok(())
+    }
+
     /// Returns the key of the normaliser.
     fn key() -> String;
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::box_cox::BoxCox;
+    use super::super::clipping::Clipping;
+    use super::super::linear_scaling::LinearScaling;
+    use super::super::log_scale::LogScaling;
+    use super::super::quantile::Quantile;
+    use super::super::robust_scaling::RobustScaling;
+    use super::super::yeo_johnson::YeoJohnson;
+    use super::super::z_score::ZScore;
+    use super::*;
+
+    /// A deterministic generator of values so the properties are checked against the same samples on every run.
+    struct Sampler {
+        state: u64,
+    }
+
+    impl Sampler {
+        fn new() -> Self {
+            Sampler {
+                state: 0x2545_f491_4f6c_dd1d,
+            }
+        }
+
+        /// A value drawn uniformly from `[low, high)`.
+        fn between(&mut self, low: f32, high: f32) -> f32 {
+            self.state ^= self.state << 13;
+            self.state ^= self.state >> 7;
+            self.state ^= self.state << 17;
+            let unit = (self.state >> 40) as f32 / (1u64 << 24) as f32;
+            low + (high - low) * unit
+        }
+    }
+
+    /// Asserts that every sampled input in the domain of the normaliser is recovered by its inverse.
+    fn assert_round_trip<N: Normaliser + std::fmt::Debug>(
+        normaliser: &N,
+        sampler: &mut Sampler,
+        low: f32,
+        high: f32,
+    ) {
+        for _ in 0..200 {
+            let input = sampler.between(low, high);
+            assert!(normaliser.check_domain(input).is_ok());
+            let normalised = normaliser.normalise(input);
+            assert!(normaliser.check_inverse_domain(normalised).is_ok());
+            let recovered = normaliser.inverse_normalise(normalised);
+            let tolerance = 1e-3 * input.abs().max(1.0);
+            assert!(
+                (recovered - input).abs() <= tolerance,
+                "{:?} recovered {} from {}",
+                normaliser,
+                recovered,
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_inverse_recovers_input_for_every_normaliser() {
+        let mut sampler = Sampler::new();
+        for _ in 0..20 {
+            let min = sampler.between(-100.0, 100.0);
+            let max = min + sampler.between(1.0, 100.0);
+            assert_round_trip(&LinearScaling { min, max }, &mut sampler, -500.0, 500.0);
+
+            let clipping = Clipping {
+                min: Some(min),
+                max: Some(max),
+            };
+            assert_round_trip(&clipping, &mut sampler, min, max);
+
+            for base in [2.0, std::f32::consts::E, 10.0] {
+                let min = sampler.between(-5.0, 5.0);
+                let log_scaling = LogScaling { base, min };
+                assert_round_trip(&log_scaling, &mut sampler, 0.01 - min, 1000.0);
+            }
+
+            let z_score = ZScore {
+                mean: sampler.between(-50.0, 50.0),
+                std_dev: sampler.between(0.1, 20.0),
+            };
+            assert_round_trip(&z_score, &mut sampler, -500.0, 500.0);
+
+            let robust_scaling = RobustScaling {
+                median: sampler.between(-50.0, 50.0),
+                iqr: sampler.between(0.1, 20.0),
+            };
+            assert_round_trip(&robust_scaling, &mut sampler, -500.0, 500.0);
+
+            let box_cox = BoxCox {
+                lambda: sampler.between(-1.0, 2.0),
+            };
+            assert_round_trip(&box_cox, &mut sampler, 0.1, 50.0);
+
+            let yeo_johnson = YeoJohnson {
+                lambda: sampler.between(-1.0, 3.0),
+            };
+            assert_round_trip(&yeo_johnson, &mut sampler, -50.0, 50.0);
+
+            let mut references = vec![sampler.between(-100.0, 0.0)];
+            for _ in 0..4 {
+                let last = references[references.len() - 1];
+                references.push(last + sampler.between(0.5, 50.0));
+            }
+            let (low, high) = (references[0], references[4]);
+            assert_round_trip(&Quantile { references }, &mut sampler, low, high);
+        }
+        for lambda in [0.0, 2.0] {
+            assert_round_trip(&BoxCox { lambda }, &mut sampler, 0.1, 50.0);
+            assert_round_trip(&YeoJohnson { lambda }, &mut sampler, -50.0, 50.0);
+        }
+    }
+}
@@ -1,9 +1,16 @@
 //! Defines the constructing and storing of normalisers.
+use super::box_cox::BoxCox;
 use super::clipping;
 use super::linear_scaling;
 use super::log_scale;
+use super::parameters::NormaliserParameters;
+use super::pipeline::Pipeline;
+use super::quantile::Quantile;
+use super::registry;
+use super::robust_scaling::RobustScaling;
 use super::traits::Normaliser;
 use super::utils::{extract_label, extract_two_numbers};
+use super::yeo_johnson::YeoJohnson;
 use super::z_score;
 use std::fmt;
 
@@ -17,16 +24,26 @@ use crate::safe_eject_option;
 /// * `Clipping` - A clipping normaliser.
 /// * `LogScaling` - A log scaling normaliser.
 /// * `ZScore` - A z-score normaliser.
-#[derive(Debug, PartialEq)]
+/// * `RobustScaling` - A median and interquartile range normaliser.
+/// * `BoxCox` - A Box-Cox power transform.
+/// * `YeoJohnson` - A Yeo-Johnson power transform.
+/// * `Quantile` - A quantile normaliser.
+/// * `Pipeline` - An ordered pipeline of the other normalisers.
+#[derive(Debug, PartialEq, Clone)]
 pub enum NormaliserType {
     LinearScaling(linear_scaling::LinearScaling),
     Clipping(clipping::Clipping),
     LogScaling(log_scale::LogScaling),
     ZScore(z_score::ZScore),
+    RobustScaling(RobustScaling),
+    BoxCox(BoxCox),
+    YeoJohnson(YeoJohnson),
+    Quantile(Quantile),
+    Pipeline(Pipeline),
 }
 
 impl NormaliserType {
-    /// Constructs a new normaliser.
+    /// Constructs a new normaliser from its label and two parameters passed by position.
     ///
     /// # Arguments
     /// * `label` - The label of the normaliser.
@@ -34,26 +51,12 @@ impl NormaliserType {
     /// * `two` - The second parameter of the normaliser.
     ///
     /// # Returns
-    /// A new normaliser.
-    pub fn new(label: String, one: f32, two: f32) -> Self {
-        match label.as_str() {
-            "linear_scaling" => {
-                NormaliserType::LinearScaling(linear_scaling::LinearScaling { min: one, max: two })
-            }
-            "clipping" => NormaliserType::Clipping(clipping::Clipping {
-                min: Some(one),
-                max: Some(two),
-            }),
-            "log_scaling" => NormaliserType::LogScaling(log_scale::LogScaling {
-                base: one,
-                min: two,
-            }),
-            "z_score" => NormaliserType::ZScore(z_score::ZScore {
-                mean: one,
-                std_dev: two,
-            }),
-            _ => panic!("Invalid normaliser label: {}", label),
-        }
+    /// A new normaliser or an error if the label is not registered.
+    pub fn new(label: String, one: f32, two: f32) -> Result<Self, SurrealError> {
+        let parameters = NormaliserParameters::new(&label)
+            .with_value(one)
+            .with_value(two);
+        registry::build(&parameters)
     }
 
     /// Unpacks a normaliser from a string.
@@ -84,40 +87,127 @@ impl NormaliserType {
     /// # Returns
     /// (normaliser, column name)
     pub fn from_string(data: String) -> Result<(Self, String), SurrealError> {
-        let (label, numbers, column_name) = Self::unpack_normaliser_data(&data)?;
-        let normaliser = match label.as_str() {
-            "linear_scaling" => {
-                let min = numbers[0];
-                let max = numbers[1];
-                NormaliserType::LinearScaling(linear_scaling::LinearScaling { min, max })
-            }
-            "clipping" => {
-                let min = numbers[0];
-                let max = numbers[1];
-                NormaliserType::Clipping(clipping::Clipping {
-                    min: Some(min),
-                    max: Some(max),
-                })
+        let mut normaliser_buffer = data.split("=>");
+        let column_name = safe_eject_option!(normaliser_buffer.next()).to_string();
+        let definition = safe_eject_option!(normaliser_buffer.next());
+        let normaliser = Self::from_definition(definition)?;
+        Ok((normaliser, column_name))
+    }
+
+    /// Constructs a normaliser from its definition without the column name such as `linear_scaling(0,1)`.
+    /// Parameters can also be named such as `clipping(min=0)`, and a pipeline is defined by its steps separated by
+    /// `|` such as `clipping(0,100)|log_scaling(10,1)|z_score(1,0.5)`.
+    ///
+    /// # Arguments
+    /// * `definition` - The definition of the normaliser.
+    ///
+    /// # Returns
+    /// The normaliser described by the definition.
+    pub fn from_definition(definition: &str) -> Result<Self, SurrealError> {
+        if definition.contains('|') {
+            let steps = definition
+                .split('|')
+                .map(Self::from_definition)
+                .collect::<Result<Vec<Self>, SurrealError>>()?;
+            return Ok(NormaliserType::Pipeline(Pipeline { steps }));
+        }
+        let parameters = NormaliserParameters::from_definition(definition)?;
+        registry::build(&parameters)
+    }
+
+    /// The label and parameters the normaliser is defined by. Normalisers that have always taken two parameters
+    /// keep passing them by position so older readers can still parse them.
+    ///
+    /// # Returns
+    /// The parameters of the normaliser or `None` for a pipeline which is defined by its steps.
+    pub fn parameters(&self) -> Option<NormaliserParameters> {
+        let parameters = match self {
+            NormaliserType::LinearScaling(normaliser) => {
+                NormaliserParameters::new("linear_scaling")
+                    .with_value(normaliser.min)
+                    .with_value(normaliser.max)
             }
-            "log_scaling" => {
-                let base = numbers[0];
-                let min = numbers[1];
-                NormaliserType::LogScaling(log_scale::LogScaling { base, min })
+            NormaliserType::Clipping(normaliser) => match (normaliser.min, normaliser.max) {
+                (Some(min), Some(max)) => NormaliserParameters::new("clipping")
+                    .with_value(min)
+                    .with_value(max),
+                (min, max) => {
+                    let mut parameters = NormaliserParameters::new("clipping");
+                    if let Some(min) = min {
+                        parameters = parameters.with_named("min", min);
+                    }
+                    if let Some(max) = max {
+                        parameters = parameters.with_named("max", max);
+                    }
+                    parameters
+                }
+            },
+            NormaliserType::LogScaling(normaliser) => NormaliserParameters::new("log_scaling")
+                .with_value(normaliser.base)
+                .with_value(normaliser.min),
+            NormaliserType::ZScore(normaliser) => NormaliserParameters::new("z_score")
+                .with_value(normaliser.mean)
+                .with_value(normaliser.std_dev),
+            NormaliserType::RobustScaling(normaliser) => {
+                NormaliserParameters::new("robust_scaling")
+                    .with_named("median", normaliser.median)
+                    .with_named("iqr", normaliser.iqr)
             }
-            "z_score" => {
-                let mean = numbers[0];
-                let std_dev = numbers[1];
-                NormaliserType::ZScore(z_score::ZScore { mean, std_dev })
+            NormaliserType::BoxCox(normaliser) => {
+                NormaliserParameters::new("box_cox").with_named("lambda", normaliser.lambda)
             }
-            _ => {
-                let error = SurrealError::new(
-                    format!("Unknown normaliser type: {}", label).to_string(),
-                    SurrealErrorStatus::Unknown,
-                );
-                return Err(error);
+            NormaliserType::YeoJohnson(normaliser) => {
+                NormaliserParameters::new("yeo_johnson").with_named("lambda", normaliser.lambda)
             }
+            NormaliserType::Quantile(normaliser) => normaliser.references.iter().fold(
+                NormaliserParameters::new("quantile"),
+                |parameters, reference| parameters.with_value(*reference),
+            ),
+            NormaliserType::Pipeline(_) => return None,
         };
-        Ok((normaliser, column_name))
+        Some(parameters)
+    }
+
+    /// Chains another normaliser after this one. Pipelines are flattened so a step is never a pipeline itself.
+    ///
+    /// # Arguments
+    /// * `next` - The normaliser applied after this one.
+    ///
+    /// # Returns
+    /// A pipeline of this normaliser followed by `next`.
+    pub fn then(self, next: NormaliserType) -> Self {
+        let mut steps = self.into_steps();
+        steps.extend(next.into_steps());
+        NormaliserType::Pipeline(Pipeline { steps })
+    }
+
+    /// The steps of the normaliser, which is the normaliser itself unless it is a pipeline.
+    fn into_steps(self) -> Vec<NormaliserType> {
+        match self {
+            NormaliserType::Pipeline(pipeline) => pipeline.steps,
+            normaliser => vec![normaliser],
+        }
+    }
+
+    /// Checks that the normaliser, or every step of a pipeline, does not divide by zero.
+    ///
+    /// # Returns
+    /// An error naming the parameters that would divide by zero.
+    pub fn check_parameters(&self) -> Result<(), SurrealError> {
+        match self {
+            NormaliserType::LinearScaling(normaliser) => normaliser.check_parameters(),
+            NormaliserType::Clipping(normaliser) => normaliser.check_parameters(),
+            NormaliserType::LogScaling(normaliser) => normaliser.check_parameters(),
+            NormaliserType::ZScore(normaliser) => normaliser.check_parameters(),
+            NormaliserType::RobustScaling(normaliser) => normaliser.check_parameters(),
+            NormaliserType::BoxCox(normaliser) => normaliser.check_parameters(),
+            NormaliserType::YeoJohnson(normaliser) => normaliser.check_parameters(),
+            NormaliserType::Quantile(normaliser) => normaliser.check_parameters(),
+            NormaliserType::Pipeline(normaliser) => normaliser
+                .steps
+                .iter()
+                .try_for_each(NormaliserType::check_parameters),
+        }
     }
 
     /// Normalises a value.
@@ -126,13 +216,19 @@ impl NormaliserType {
     /// * `value` - The value to normalise.
     ///
     /// # Returns
-    /// The normalised value.
-    pub fn normalise(&self, value: f32) -> f32 {
+    /// The normalised value or an error if the parameters divide by zero or the value is outside of the domain of
+    /// the normaliser.
+    pub fn normalise(&self, value: f32) -> Result<f32, SurrealError> {
         match self {
-            NormaliserType::LinearScaling(normaliser) => normaliser.normalise(value),
-            NormaliserType::Clipping(normaliser) => normaliser.normalise(value),
-            NormaliserType::LogScaling(normaliser) => normaliser.normalise(value),
-            NormaliserType::ZScore(normaliser) => normaliser.normalise(value),
+            NormaliserType::LinearScaling(normaliser) => checked_normalise(normaliser, value),
+            NormaliserType::Clipping(normaliser) => checked_normalise(normaliser, value),
+            NormaliserType::LogScaling(normaliser) => checked_normalise(normaliser, value),
+            NormaliserType::ZScore(normaliser) => checked_normalise(normaliser, value),
+            NormaliserType::RobustScaling(normaliser) => checked_normalise(normaliser, value),
+            NormaliserType::BoxCox(normaliser) => checked_normalise(normaliser, value),
+            NormaliserType::YeoJohnson(normaliser) => checked_normalise(normaliser, value),
+            NormaliserType::Quantile(normaliser) => checked_normalise(normaliser, value),
+            NormaliserType::Pipeline(normaliser) => normaliser.normalise(value),
         }
     }
 
@@ -142,34 +238,48 @@ impl NormaliserType {
     /// * `value` - The value to inverse normalise.
     ///
     /// # Returns
-    /// The inverse normalised value.
-    pub fn inverse_normalise(&self, value: f32) -> f32 {
+    /// The inverse normalised value or an error if the value is outside of the domain of the inverse.
+    pub fn inverse_normalise(&self, value: f32) -> Result<f32, SurrealError> {
         match self {
-            NormaliserType::LinearScaling(normaliser) => normaliser.inverse_normalise(value),
-            NormaliserType::Clipping(normaliser) => normaliser.inverse_normalise(value),
-            NormaliserType::LogScaling(normaliser) => normaliser.inverse_normalise(value),
-            NormaliserType::ZScore(normaliser) => normaliser.inverse_normalise(value),
+            NormaliserType::LinearScaling(normaliser) => checked_inverse(normaliser, value),
+            NormaliserType::Clipping(normaliser) => checked_inverse(normaliser, value),
+            NormaliserType::LogScaling(normaliser) => checked_inverse(normaliser, value),
+            NormaliserType::ZScore(normaliser) => checked_inverse(normaliser, value),
+            NormaliserType::RobustScaling(normaliser) => checked_inverse(normaliser, value),
+            NormaliserType::BoxCox(normaliser) => checked_inverse(normaliser, value),
+            NormaliserType::YeoJohnson(normaliser) => checked_inverse(normaliser, value),
+            NormaliserType::Quantile(normaliser) => checked_inverse(normaliser, value),
+            NormaliserType::Pipeline(normaliser) => normaliser.inverse_normalise(value),
         }
     }
 }
 
+/// Normalises a value once the parameters and the value have been checked against the domain of the normaliser.
+fn checked_normalise<N: Normaliser>(normaliser: &N, value: f32) -> Result<f32, SurrealError> {
+    normaliser.check_parameters()?;
+    normaliser.check_domain(value)?;
+    Ok(normaliser.normalise(value))
+}
+
+/// Inverse normalises a value once it has been checked against the domain of the inverse.
+fn checked_inverse<N: Normaliser>(normaliser: &N, value: f32) -> Result<f32, SurrealError> {
+    normaliser.check_inverse_domain(value)?;
+    Ok(normaliser.inverse_normalise(value))
+}
+
 impl fmt::Display for NormaliserType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            NormaliserType::LinearScaling(normaliser) => {
-                write!(f, "linear_scaling({},{})", normaliser.min, normaliser.max)
-            }
-            NormaliserType::Clipping(normaliser) => {
-                let min = normaliser.min.unwrap_or_default();
-                let max = normaliser.max.unwrap_or_default();
-                write!(f, "clipping({},{})", min, max)
-            }
-            NormaliserType::LogScaling(normaliser) => {
-                write!(f, "log_scaling({},{})", normaliser.base, normaliser.min)
-            }
-            NormaliserType::ZScore(normaliser) => {
-                write!(f, "z_score({},{})", normaliser.mean, normaliser.std_dev)
+        match (self, self.parameters()) {
+            (NormaliserType::Pipeline(pipeline), _) => {
+                let steps = pipeline
+                    .steps
+                    .iter()
+                    .map(|step| step.to_string())
+                    .collect::<Vec<String>>();
+                write!(f, "{}", steps.join("|"))
             }
+            (_, Some(parameters)) => write!(f, "{}", parameters),
+            (_, None) => Ok(()),
         }
     }
 }
@@ -203,4 +313,74 @@ mod tests {
         );
         assert_eq!(column_name, "column_name");
     }
+
+    #[test]
+    fn test_pipeline_definition() {
+        let normaliser = NormaliserType::Clipping(clipping::Clipping {
+            min: Some(0.0),
+            max: Some(100.0),
+        })
+        .then(NormaliserType::LinearScaling(
+            linear_scaling::LinearScaling {
+                min: 0.0,
+                max: 100.0,
+            },
+        ))
+        .then(NormaliserType::ZScore(z_score::ZScore {
+            mean: 0.5,
+            std_dev: 0.25,
+        }));
+        let definition = normaliser.to_string();
+        assert_eq!(
+            definition,
+            "clipping(0,100)|linear_scaling(0,100)|z_score(0.5,0.25)"
+        );
+        assert_eq!(
+            NormaliserType::from_definition(&definition).unwrap(),
+            normaliser
+        );
+        assert_eq!(normaliser.normalise(150.0).unwrap(), 2.0);
+        assert_eq!(normaliser.inverse_normalise(2.0).unwrap(), 100.0);
+
+        let (parsed, column_name) =
+            NormaliserType::from_string(format!("price=>{}", definition)).unwrap();
+        assert_eq!(parsed, normaliser);
+        assert_eq!(column_name, "price");
+    }
+
+    #[test]
+    fn test_named_parameter_definitions() {
+        let normaliser = NormaliserType::from_definition("clipping(min=0)").unwrap();
+        assert_eq!(
+            normaliser,
+            NormaliserType::Clipping(clipping::Clipping {
+                min: Some(0.0),
+                max: None,
+            })
+        );
+        assert_eq!(normaliser.to_string(), "clipping(min=0)");
+        assert_eq!(normaliser.normalise(-1.0).unwrap(), 0.0);
+        assert_eq!(normaliser.normalise(1000.0).unwrap(), 1000.0);
+
+        let normaliser = NormaliserType::from_definition("linear_scaling(max=10, min=0)").unwrap();
+        assert_eq!(normaliser.to_string(), "linear_scaling(0,10)");
+
+        let definition =
+            "robust_scaling(median=3,iqr=2)|box_cox(lambda=0.5)|yeo_johnson(lambda=1.5)|quantile(0,1,4,9)";
+        let normaliser = NormaliserType::from_definition(definition).unwrap();
+        assert_eq!(normaliser.to_string(), definition);
+        assert_eq!(
+            NormaliserType::from_definition(&normaliser.to_string()).unwrap(),
+            normaliser
+        );
+    }
+
+    #[test]
+    fn test_new_does_not_panic_on_unknown_label() {
+        let normaliser = NormaliserType::new("z_score".to_string(), 1.0, 2.0).unwrap();
+        assert_eq!(normaliser.to_string(), "z_score(1,2)");
+        let error = NormaliserType::new("unknown".to_string(), 1.0, 2.0).unwrap_err();
+        assert_eq!(error.message, "Unknown normaliser type: unknown");
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+    }
 }
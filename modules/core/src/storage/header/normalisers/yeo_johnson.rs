@@ -0,0 +1,125 @@
+//! The functionality and parameters around a Yeo-Johnson power transform.
+use super::parameters::NormaliserParameters;
+use super::traits::Normaliser;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+
+/// A Yeo-Johnson power transform which, unlike Box-Cox, supports zero and negative values. Chain a z-score after it
+/// for a standardised power transform.
+///
+/// # Fields
+/// * `lambda` - The power of the transform.
+#[derive(Debug, PartialEq, Clone)]
+pub struct YeoJohnson {
+    pub lambda: f32,
+}
+
+impl YeoJohnson {
+    /// Constructs the normaliser from the parameter `lambda`.
+    ///
+    /// # Arguments
+    /// * `parameters` - The parameters of the definition.
+    ///
+    /// # Returns
+    /// The Yeo-Johnson normaliser.
+    pub fn from_parameters(parameters: &NormaliserParameters) -> Result<Self, SurrealError> {
+        parameters.accept_only(&["lambda"])?;
+        let lambda = parameters.require("lambda", 0)?;
+        Ok(YeoJohnson { lambda })
+    }
+}
+
+impl Normaliser for YeoJohnson {
+    /// Normalises a value.
+    ///
+    /// # Arguments
+    /// * `input` - The value to normalise.
+    ///
+    /// # Returns
+    /// The normalised value.
+    fn normalise(&self, input: f32) -> f32 {
+        let lambda = self.lambda;
+        if input >= 0.0 {
+            if lambda == 0.0 {
+                input.ln_1p()
+            } else {
+                ((input + 1.0).powf(lambda) - 1.0) / lambda
+            }
+        } else if lambda == 2.0 {
+            -(-input).ln_1p()
+        } else {
+            -((1.0 - input).powf(2.0 - lambda) - 1.0) / (2.0 - lambda)
+        }
+    }
+
+    /// Applies the inverse of the value for the normaliser.
+    ///
+    /// # Arguments
+    /// * `input` - The value to inverse normalise.
+    ///
+    /// # Returns
+    /// The inverse normalised value.
+    fn inverse_normalise(&self, input: f32) -> f32 {
+        let lambda = self.lambda;
+        if input >= 0.0 {
+            if lambda == 0.0 {
+                input.exp_m1()
+            } else {
+                (lambda * input + 1.0).powf(1.0 / lambda) - 1.0
+            }
+        } else if lambda == 2.0 {
+            -(-input).exp_m1()
+        } else {
+            1.0 - (1.0 - (2.0 - lambda) * input).powf(1.0 / (2.0 - lambda))
+        }
+    }
+
+    /// Checks that the value is in the range of the transform so the root of the inverse is defined, which only
+    /// restricts a lambda below zero or above two.
+    ///
+    /// # Arguments
+    /// * `input` - The value to check.
+    fn check_inverse_domain(&self, input: f32) -> Result<(), SurrealError> {
+        let lambda = self.lambda;
+        let defined = if input >= 0.0 {
+            lambda == 0.0 || lambda * input + 1.0 > 0.0
+        } else {
+            lambda == 2.0 || 1.0 - (2.0 - lambda) * input > 0.0
+        };
+        if defined {
+            return Ok(());
+        }
+        Err(SurrealError::new(
+            format!(
+                "{} is outside of the range of yeo_johnson with a lambda of {}",
+                input, lambda
+            ),
+            SurrealErrorStatus::BadRequest,
+        ))
+    }
+
+    fn key() -> String {
+        "yeo_johnson".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_normalise_and_inverse() {
+        let normaliser = YeoJohnson { lambda: 0.5 };
+        assert_eq!(normaliser.normalise(3.0), 2.0);
+        assert_eq!(normaliser.inverse_normalise(2.0), 3.0);
+        assert_eq!(normaliser.normalise(-3.0), -14.0 / 3.0);
+
+        for lambda in [0.0, 0.5, 2.0] {
+            let normaliser = YeoJohnson { lambda };
+            for input in [-4.0, -0.5, 0.0, 0.5, 4.0] {
+                let recovered = normaliser.inverse_normalise(normaliser.normalise(input));
+                assert!((recovered - input).abs() < 1e-4);
+            }
+        }
+    }
+}
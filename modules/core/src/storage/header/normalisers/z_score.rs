@@ -1,18 +1,71 @@
 //! The functionality and parameters around a z-score normaliser.
+use super::fit::ZScoreFit;
+use super::parameters::NormaliserParameters;
 use super::traits::Normaliser;
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
 
 /// A z-score normaliser.
 ///
 /// # Fields
 /// * `mean` - The mean of the normaliser.
 /// * `std_dev` - The standard deviation of the normaliser.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct ZScore {
     pub mean: f32,
     pub std_dev: f32,
 }
 
+impl ZScore {
+    /// Constructs the normaliser from the parameters `mean` and `std_dev`.
+    ///
+    /// # Arguments
+    /// * `parameters` - The parameters of the definition.
+    ///
+    /// # Returns
+    /// The z-score normaliser.
+    pub fn from_parameters(parameters: &NormaliserParameters) -> Result<Self, SurrealError> {
+        parameters.accept_only(&["mean", "std_dev"])?;
+        let mean = parameters.require("mean", 0)?;
+        let std_dev = parameters.require("std_dev", 1)?;
+        Ok(ZScore { mean, std_dev })
+    }
+
+    /// Fits the normaliser to the values of a column using the population standard deviation.
+    ///
+    /// # Arguments
+    /// * `values` - The values of the column.
+    ///
+    /// # Returns
+    /// The fitted z-score normaliser.
+    pub fn fit(values: &[f32]) -> Result<Self, SurrealError> {
+        let mut fit = ZScore::online();
+        for value in values {
+            fit.update(*value)?;
+        }
+        fit.finish()
+    }
+
+    /// Constructs an online fit for tables too large to hold in memory.
+    ///
+    /// # Returns
+    /// A fit that has not seen any values.
+    pub fn online() -> ZScoreFit {
+        ZScoreFit::default()
+    }
+}
+
 impl Normaliser for ZScore {
+    /// Checks that the normaliser does not divide by zero.
+    fn check_parameters(&self) -> Result<(), SurrealError> {
+        if self.std_dev == 0.0 {
+            return Err(SurrealError::new(
+                "The std_dev of z_score cannot be zero".to_string(),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        Ok(())
+    }
+
     /// Normalises a value.
     ///
     /// # Arguments
@@ -1,7 +1,9 @@
-//! Defines the origin of the model in the file.
+//! Defines the origin and provenance of the model in the file.
 use crate::errors::error::{SurrealError, SurrealErrorStatus};
 use std::fmt;
 
+use super::column::parse_timestamp;
+use super::encoding::{PayloadReader, PayloadWriter};
 use super::string_value::StringValue;
 
 const LOCAL: &str = "local";
@@ -13,11 +15,13 @@ const NONE: &str = "";
 /// # Fields
 /// * `Local` - The model was created locally.
 /// * `SurrealDb` - The model was created in the surreal database.
+/// * `Uri` - The model was created in another system such as `mlflow://runs/42` or `s3://models/house`.
 /// * `None` - The model has no origin
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum OriginValue {
     Local(StringValue),
     SurrealDb(StringValue),
+    Uri(StringValue),
     None(StringValue),
 }
 
@@ -33,7 +37,7 @@ impl OriginValue {
     /// Create a `OriginValue` from a string.
     ///
     /// # Arguments
-    /// * `origin` - The origin as a string.
+    /// * `origin` - The origin as a string which is `local`, `surreal_db` or a URI of the source system.
     ///
     /// # Returns
     /// A new `OriginValue`.
@@ -42,33 +46,154 @@ impl OriginValue {
             LOCAL => Ok(OriginValue::Local(StringValue::from_string(origin))),
             SURREAL_DB => Ok(OriginValue::SurrealDb(StringValue::from_string(origin))),
             NONE => Ok(OriginValue::None(StringValue::from_string(origin))),
+            _ if Self::is_uri(&origin) => Ok(OriginValue::Uri(StringValue::from_string(origin))),
             _ => Err(SurrealError::new(
                 format!("invalid origin: {}", origin),
                 SurrealErrorStatus::BadRequest,
             )),
         }
     }
+
+    /// Checks if the origin is a URI with a scheme such as `https:` or `mlflow:` followed by a location.
+    fn is_uri(origin: &str) -> bool {
+        match origin.split_once(':') {
+            Some((scheme, location)) => {
+                scheme.starts_with(|character: char| character.is_ascii_alphabetic())
+                    && scheme.chars().all(|character| {
+                        character.is_ascii_alphanumeric() || matches!(character, '+' | '-' | '.')
+                    })
+                    && !location.is_empty()
+                    && !location.contains(char::is_whitespace)
+            }
+            None => false,
+        }
+    }
 }
 
 impl fmt::Display for OriginValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            OriginValue::Local(val) | OriginValue::SurrealDb(val) | OriginValue::None(val) => {
-                write!(f, "{}", val)
+            OriginValue::Local(val)
+            | OriginValue::SurrealDb(val)
+            | OriginValue::Uri(val)
+            | OriginValue::None(val) => write!(f, "{}", val),
+        }
+    }
+}
+
+/// Records how the model was trained so models can be linked into a lineage graph.
+///
+/// # Fields
+/// * `run_id` - The identifier of the training run in the source system.
+/// * `commit` - The git commit of the training code.
+/// * `parent_digest` - The hex encoded SHA-256 digest of the model this model was fine-tuned or retrained from.
+/// * `trained_at` - When the model was trained as an RFC 3339 timestamp such as `2024-01-31T12:00:00Z`.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Provenance {
+    pub run_id: Option<String>,
+    pub commit: Option<String>,
+    pub parent_digest: Option<String>,
+    pub trained_at: Option<String>,
+}
+
+impl Provenance {
+    /// Creates a new `Provenance` with nothing recorded.
+    ///
+    /// # Returns
+    /// A new empty `Provenance`.
+    pub fn fresh() -> Self {
+        Provenance::default()
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        *self == Provenance::fresh()
+    }
+
+    /// Sets the parent digest from the raw bytes of a digest such as the one returned by `SurMlFile::digest`.
+    ///
+    /// # Arguments
+    /// * `digest` - The SHA-256 digest of the parent model.
+    ///
+    /// # Returns
+    /// The provenance with the parent digest hex encoded.
+    pub fn with_parent_digest(mut self, digest: &[u8]) -> Self {
+        self.parent_digest = Some(digest.iter().map(|byte| format!("{:02x}", byte)).collect());
+        self
+    }
+
+    /// The time the model was trained.
+    ///
+    /// # Returns
+    /// The seconds since the Unix epoch if a time was recorded.
+    pub fn trained_at_seconds(&self) -> Option<f64> {
+        self.trained_at.as_deref().and_then(parse_timestamp)
+    }
+
+    /// Checks the format of the commit, parent digest and timestamp.
+    pub fn check(&self) -> Result<(), SurrealError> {
+        let is_hex = |value: &str| value.bytes().all(|byte| byte.is_ascii_hexdigit());
+        if let Some(commit) = &self.commit {
+            if !(7..=64).contains(&commit.len()) || !is_hex(commit) {
+                return Err(SurrealError::new(
+                    format!("Invalid git commit: {}", commit),
+                    SurrealErrorStatus::BadRequest,
+                ));
+            }
+        }
+        if let Some(digest) = &self.parent_digest {
+            if digest.len() != 64 || !is_hex(digest) {
+                return Err(SurrealError::new(
+                    format!(
+                        "Parent digest must be a hex encoded SHA-256 digest, received {}",
+                        digest
+                    ),
+                    SurrealErrorStatus::BadRequest,
+                ));
+            }
+        }
+        if let Some(trained_at) = &self.trained_at {
+            if parse_timestamp(trained_at).is_none() {
+                return Err(SurrealError::new(
+                    format!("Invalid training timestamp: {}", trained_at),
+                    SurrealErrorStatus::BadRequest,
+                ));
             }
         }
+        Ok(())
+    }
+
+    /// Writes the provenance to a payload.
+    fn write(&self, writer: &mut PayloadWriter) -> Result<(), SurrealError> {
+        writer.write_optional_str(self.run_id.as_deref())?;
+        writer.write_optional_str(self.commit.as_deref())?;
+        writer.write_optional_str(self.parent_digest.as_deref())?;
+        writer.write_optional_str(self.trained_at.as_deref())?;
+        Ok(())
+    }
+
+    /// Reads a provenance written by `Provenance::write`.
+    fn read(reader: &mut PayloadReader) -> Result<Self, SurrealError> {
+        Ok(Provenance {
+            run_id: reader.read_optional_str()?,
+            commit: reader.read_optional_str()?,
+            parent_digest: reader.read_optional_str()?,
+            trained_at: reader.read_optional_str()?,
+        })
     }
 }
 
 /// Defines the origin of the model in the file header.
 ///
 /// # Fields
-/// * `origin` - The origin of the model.
+/// * `origin` - The origin of the model which is the system it was created in.
 /// * `author` - The author of the model.
-#[derive(Debug, PartialEq)]
+/// * `provenance` - How the model was trained such as the run, commit and parent model.
+#[derive(Debug, PartialEq, Clone)]
 pub struct Origin {
     pub origin: OriginValue,
     pub author: StringValue,
+    pub provenance: Provenance,
 }
 
 impl Origin {
@@ -80,6 +205,7 @@ impl Origin {
         Origin {
             origin: OriginValue::fresh(),
             author: StringValue::fresh(),
+            provenance: Provenance::fresh(),
         }
     }
 
@@ -94,11 +220,22 @@ impl Origin {
     /// Adds an origin to the origin struct.
     ///
     /// # Arguments
+    /// * `origin` - The origin to be added which is `local`, `surreal_db` or a URI of the source system.
     pub fn add_origin(&mut self, origin: String) -> Result<(), SurrealError> {
         self.origin = OriginValue::from_string(origin)?;
         Ok(())
     }
 
+    /// Replaces the provenance of the origin struct.
+    ///
+    /// # Arguments
+    /// * `provenance` - How the model was trained.
+    pub fn add_provenance(&mut self, provenance: Provenance) -> Result<(), SurrealError> {
+        provenance.check()?;
+        self.provenance = provenance;
+        Ok(())
+    }
+
     /// Creates a new origin from a string.
     ///
     /// # Arguments
@@ -110,12 +247,53 @@ impl Origin {
         if origin == *"" {
             return Ok(Origin::fresh());
         }
-        let mut split = origin.split("=>");
-        let author = split.next().unwrap().to_string();
-        let origin = split.next().unwrap().to_string();
+        let (author, origin_value) = origin.split_once("=>").ok_or_else(|| {
+            SurrealError::new(
+                format!("Origin {} must be written as author=>origin", origin),
+                SurrealErrorStatus::BadRequest,
+            )
+        })?;
+        Ok(Origin {
+            origin: OriginValue::from_string(origin_value.to_string())?,
+            author: StringValue::from_string(author.to_string()),
+            provenance: Provenance::fresh(),
+        })
+    }
+
+    /// Encodes the origin as a field of the binary header.
+    ///
+    /// # Returns
+    /// The author followed by the origin as length-prefixed strings, followed by the provenance if there is one.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_str(&self.author.to_string())?;
+        writer.write_str(&self.origin.to_string())?;
+        // older readers stop after the origin so the provenance can be appended to the same field
+        if !self.provenance.is_empty() {
+            self.provenance.write(&mut writer)?;
+        }
+        Ok(writer.finish())
+    }
+
+    /// Decodes the origin from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    ///
+    /// # Returns
+    /// The origin decoded from the field.
+    pub fn from_field(data: &[u8]) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let author = reader.read_str()?;
+        let origin = reader.read_str()?;
+        let provenance = match reader.is_empty() {
+            true => Provenance::fresh(),
+            false => Provenance::read(&mut reader)?,
+        };
         Ok(Origin {
             origin: OriginValue::from_string(origin)?,
             author: StringValue::from_string(author),
+            provenance,
         })
     }
 }
@@ -146,6 +324,7 @@ mod tests {
             Origin {
                 origin: OriginValue::fresh(),
                 author: StringValue::fresh(),
+                provenance: Provenance::fresh(),
             }
         );
     }
@@ -155,6 +334,7 @@ mod tests {
         let origin = Origin {
             origin: OriginValue::from_string("local".to_string()).unwrap(),
             author: StringValue::from_string("author".to_string()),
+            provenance: Provenance::fresh(),
         };
         assert_eq!(origin.to_string(), "author=>local".to_string());
 
@@ -170,6 +350,7 @@ mod tests {
             Origin {
                 origin: OriginValue::from_string("local".to_string()).unwrap(),
                 author: StringValue::from_string("author".to_string()),
+                provenance: Provenance::fresh(),
             }
         );
 
@@ -177,5 +358,82 @@ mod tests {
 
         assert_eq!(None, origin.author.value);
         assert_eq!("local".to_string(), origin.origin.to_string());
+
+        let origin = Origin::from_string("author=>mlflow://runs/42".to_string()).unwrap();
+        assert!(matches!(origin.origin, OriginValue::Uri(_)));
+        assert_eq!(origin.to_string(), "author=>mlflow://runs/42");
+    }
+
+    #[test]
+    fn test_from_string_errors() {
+        assert_eq!(
+            Origin::from_string("author".to_string())
+                .unwrap_err()
+                .message,
+            "Origin author must be written as author=>origin"
+        );
+        for origin in ["elsewhere", "1http://a", "https:", "https://a b"] {
+            assert!(
+                OriginValue::from_string(origin.to_string()).is_err(),
+                "{} should not parse",
+                origin
+            );
+        }
+    }
+
+    #[test]
+    fn test_provenance() {
+        let mut origin = Origin::fresh();
+        let provenance = Provenance {
+            run_id: Some("run-7".to_string()),
+            commit: Some("9edbc98".to_string()),
+            parent_digest: None,
+            trained_at: Some("2024-01-31T12:00:00Z".to_string()),
+        }
+        .with_parent_digest(&[0xab; 32]);
+        origin.add_provenance(provenance.clone()).unwrap();
+        assert_eq!(origin.provenance.parent_digest, Some("ab".repeat(32)));
+        assert_eq!(
+            origin.provenance.trained_at_seconds(),
+            Some(1_706_702_400.0)
+        );
+
+        for invalid in [
+            Provenance {
+                commit: Some("main".to_string()),
+                ..Provenance::fresh()
+            },
+            Provenance {
+                parent_digest: Some("abc".to_string()),
+                ..Provenance::fresh()
+            },
+            Provenance {
+                trained_at: Some("yesterday".to_string()),
+                ..Provenance::fresh()
+            },
+        ] {
+            assert!(origin.add_provenance(invalid).is_err());
+        }
+        assert_eq!(origin.provenance, provenance);
+    }
+
+    #[test]
+    fn test_field_round_trip() {
+        let mut origin = Origin::fresh();
+        origin.add_author("first=>last".to_string());
+        origin.add_origin("local".to_string()).unwrap();
+
+        let decoded = Origin::from_field(&origin.to_field().unwrap()).unwrap();
+        assert_eq!(decoded, origin);
+
+        origin.add_origin("s3://models/house".to_string()).unwrap();
+        origin
+            .add_provenance(Provenance {
+                run_id: Some("run-7".to_string()),
+                ..Provenance::fresh()
+            })
+            .unwrap();
+        let decoded = Origin::from_field(&origin.to_field().unwrap()).unwrap();
+        assert_eq!(decoded, origin);
     }
 }
@@ -1,4 +1,5 @@
-//! Defines the struct housing data around the outputs of the model.
+//! Defines the structs housing data around the outputs of the model.
+use super::encoding::{PayloadReader, PayloadWriter};
 use super::normalisers::wrapper::NormaliserType;
 use crate::{
     errors::error::{SurrealError, SurrealErrorStatus},
@@ -11,7 +12,7 @@ use std::fmt;
 /// # Fields
 /// * `name` - The name of the output.
 /// * `normaliser` - The normaliser to be applied to the output if there is one.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Output {
     pub name: Option<String>,
     pub normaliser: Option<NormaliserType>,
@@ -48,6 +49,18 @@ impl Output {
         self.normaliser = Some(normaliser);
     }
 
+    /// Appends a normaliser to the pipeline of the output. The outputs of the model are passed through the inverse of
+    /// the pipeline in reverse order.
+    ///
+    /// # Arguments
+    /// * `normaliser` - The normaliser to append.
+    pub fn add_normaliser_step(&mut self, normaliser: NormaliserType) {
+        self.normaliser = Some(match self.normaliser.take() {
+            Some(current) => current.then(normaliser),
+            None => normaliser,
+        });
+    }
+
     /// Converts a string to an instance of the Output struct.
     ///
     /// # Arguments
@@ -70,12 +83,213 @@ impl Output {
         let normaliser = safe_eject_option!(buffer.next());
         let normaliser = match normaliser {
             "none" => None,
-            _ => Some(NormaliserType::from_string(data).unwrap().0),
+            _ => Some(NormaliserType::from_definition(normaliser)?),
+        };
+        Ok(Output { name, normaliser })
+    }
+
+    /// Encodes the output as a field of the binary header.
+    ///
+    /// # Returns
+    /// The optional name followed by the optional normaliser definition.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_optional_str(self.name.as_deref())?;
+        writer.write_optional_str(self.normaliser.as_ref().map(|n| n.to_string()).as_deref())?;
+        Ok(writer.finish())
+    }
+
+    /// Decodes the output from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    ///
+    /// # Returns
+    /// The output decoded from the field.
+    pub fn from_field(data: &[u8]) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let name = reader.read_optional_str()?;
+        let normaliser = match reader.read_optional_str()? {
+            Some(definition) => Some(NormaliserType::from_definition(&definition)?),
+            None => None,
         };
         Ok(Output { name, normaliser })
     }
 }
 
+/// The outputs of a model that returns several values such as the price and the time on the market, each with its
+/// own name and normaliser. A model with a single output, or one returning a vector such as class probabilities,
+/// has at most one output whose normaliser is applied to every value.
+///
+/// # Fields
+/// * `store` - The outputs in the order of the values the model returns.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Outputs {
+    pub store: Vec<Output>,
+}
+
+impl Outputs {
+    /// Creates a new `Outputs` with no outputs.
+    ///
+    /// # Returns
+    /// A new `Outputs` with no outputs.
+    pub fn fresh() -> Self {
+        Outputs { store: Vec::new() }
+    }
+
+    /// Returns `true` if no outputs are declared.
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    /// Gets the first output which is the only output of a model with a single output.
+    ///
+    /// # Returns
+    /// The first output if there is one.
+    pub fn first(&self) -> Option<&Output> {
+        self.store.first()
+    }
+
+    /// Gets the first output, declaring an unnamed one if there are no outputs yet.
+    ///
+    /// # Returns
+    /// A mutable reference to the first output.
+    pub fn first_mut(&mut self) -> &mut Output {
+        if self.store.is_empty() {
+            self.store.push(Output::fresh());
+        }
+        &mut self.store[0]
+    }
+
+    /// Gets an output by its name.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the output.
+    ///
+    /// # Returns
+    /// The output if it is declared.
+    pub fn get(&self, name: &str) -> Option<&Output> {
+        self.store
+            .iter()
+            .find(|output| output.name.as_deref() == Some(name))
+    }
+
+    /// Gets an output by its name to change its normaliser.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the output.
+    ///
+    /// # Returns
+    /// The output if it is declared.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Output> {
+        self.store
+            .iter_mut()
+            .find(|output| output.name.as_deref() == Some(name))
+    }
+
+    /// Declares an output after the outputs already declared, replacing the normaliser of an output with the same
+    /// name. A lone unnamed output, such as one given a normaliser before it was named, is named instead.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the output.
+    /// * `normaliser` - The normaliser to be applied to the output.
+    pub fn add(&mut self, name: String, normaliser: Option<NormaliserType>) {
+        if let Some(output) = self.get_mut(&name) {
+            output.normaliser = normaliser;
+            return;
+        }
+        if let [output] = self.store.as_mut_slice() {
+            if output.name.is_none() {
+                output.name = Some(name);
+                output.normaliser = normaliser;
+                return;
+            }
+        }
+        self.store.push(Output {
+            name: Some(name),
+            normaliser,
+        });
+    }
+
+    /// The name of each value the model returns. With several declared outputs the model has to return a value for
+    /// each of them. Otherwise the values are named after the single output, suffixed by their index if there are
+    /// several of them, and named `output` if the output has no name.
+    ///
+    /// # Arguments
+    /// * `count` - The number of values the model returned.
+    ///
+    /// # Returns
+    /// The names of the values in the order the model returned them.
+    pub fn names(&self, count: usize) -> Result<Vec<String>, SurrealError> {
+        if self.store.len() > 1 {
+            if count != self.store.len() {
+                return Err(SurrealError::new(
+                    format!(
+                        "The model returned {} values but the header declares {} outputs",
+                        count,
+                        self.store.len()
+                    ),
+                    SurrealErrorStatus::BadRequest,
+                ));
+            }
+            let names = self
+                .store
+                .iter()
+                .enumerate()
+                .map(|(index, output)| {
+                    output
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| format!("output_{}", index))
+                })
+                .collect();
+            return Ok(names);
+        }
+        let name = self
+            .first()
+            .and_then(|output| output.name.clone())
+            .unwrap_or_else(|| "output".to_string());
+        if count == 1 {
+            return Ok(vec![name]);
+        }
+        Ok((0..count)
+            .map(|index| format!("{}_{}", name, index))
+            .collect())
+    }
+
+    /// Encodes the outputs as a field of the binary header.
+    ///
+    /// # Returns
+    /// The number of outputs followed by the encoded field of each output.
+    pub fn to_field(&self) -> Result<Vec<u8>, SurrealError> {
+        let mut writer = PayloadWriter::new();
+        writer.write_u32(self.store.len() as u32);
+        for output in &self.store {
+            writer.write_bytes(&output.to_field()?)?;
+        }
+        Ok(writer.finish())
+    }
+
+    /// Decodes the outputs from a field of the binary header.
+    ///
+    /// # Arguments
+    /// * `data` - The payload of the field.
+    ///
+    /// # Returns
+    /// The outputs decoded from the field.
+    pub fn from_field(data: &[u8]) -> Result<Self, SurrealError> {
+        let mut reader = PayloadReader::new(data);
+        let count = reader.read_u32()?;
+        let mut outputs = Outputs::fresh();
+        for _ in 0..count {
+            outputs
+                .store
+                .push(Output::from_field(reader.read_bytes()?)?);
+        }
+        Ok(outputs)
+    }
+}
+
 impl fmt::Display for Output {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.name.is_none() && self.normaliser.is_none() {
@@ -141,6 +355,12 @@ pub mod tests {
         assert!(output.normaliser.is_none());
     }
 
+    #[test]
+    fn test_from_string_with_malformed_normaliser() {
+        let data = "test=>linear_scaling(0".to_string();
+        assert!(Output::from_string(data).is_err());
+    }
+
     #[test]
     fn test_from_string_with_empty_string() {
         let data = "".to_string();
@@ -155,4 +375,64 @@ pub mod tests {
         let output = Output::fresh();
         assert_eq!(output.to_string(), "");
     }
+
+    #[test]
+    fn test_field_round_trip() {
+        let mut output = Output::new("price=>none".to_string());
+        output.add_normaliser(NormaliserType::from_definition("z_score(1,2)").unwrap());
+
+        let decoded = Output::from_field(&output.to_field().unwrap()).unwrap();
+        assert_eq!(decoded, output);
+
+        let decoded = Output::from_field(&Output::fresh().to_field().unwrap()).unwrap();
+        assert_eq!(decoded, Output::fresh());
+    }
+
+    #[test]
+    fn test_add_normaliser_step() {
+        let mut output = Output::new("price".to_string());
+        output.add_normaliser_step(NormaliserType::from_definition("log_scaling(10,0)").unwrap());
+        output.add_normaliser_step(NormaliserType::from_definition("z_score(1,2)").unwrap());
+        assert_eq!(output.to_string(), "price=>log_scaling(10,0)|z_score(1,2)");
+
+        let decoded = Output::from_field(&output.to_field().unwrap()).unwrap();
+        assert_eq!(decoded, output);
+        let decoded = Output::from_string(output.to_string()).unwrap();
+        assert_eq!(decoded, output);
+    }
+
+    #[test]
+    fn test_outputs() {
+        let mut outputs = Outputs::fresh();
+        outputs
+            .first_mut()
+            .add_normaliser(NormaliserType::from_definition("z_score(1,2)").unwrap());
+        outputs.add("price".to_string(), None);
+        assert_eq!(outputs.store.len(), 1);
+        assert_eq!(outputs.names(1).unwrap(), vec!["price"]);
+        assert_eq!(outputs.names(2).unwrap(), vec!["price_0", "price_1"]);
+
+        outputs.add(
+            "days_on_market".to_string(),
+            Some(NormaliserType::from_definition("log_scaling(10,1)").unwrap()),
+        );
+        outputs.add(
+            "price".to_string(),
+            Some(NormaliserType::from_definition("z_score(1,2)").unwrap()),
+        );
+        assert_eq!(outputs.store.len(), 2);
+        assert_eq!(
+            outputs.get("price").unwrap().to_string(),
+            "price=>z_score(1,2)"
+        );
+        assert_eq!(outputs.names(2).unwrap(), vec!["price", "days_on_market"]);
+        assert_eq!(
+            outputs.names(3).unwrap_err().message,
+            "The model returned 3 values but the header declares 2 outputs"
+        );
+
+        let decoded = Outputs::from_field(&outputs.to_field().unwrap()).unwrap();
+        assert_eq!(decoded, outputs);
+        assert_eq!(Outputs::fresh().names(1).unwrap(), vec!["output"]);
+    }
 }
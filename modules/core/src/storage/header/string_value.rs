@@ -5,7 +5,7 @@ use std::fmt;
 ///
 /// # Fields
 /// * `value` - The value of the string.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct StringValue {
     pub value: Option<String>,
 }
@@ -1,21 +1,109 @@
 //! Defines the process of managing the version of the `surml` file in the file.
-use crate::{
-    errors::error::{SurrealError, SurrealErrorStatus},
-    safe_eject, safe_eject_option,
-};
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use std::cmp::Ordering;
 use std::fmt;
 
-/// The `Version` struct represents the version of the `surml` file.
+/// A dot separated identifier of a pre-release such as `rc` or `1` in `1.2.0-rc.1`.
+///
+/// # Arguments
+/// * `Numeric` - An identifier made of digits which is compared numerically.
+/// * `AlphaNumeric` - Any other identifier which is compared lexically.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl Identifier {
+    /// Parses a single pre-release identifier.
+    ///
+    /// # Arguments
+    /// * `data` - The identifier.
+    ///
+    /// # Returns
+    /// The identifier or an error if it is empty, has a leading zero or holds invalid characters.
+    fn from_str(data: &str) -> Result<Self, SurrealError> {
+        check_identifier(data)?;
+        if data.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Ok(Identifier::Numeric(parse_number(data)?));
+        }
+        Ok(Identifier::AlphaNumeric(data.to_string()))
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Identifier {
+    /// Numeric identifiers always have a lower precedence than alphanumeric identifiers.
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(a), Identifier::Numeric(b)) => a.cmp(b),
+            (Identifier::Numeric(_), Identifier::AlphaNumeric(_)) => Ordering::Less,
+            (Identifier::AlphaNumeric(_), Identifier::Numeric(_)) => Ordering::Greater,
+            (Identifier::AlphaNumeric(a), Identifier::AlphaNumeric(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(number) => write!(f, "{}", number),
+            Identifier::AlphaNumeric(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Checks that an identifier is not empty and only holds ASCII alphanumerics and hyphens.
+fn check_identifier(data: &str) -> Result<(), SurrealError> {
+    if data.is_empty()
+        || !data
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+    {
+        return Err(SurrealError::new(
+            format!("Invalid version identifier: {:?}", data),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+    Ok(())
+}
+
+/// Parses a numeric part of a version which cannot have a leading zero.
+fn parse_number(data: &str) -> Result<u64, SurrealError> {
+    if data.len() > 1 && data.starts_with('0') {
+        return Err(SurrealError::new(
+            format!("Version number {} has a leading zero", data),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+    data.parse::<u64>().map_err(|_| {
+        SurrealError::new(
+            format!("Invalid version number: {:?}", data),
+            SurrealErrorStatus::BadRequest,
+        )
+    })
+}
+
+/// The `Version` struct represents the semantic version of the `surml` file such as `1.2.0-rc.1+build.5`.
 ///
 /// # Fields
-/// * `one` - The first number in the version.
-/// * `two` - The second number in the version.
-/// * `three` - The third number in the version.
-#[derive(Debug, PartialEq)]
+/// * `major` - Incremented for changes that break the callers of the model.
+/// * `minor` - Incremented for changes that callers of the model can adopt without changes.
+/// * `patch` - Incremented for fixes such as retraining on more data.
+/// * `pre` - The pre-release identifiers which give the version a lower precedence than its release.
+/// * `build` - The build metadata which is ignored when checking precedence.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Version {
-    pub one: u8,
-    pub two: u8,
-    pub three: u8,
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Vec<Identifier>,
+    pub build: Vec<String>,
 }
 
 impl Version {
@@ -24,14 +112,30 @@ impl Version {
     /// # Returns
     /// A new `Version` struct with all zeros.
     pub fn fresh() -> Self {
+        Version::new(0, 0, 0)
+    }
+
+    /// Creates a new release `Version`.
+    ///
+    /// # Arguments
+    /// * `major` - The major version.
+    /// * `minor` - The minor version.
+    /// * `patch` - The patch version.
+    ///
+    /// # Returns
+    /// A new `Version` without pre-release identifiers or build metadata.
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
         Version {
-            one: 0,
-            two: 0,
-            three: 0,
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+            build: Vec::new(),
         }
     }
 
-    /// Creates a new `Version` struct from a string.
+    /// Creates a new `Version` struct from a string such as `1.2.0-rc.1+build.5`. An empty string is the unset
+    /// version written by older headers.
     ///
     /// # Arguments
     /// * `version` - The version as a string.
@@ -39,42 +143,171 @@ impl Version {
     /// # Returns
     /// A new `Version` struct.
     pub fn from_string(version: String) -> Result<Self, SurrealError> {
-        if version == *"" {
+        if version.is_empty() {
             return Ok(Version::fresh());
         }
-        let mut split = version.split(".");
-        let one_str = safe_eject_option!(split.next());
-        let two_str = safe_eject_option!(split.next());
-        let three_str = safe_eject_option!(split.next());
+        let (rest, build) = match version.split_once('+') {
+            Some((rest, build)) => (rest, Some(build)),
+            None => (version.as_str(), None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+
+        let numbers = core.split('.').collect::<Vec<&str>>();
+        if numbers.len() != 3 {
+            return Err(SurrealError::new(
+                format!(
+                    "Version {} must have a major, minor and patch number",
+                    version
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        let mut parsed = Version::new(
+            parse_number(numbers[0])?,
+            parse_number(numbers[1])?,
+            parse_number(numbers[2])?,
+        );
+        if let Some(pre) = pre {
+            parsed.pre = pre
+                .split('.')
+                .map(Identifier::from_str)
+                .collect::<Result<Vec<Identifier>, SurrealError>>()?;
+        }
+        if let Some(build) = build {
+            for identifier in build.split('.') {
+                check_identifier(identifier)?;
+                parsed.build.push(identifier.to_string());
+            }
+        }
+        Ok(parsed)
+    }
 
-        Ok(Version {
-            one: safe_eject!(one_str.parse::<u8>(), SurrealErrorStatus::BadRequest),
-            two: safe_eject!(two_str.parse::<u8>(), SurrealErrorStatus::BadRequest),
-            three: safe_eject!(three_str.parse::<u8>(), SurrealErrorStatus::BadRequest),
-        })
+    /// Returns `true` if the version has pre-release identifiers.
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
     }
 
-    /// Increments the version by one.
+    /// Increments the patch version, releasing a pre-release of the same version.
     pub fn increment(&mut self) {
-        self.three += 1;
-        if self.three == 10 {
-            self.three = 0;
-            self.two += 1;
-            if self.two == 10 {
-                self.two = 0;
-                self.one += 1;
-            }
+        self.bump_patch();
+    }
+
+    /// Bumps the patch version, so `1.2.3` becomes `1.2.4` and the pre-release `1.2.4-rc.1` becomes `1.2.4`.
+    pub fn bump_patch(&mut self) {
+        if !self.is_prerelease() {
+            self.patch += 1;
+        }
+        self.clear_labels();
+    }
+
+    /// Bumps the minor version resetting the patch, so `1.2.3` becomes `1.3.0` and the pre-release `1.3.0-rc.1`
+    /// becomes `1.3.0`.
+    pub fn bump_minor(&mut self) {
+        if !(self.is_prerelease() && self.patch == 0) {
+            self.minor += 1;
+        }
+        self.patch = 0;
+        self.clear_labels();
+    }
+
+    /// Bumps the major version resetting the minor and patch, so `1.2.3` becomes `2.0.0` and the pre-release
+    /// `2.0.0-rc.1` becomes `2.0.0`.
+    pub fn bump_major(&mut self) {
+        if !(self.is_prerelease() && self.minor == 0 && self.patch == 0) {
+            self.major += 1;
+        }
+        self.minor = 0;
+        self.patch = 0;
+        self.clear_labels();
+    }
+
+    /// Removes the pre-release identifiers and build metadata after a bump.
+    fn clear_labels(&mut self) {
+        self.pre.clear();
+        self.build.clear();
+    }
+
+    /// Checks if a model at this version can replace a model at the `required` version without breaking its callers.
+    /// This follows caret requirements where the left-most non-zero number has to match, so `1.4.0` can replace
+    /// `1.2.0` but `0.3.0` cannot replace `0.2.0`. A pre-release can only replace a version with the same numbers.
+    ///
+    /// # Arguments
+    /// * `required` - The version the callers were built against.
+    ///
+    /// # Returns
+    /// `true` if this version is compatible with the required version.
+    pub fn is_compatible_with(&self, required: &Version) -> bool {
+        if self.cmp_precedence(required) == Ordering::Less {
+            return false;
+        }
+        if self.is_prerelease()
+            && (self.major, self.minor, self.patch)
+                != (required.major, required.minor, required.patch)
+        {
+            return false;
+        }
+        match (required.major, required.minor) {
+            (0, 0) => self.major == 0 && self.minor == 0 && self.patch == required.patch,
+            (0, minor) => self.major == 0 && self.minor == minor,
+            (major, _) => self.major == major,
         }
     }
+
+    /// Compares the precedence of two versions, which ignores the build metadata.
+    ///
+    /// # Arguments
+    /// * `other` - The version to compare against.
+    ///
+    /// # Returns
+    /// The ordering of this version relative to `other`.
+    pub fn cmp_precedence(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                // a pre-release has a lower precedence than its release
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    /// Orders by precedence, falling back to the build metadata so the ordering agrees with equality.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_precedence(other)
+            .then_with(|| self.build.cmp(&other.build))
+    }
 }
 
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.one == 0 && self.two == 0 && self.three == 0 {
-            write!(f, "")
-        } else {
-            write!(f, "{}.{}.{}", self.one, self.two, self.three)
+        if *self == Version::fresh() {
+            return write!(f, "");
         }
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            let pre = self
+                .pre
+                .iter()
+                .map(|identifier| identifier.to_string())
+                .collect::<Vec<String>>();
+            write!(f, "-{}", pre.join("."))?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+        Ok(())
     }
 }
 
@@ -83,68 +316,133 @@ pub mod tests {
 
     use super::*;
 
+    fn version(data: &str) -> Version {
+        Version::from_string(data.to_string()).unwrap()
+    }
+
     #[test]
     fn test_from_string() {
-        let version = Version::from_string("0.0.0".to_string()).unwrap();
-        assert_eq!(version.one, 0);
-        assert_eq!(version.two, 0);
-        assert_eq!(version.three, 0);
+        let parsed = version("0.0.0");
+        assert_eq!(parsed, Version::fresh());
+
+        let parsed = version("1.2.3");
+        assert_eq!(parsed.major, 1);
+        assert_eq!(parsed.minor, 2);
+        assert_eq!(parsed.patch, 3);
+
+        let parsed = version("300.0.1024-rc.1+build.5");
+        assert_eq!(parsed.major, 300);
+        assert_eq!(parsed.patch, 1024);
+        assert_eq!(
+            parsed.pre,
+            vec![
+                Identifier::AlphaNumeric("rc".to_string()),
+                Identifier::Numeric(1)
+            ]
+        );
+        assert_eq!(parsed.build, vec!["build", "5"]);
+        assert_eq!(
+            Version::from_string(String::new()).unwrap(),
+            Version::fresh()
+        );
+    }
 
-        let version = Version::from_string("1.2.3".to_string()).unwrap();
-        assert_eq!(version.one, 1);
-        assert_eq!(version.two, 2);
-        assert_eq!(version.three, 3);
+    #[test]
+    fn test_from_string_errors() {
+        for data in [
+            "1.2",
+            "1.2.3.4",
+            "1.02.3",
+            "1.2.3-",
+            "1.2.3-rc..1",
+            "1.2.3+",
+            "a.b.c",
+        ] {
+            assert!(
+                Version::from_string(data.to_string()).is_err(),
+                "{} should not parse",
+                data
+            );
+        }
     }
 
     #[test]
     fn test_to_string() {
-        let version = Version {
-            one: 0,
-            two: 0,
-            three: 0,
-        };
-        assert_eq!(version.to_string(), "");
+        assert_eq!(Version::fresh().to_string(), "");
+        assert_eq!(Version::new(1, 2, 3).to_string(), "1.2.3");
+        for data in [
+            "1.2.0-rc.1",
+            "1.2.0+build.5",
+            "1.2.0-alpha-1.beta+exp.sha.5114f85",
+        ] {
+            assert_eq!(version(data).to_string(), data);
+        }
+    }
 
-        let version = Version {
-            one: 1,
-            two: 2,
-            three: 3,
-        };
-        assert_eq!(version.to_string(), "1.2.3");
+    #[test]
+    fn test_ordering() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.10.0",
+            "2.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(
+                version(pair[0]) < version(pair[1]),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert_eq!(
+            version("1.0.0+a").cmp_precedence(&version("1.0.0+b")),
+            Ordering::Equal
+        );
     }
 
     #[test]
-    fn test_increment() {
-        let mut version = Version {
-            one: 0,
-            two: 0,
-            three: 0,
-        };
-        version.increment();
-        assert_eq!(version.to_string(), "0.0.1");
+    fn test_bumps() {
+        let mut bumped = version("0.0.9");
+        bumped.increment();
+        assert_eq!(bumped.to_string(), "0.0.10");
 
-        let mut version = Version {
-            one: 0,
-            two: 0,
-            three: 9,
-        };
-        version.increment();
-        assert_eq!(version.to_string(), "0.1.0");
+        let mut bumped = version("1.2.3+build.1");
+        bumped.bump_patch();
+        assert_eq!(bumped.to_string(), "1.2.4");
+        bumped.bump_minor();
+        assert_eq!(bumped.to_string(), "1.3.0");
+        bumped.bump_major();
+        assert_eq!(bumped.to_string(), "2.0.0");
 
-        let mut version = Version {
-            one: 0,
-            two: 9,
-            three: 9,
-        };
-        version.increment();
-        assert_eq!(version.to_string(), "1.0.0");
+        let mut bumped = version("1.2.4-rc.1");
+        bumped.bump_patch();
+        assert_eq!(bumped.to_string(), "1.2.4");
+        let mut bumped = version("1.3.0-rc.1");
+        bumped.bump_minor();
+        assert_eq!(bumped.to_string(), "1.3.0");
+        let mut bumped = version("2.0.0-rc.1");
+        bumped.bump_major();
+        assert_eq!(bumped.to_string(), "2.0.0");
+    }
 
-        let mut version = Version {
-            one: 9,
-            two: 9,
-            three: 9,
-        };
-        version.increment();
-        assert_eq!(version.to_string(), "10.0.0");
+    #[test]
+    fn test_is_compatible_with() {
+        assert!(version("1.4.0").is_compatible_with(&version("1.2.0")));
+        assert!(!version("1.1.0").is_compatible_with(&version("1.2.0")));
+        assert!(!version("2.0.0").is_compatible_with(&version("1.2.0")));
+        assert!(version("0.2.5").is_compatible_with(&version("0.2.0")));
+        assert!(!version("0.3.0").is_compatible_with(&version("0.2.0")));
+        assert!(!version("0.0.4").is_compatible_with(&version("0.0.3")));
+        assert!(version("1.2.0").is_compatible_with(&version("1.2.0-rc.1")));
+        assert!(!version("1.3.0-rc.1").is_compatible_with(&version("1.2.0")));
+        assert!(version("1.2.0-rc.2").is_compatible_with(&version("1.2.0-rc.1")));
     }
 }
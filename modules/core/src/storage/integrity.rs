@@ -0,0 +1,164 @@
+//! Defines the content digest that is used to detect corrupted or truncated `surml` files.
+//!
+//! The digest is stored as the last field of the binary header, with only the signature allowed after it, and covers
+//! every header byte before that field followed by the model bytes.
+use sha2::{Digest, Sha256};
+
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use crate::storage::header::encoding::{self, tags, HeaderWriter, PayloadReader, PayloadWriter};
+
+/// The name of the only digest algorithm that is currently written.
+pub const SHA_256: &str = "sha256";
+
+/// Computes the SHA-256 digest over the header and model sections.
+///
+/// # Arguments
+/// * `header_bytes` - The bytes of the header that are covered by the digest.
+/// * `model` - The bytes of the model.
+///
+/// # Returns
+/// The digest.
+pub fn compute_digest(header_bytes: &[u8], model: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(header_bytes);
+    hasher.update(model);
+    hasher.finalize().to_vec()
+}
+
+/// Appends the digest field to a binary header.
+///
+/// # Arguments
+/// * `header_bytes` - The bytes of the binary header without a digest.
+/// * `model` - The bytes of the model.
+///
+/// # Returns
+/// The bytes of the header with the digest field appended.
+pub fn seal(header_bytes: Vec<u8>, model: &[u8]) -> Result<Vec<u8>, SurrealError> {
+    let digest = compute_digest(&header_bytes, model);
+    let mut payload = PayloadWriter::new();
+    payload.write_str(SHA_256)?;
+    payload.write_bytes(&digest)?;
+
+    let mut writer = HeaderWriter::resume(header_bytes);
+    writer.write_field(tags::DIGEST, &payload.finish())?;
+    Ok(writer.finish())
+}
+
+/// Reads the digest recorded in a binary header without checking it against the model.
+///
+/// # Arguments
+/// * `header_bytes` - The raw bytes of the header as read from the file.
+///
+/// # Returns
+/// The position of the digest field, which is the end of the header bytes it covers, and the digest itself if the
+/// header has one.
+pub fn recorded_digest(header_bytes: &[u8]) -> Result<Option<(usize, Vec<u8>)>, SurrealError> {
+    if !encoding::is_binary(header_bytes) {
+        return Ok(None);
+    }
+    let (_, fields) = encoding::read_fields(header_bytes)?;
+    let field = match fields.into_iter().find(|field| field.tag == tags::DIGEST) {
+        Some(field) => field,
+        None => return Ok(None),
+    };
+
+    let mut reader = PayloadReader::new(field.payload);
+    let algorithm = reader.read_str()?;
+    let digest = reader.read_bytes()?;
+    if algorithm != SHA_256 {
+        return Err(SurrealError::new(
+            format!("Unsupported digest algorithm: {}", algorithm),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+    Ok(Some((field.offset, digest.to_vec())))
+}
+
+/// Checks the digest recorded in the header against the header and model bytes.
+///
+/// # Arguments
+/// * `header_bytes` - The raw bytes of the header as read from the file.
+/// * `model` - The bytes of the model as read from the file.
+/// * `strict` - If `true` a missing digest is an error, otherwise files without a digest are accepted.
+///
+/// # Returns
+/// An error with a `BadRequest` status if the digest does not match or is required but missing.
+pub fn verify(header_bytes: &[u8], model: &[u8], strict: bool) -> Result<(), SurrealError> {
+    let (offset, expected) = match recorded_digest(header_bytes)? {
+        Some(recorded) => recorded,
+        None if strict => {
+            return Err(SurrealError::new(
+                "The surml file has no digest and strict integrity checking is enabled".to_string(),
+                SurrealErrorStatus::BadRequest,
+            ))
+        }
+        None => return Ok(()),
+    };
+
+    if compute_digest(&header_bytes[..offset], model) != expected {
+        return Err(SurrealError::new(
+            "The digest of the surml file does not match, the file may be corrupted or truncated"
+                .to_string(),
+            SurrealErrorStatus::BadRequest,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::storage::header::Header;
+
+    #[test]
+    fn test_seal_and_verify() {
+        let (_, header_bytes) = Header::fresh().to_bytes().unwrap();
+        let model = vec![1, 2, 3, 4];
+        let sealed = seal(header_bytes, &model).unwrap();
+
+        verify(&sealed, &model, true).unwrap();
+        let (offset, digest) = recorded_digest(&sealed).unwrap().unwrap();
+        assert_eq!(digest, compute_digest(&sealed[..offset], &model));
+
+        let error = verify(&sealed, &model[..3], false).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_field_after_digest() {
+        let (_, header_bytes) = Header::fresh().to_bytes().unwrap();
+        let model = vec![1, 2, 3, 4];
+        let sealed = seal(header_bytes, &model).unwrap();
+
+        let mut tampered = sealed.clone();
+        tampered.extend_from_slice(&(tags::METADATA | tags::CRITICAL).to_be_bytes());
+        tampered.extend_from_slice(&1u32.to_be_bytes());
+        tampered.push(0);
+        let error = verify(&tampered, &model, true).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert_eq!(
+            error.message,
+            "Header field 18 is written after the digest so it is not covered by it"
+        );
+        assert!(Header::from_bytes(tampered).is_err());
+
+        // a second digest is not covered by the first
+        let resealed = seal(sealed, &model).unwrap();
+        let error = verify(&resealed, &model, true).unwrap_err();
+        assert_eq!(error.message, "Header field 10 is written more than once");
+    }
+
+    #[test]
+    fn test_missing_digest() {
+        let (_, header_bytes) = Header::fresh().to_bytes().unwrap();
+
+        verify(&header_bytes, &[], false).unwrap();
+        let error = verify(&header_bytes, &[], true).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+
+        assert_eq!(recorded_digest(&header_bytes).unwrap(), None);
+        verify(b"//=>//=>", &[], false).unwrap();
+        assert!(verify(b"//=>//=>", &[], true).is_err());
+    }
+}
@@ -1,4 +1,7 @@
 //! Responsible for the saving and loading of the model including meta data around the model.
 pub mod header;
+pub mod integrity;
+pub mod signature;
 pub mod stream_adapter;
 pub mod surml_file;
+pub mod validation;
@@ -0,0 +1,185 @@
+//! Defines the Ed25519 signature that can be embedded in a `surml` file.
+//!
+//! The signature is stored as the last field of the binary header after the digest. It signs the SHA-256 digest of
+//! every header byte before the signature field followed by the model bytes, so any change to the header, the digest
+//! or the model after signing results in a signature that no longer verifies.
+pub use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+use ed25519_dalek::{Signer, PUBLIC_KEY_LENGTH};
+
+use crate::errors::error::{SurrealError, SurrealErrorStatus};
+use crate::storage::header::encoding::{self, tags, HeaderWriter, PayloadReader, PayloadWriter};
+use crate::storage::integrity::compute_digest;
+
+/// An Ed25519 signature over a `surml` file.
+///
+/// # Fields
+/// * `verifying_key` - The public key of the key that signed the file.
+/// * `signature` - The signature.
+/// * `message` - The digest of the signed bytes that the signature is checked against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelSignature {
+    pub verifying_key: VerifyingKey,
+    pub signature: Signature,
+    pub message: Vec<u8>,
+}
+
+impl ModelSignature {
+    /// Signs the bytes of a `surml` file.
+    ///
+    /// # Arguments
+    /// * `signing_key` - The private key to sign with.
+    /// * `header_bytes` - The header bytes that come before the signature field.
+    /// * `model` - The bytes of the model.
+    ///
+    /// # Returns
+    /// The signature.
+    pub fn sign(signing_key: &SigningKey, header_bytes: &[u8], model: &[u8]) -> Self {
+        let message = compute_digest(header_bytes, model);
+        ModelSignature {
+            verifying_key: signing_key.verifying_key(),
+            signature: signing_key.sign(&message),
+            message,
+        }
+    }
+
+    /// Checks that the signature was made by one of the trusted keys and matches the signed bytes.
+    ///
+    /// # Arguments
+    /// * `trusted_keys` - The public keys that are allowed to sign models.
+    ///
+    /// # Returns
+    /// An error with an `Unauthorized` status if the key is not trusted or the signature does not match.
+    pub fn verify(&self, trusted_keys: &[VerifyingKey]) -> Result<(), SurrealError> {
+        if !trusted_keys.contains(&self.verifying_key) {
+            return Err(SurrealError::new(
+                "The surml file is signed by a key that is not trusted".to_string(),
+                SurrealErrorStatus::Unauthorized,
+            ));
+        }
+        match self
+            .verifying_key
+            .verify_strict(&self.message, &self.signature)
+        {
+            Ok(()) => Ok(()),
+            Err(_) => Err(SurrealError::new(
+                "The signature of the surml file does not match its contents".to_string(),
+                SurrealErrorStatus::Unauthorized,
+            )),
+        }
+    }
+
+    /// Appends the signature field to a binary header.
+    ///
+    /// # Arguments
+    /// * `header_bytes` - The bytes of the binary header that were signed.
+    ///
+    /// # Returns
+    /// The bytes of the header with the signature field appended.
+    pub fn append_to(&self, header_bytes: Vec<u8>) -> Result<Vec<u8>, SurrealError> {
+        let mut payload = PayloadWriter::new();
+        payload.write_bytes(self.verifying_key.as_bytes())?;
+        payload.write_bytes(&self.signature.to_bytes())?;
+
+        let mut writer = HeaderWriter::resume(header_bytes);
+        writer.write_field(tags::SIGNATURE, &payload.finish())?;
+        Ok(writer.finish())
+    }
+
+    /// Reads the signature from the raw bytes of a header if there is one.
+    ///
+    /// # Arguments
+    /// * `header_bytes` - The raw bytes of the header as read from the file.
+    /// * `model` - The bytes of the model as read from the file.
+    ///
+    /// # Returns
+    /// The signature with the digest of the bytes it covers, or `None` if the file is not signed.
+    pub fn from_header_bytes(
+        header_bytes: &[u8],
+        model: &[u8],
+    ) -> Result<Option<Self>, SurrealError> {
+        if !encoding::is_binary(header_bytes) {
+            return Ok(None);
+        }
+        let (_, fields) = encoding::read_fields(header_bytes)?;
+        let field = match fields
+            .into_iter()
+            .find(|field| field.tag == tags::SIGNATURE)
+        {
+            Some(field) => field,
+            None => return Ok(None),
+        };
+
+        let mut reader = PayloadReader::new(field.payload);
+        let key_bytes: &[u8; PUBLIC_KEY_LENGTH] = match reader.read_bytes()?.try_into() {
+            Ok(key_bytes) => key_bytes,
+            Err(_) => {
+                return Err(SurrealError::new(
+                    "The public key in the signature of the surml file has the wrong length"
+                        .to_string(),
+                    SurrealErrorStatus::BadRequest,
+                ))
+            }
+        };
+        let verifying_key = match VerifyingKey::from_bytes(key_bytes) {
+            Ok(verifying_key) => verifying_key,
+            Err(error) => {
+                return Err(SurrealError::new(
+                    format!("Invalid public key in the surml file: {}", error),
+                    SurrealErrorStatus::BadRequest,
+                ))
+            }
+        };
+        let signature = match Signature::from_slice(reader.read_bytes()?) {
+            Ok(signature) => signature,
+            Err(error) => {
+                return Err(SurrealError::new(
+                    format!("Invalid signature in the surml file: {}", error),
+                    SurrealErrorStatus::BadRequest,
+                ))
+            }
+        };
+        Ok(Some(ModelSignature {
+            verifying_key,
+            signature,
+            message: compute_digest(&header_bytes[..field.offset], model),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_sign_and_read() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let header_bytes = encoding::HeaderWriter::new().finish();
+        let model = vec![1, 2, 3];
+
+        let signature = ModelSignature::sign(&signing_key, &header_bytes, &model);
+        let signed_header = signature.append_to(header_bytes).unwrap();
+
+        let read = ModelSignature::from_header_bytes(&signed_header, &model)
+            .unwrap()
+            .unwrap();
+        assert_eq!(read, signature);
+        read.verify(&[signing_key.verifying_key()]).unwrap();
+
+        let tampered = ModelSignature::from_header_bytes(&signed_header, &[1, 2, 4])
+            .unwrap()
+            .unwrap();
+        let error = tampered.verify(&[signing_key.verifying_key()]).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::Unauthorized);
+    }
+
+    #[test]
+    fn test_untrusted_key() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let other_key = SigningKey::from_bytes(&[8u8; 32]);
+        let signature = ModelSignature::sign(&signing_key, &[], &[1, 2, 3]);
+
+        let error = signature.verify(&[other_key.verifying_key()]).unwrap_err();
+        assert_eq!(error.status, SurrealErrorStatus::Unauthorized);
+    }
+}
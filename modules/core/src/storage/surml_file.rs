@@ -2,20 +2,50 @@
 use std::fs::File;
 use std::io::{Read, Write};
 
+use bytes::Bytes;
+use memmap2::Mmap;
+
 use crate::{
     errors::error::{SurrealError, SurrealErrorStatus},
+    execution::session::get_session,
     safe_eject, safe_eject_internal,
-    storage::header::Header,
+    storage::header::{compression::Compression, Header},
+    storage::integrity,
+    storage::signature::{ModelSignature, SigningKey, VerifyingKey},
+    storage::validation::{Issue, Location, Severity, ValidationReport},
 };
 
 /// The `SurMlFile` struct represents the entire `surml` file.
 ///
 /// # Fields
 /// * `header` - The header of the `surml` file containing data such as key bindings for inputs and normalisers.
-/// * `model` - The PyTorch model in C.
+/// * `model` - The PyTorch model in C. This is a shared buffer so a model loaded with `from_file_mapped` or
+///   `from_shared` points into the mapped file or the original buffer instead of being copied.
+/// * `signature` - The Ed25519 signature over the header and model if the file has been signed.
+/// * `loaded` - The header section of the file it was loaded from so it is not rewritten while nothing has changed.
 pub struct SurMlFile {
     pub header: Header,
-    pub model: Vec<u8>,
+    pub model: Bytes,
+    pub signature: Option<ModelSignature>,
+    loaded: Option<LoadedHeader>,
+}
+
+/// The header section of a loaded file along with what it was decoded to. A header from a newer writer can hold
+/// fields this version skips, so the raw bytes are the only bytes the recorded digest and signature are known to
+/// cover. They are reused for as long as the header, model and signature are unchanged.
+///
+/// # Fields
+/// * `raw` - The header section as it was read including the digest and signature fields.
+/// * `decoded` - The header encoded again straight after it was decoded to detect later changes.
+/// * `model` - The model that was loaded with the header.
+/// * `digest` - The SHA-256 digest recorded in the header if there is one.
+/// * `signature` - The signature read from the header if there is one.
+struct LoadedHeader {
+    raw: Vec<u8>,
+    decoded: Vec<u8>,
+    model: Bytes,
+    digest: Option<Vec<u8>>,
+    signature: Option<ModelSignature>,
 }
 
 impl SurMlFile {
@@ -29,7 +59,9 @@ impl SurMlFile {
     pub fn fresh(model: Vec<u8>) -> Self {
         Self {
             header: Header::fresh(),
-            model,
+            model: Bytes::from(model),
+            signature: None,
+            loaded: None,
         }
     }
 
@@ -42,10 +74,16 @@ impl SurMlFile {
     /// # Returns
     /// A new `SurMlFile` struct.
     pub fn new(header: Header, model: Vec<u8>) -> Self {
-        Self { header, model }
+        Self {
+            header,
+            model: Bytes::from(model),
+            signature: None,
+            loaded: None,
+        }
     }
 
-    /// Creates a new `SurMlFile` struct from a vector of bytes.
+    /// Creates a new `SurMlFile` struct from a vector of bytes. Headers in the legacy string format are still supported.
+    /// If the file has a digest it is checked against the header and model.
     ///
     /// # Arguments
     /// * `bytes` - A vector of bytes representing the header and the model.
@@ -53,6 +91,41 @@ impl SurMlFile {
     /// # Returns
     /// A new `SurMlFile` struct.
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, SurrealError> {
+        Self::from_shared_with_check(Bytes::from(bytes), false)
+    }
+
+    /// Creates a new `SurMlFile` struct from a vector of bytes requiring the file to have a matching digest.
+    ///
+    /// # Arguments
+    /// * `bytes` - A vector of bytes representing the header and the model.
+    ///
+    /// # Returns
+    /// A new `SurMlFile` struct.
+    pub fn from_bytes_strict(bytes: Vec<u8>) -> Result<Self, SurrealError> {
+        Self::from_shared_with_check(Bytes::from(bytes), true)
+    }
+
+    /// Creates a new `SurMlFile` struct from a shared buffer. An uncompressed model is a slice of the buffer rather
+    /// than a copy.
+    ///
+    /// # Arguments
+    /// * `bytes` - A buffer representing the header and the model.
+    ///
+    /// # Returns
+    /// A new `SurMlFile` struct.
+    pub fn from_shared(bytes: Bytes) -> Result<Self, SurrealError> {
+        Self::from_shared_with_check(bytes, false)
+    }
+
+    /// Creates a new `SurMlFile` struct from a shared buffer checking the digest.
+    ///
+    /// # Arguments
+    /// * `bytes` - A buffer representing the header and the model.
+    /// * `strict` - If `true` the file must have a digest.
+    ///
+    /// # Returns
+    /// A new `SurMlFile` struct.
+    fn from_shared_with_check(bytes: Bytes, strict: bool) -> Result<Self, SurrealError> {
         // check to see if there is enough bytes to read
         if bytes.len() < 4 {
             return Err(SurrealError::new(
@@ -60,9 +133,6 @@ impl SurMlFile {
                 SurrealErrorStatus::BadRequest,
             ));
         }
-        let mut header_bytes = Vec::new();
-        let mut model_bytes = Vec::new();
-
         // extract the first 4 bytes as an integer to get the length of the header
         let mut buffer = [0u8; 4];
         buffer.copy_from_slice(&bytes[0..4]);
@@ -78,18 +148,17 @@ impl SurMlFile {
         }
 
         // Read the next integer_value bytes for the header
-        header_bytes.extend_from_slice(&bytes[4..(4 + integer_value as usize)]);
+        let header_bytes = &bytes[4..(4 + integer_value as usize)];
 
-        // Read the remaining bytes for the model
-        model_bytes.extend_from_slice(&bytes[(4 + integer_value as usize)..]);
+        // Slice the remaining bytes for the model without copying them
+        let model_bytes = bytes.slice((4 + integer_value as usize)..);
 
         // construct the header and C model from the bytes
-        let header = Header::from_bytes(header_bytes)?;
-        let model = model_bytes;
-        Ok(Self { header, model })
+        Self::from_parts(header_bytes, model_bytes, strict)
     }
 
-    /// Creates a new `SurMlFile` struct from a file.
+    /// Creates a new `SurMlFile` struct from a file. Headers in the legacy string format are still supported.
+    /// If the file has a digest it is checked against the header and model.
     ///
     /// # Arguments
     /// * `file_path` - The path to the `surml` file.
@@ -97,19 +166,33 @@ impl SurMlFile {
     /// # Returns
     /// A new `SurMlFile` struct.
     pub fn from_file(file_path: &str) -> Result<Self, SurrealError> {
-        let mut file = safe_eject!(File::open(file_path), SurrealErrorStatus::NotFound);
+        Self::from_file_with_check(file_path, false)
+    }
 
-        // extract the first 4 bytes as an integer to get the length of the header
-        let mut buffer = [0u8; 4];
-        safe_eject!(file.read_exact(&mut buffer), SurrealErrorStatus::BadRequest);
-        let integer_value = u32::from_be_bytes(buffer);
+    /// Creates a new `SurMlFile` struct from a file requiring the file to have a matching digest.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path to the `surml` file.
+    ///
+    /// # Returns
+    /// A new `SurMlFile` struct.
+    pub fn from_file_strict(file_path: &str) -> Result<Self, SurrealError> {
+        Self::from_file_with_check(file_path, true)
+    }
 
-        // Read the next integer_value bytes for the header
-        let mut header_buffer = vec![0u8; integer_value as usize];
-        safe_eject!(
-            file.read_exact(&mut header_buffer),
-            SurrealErrorStatus::BadRequest
-        );
+    /// Creates a new `SurMlFile` struct from a file checking the digest.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path to the `surml` file.
+    /// * `strict` - If `true` the file must have a digest.
+    ///
+    /// # Returns
+    /// A new `SurMlFile` struct.
+    fn from_file_with_check(file_path: &str, strict: bool) -> Result<Self, SurrealError> {
+        let mut file = safe_eject!(File::open(file_path), SurrealErrorStatus::NotFound);
+
+        // read the length prefix and the header section
+        let header_buffer = Header::read_section(&mut file)?;
 
         // Create a Vec<u8> to store the data
         let mut model_buffer = Vec::new();
@@ -121,28 +204,226 @@ impl SurMlFile {
         );
 
         // construct the header and C model from the bytes
-        let header = Header::from_bytes(header_buffer)?;
+        Self::from_parts(&header_buffer, Bytes::from(model_buffer), strict)
+    }
+
+    /// Creates a new `SurMlFile` struct by memory-mapping a file. The model is not read onto the heap, it is a
+    /// slice of the mapped file that stays mapped for as long as the model is alive. Compressed models still have to
+    /// be decompressed onto the heap. The file must not be modified while it is mapped.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path to the `surml` file.
+    ///
+    /// # Returns
+    /// A new `SurMlFile` struct.
+    pub fn from_file_mapped(file_path: &str) -> Result<Self, SurrealError> {
+        Self::from_file_mapped_with_check(file_path, false)
+    }
+
+    /// Creates a new `SurMlFile` struct by memory-mapping a file requiring the file to have a matching digest.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path to the `surml` file.
+    ///
+    /// # Returns
+    /// A new `SurMlFile` struct.
+    pub fn from_file_mapped_strict(file_path: &str) -> Result<Self, SurrealError> {
+        Self::from_file_mapped_with_check(file_path, true)
+    }
+
+    /// Creates a new `SurMlFile` struct by memory-mapping a file checking the digest.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path to the `surml` file.
+    /// * `strict` - If `true` the file must have a digest.
+    ///
+    /// # Returns
+    /// A new `SurMlFile` struct.
+    fn from_file_mapped_with_check(file_path: &str, strict: bool) -> Result<Self, SurrealError> {
+        let file = safe_eject!(File::open(file_path), SurrealErrorStatus::NotFound);
+
+        // the map is only sound while the file is not changed underneath it which callers are warned about above
+        let mapped = safe_eject!(unsafe { Mmap::map(&file) }, SurrealErrorStatus::Unknown);
+        Self::from_shared_with_check(Bytes::from_owner(mapped), strict)
+    }
+
+    /// Creates a new `SurMlFile` struct from the header and model sections of a file. The model is decompressed if
+    /// the header records a compression codec, and the digest and signature are checked against the decompressed
+    /// model.
+    ///
+    /// # Arguments
+    /// * `header_bytes` - The raw bytes of the header.
+    /// * `model_bytes` - The model bytes as stored in the file.
+    /// * `strict` - If `true` the file must have a digest.
+    ///
+    /// # Returns
+    /// A new `SurMlFile` struct.
+    fn from_parts(
+        header_bytes: &[u8],
+        model_bytes: Bytes,
+        strict: bool,
+    ) -> Result<Self, SurrealError> {
+        let header = Header::from_bytes(header_bytes.to_vec())?;
+        let model = match header.compression {
+            Compression::None => model_bytes,
+            codec => Bytes::from(codec.decompress(&model_bytes)?),
+        };
+        integrity::verify(header_bytes, &model, strict)?;
+        let signature = ModelSignature::from_header_bytes(header_bytes, &model)?;
+        let loaded = LoadedHeader {
+            raw: header_bytes.to_vec(),
+            decoded: header.to_bytes()?.1,
+            model: model.clone(),
+            digest: integrity::recorded_digest(header_bytes)?.map(|(_, digest)| digest),
+            signature: signature.clone(),
+        };
         Ok(Self {
             header,
-            model: model_buffer,
+            model,
+            signature,
+            loaded: Some(loaded),
         })
     }
 
-    /// Converts the header and the model to a vector of bytes.
+    /// The header section of the file this was loaded from if the header, model and signature have not been changed
+    /// since.
+    ///
+    /// # Returns
+    /// The loaded header section, or `None` if the file was not loaded or has been changed.
+    fn unchanged_load(&self) -> Option<&LoadedHeader> {
+        let loaded = self.loaded.as_ref()?;
+        // the bytes of a buffer never change so the same slice of the same buffer is the model that was read
+        let same_model =
+            loaded.model.as_ptr() == self.model.as_ptr() && loaded.model.len() == self.model.len();
+        if same_model
+            && loaded.signature == self.signature
+            && self
+                .header
+                .to_bytes()
+                .is_ok_and(|(_, header_bytes)| header_bytes == loaded.decoded)
+        {
+            return Some(loaded);
+        }
+        None
+    }
+
+    /// Compiles the header into bytes with the digest of the header and model appended.
+    ///
+    /// # Returns
+    /// The bytes of the sealed header without a signature.
+    fn sealed_header(&self) -> Result<Vec<u8>, SurrealError> {
+        let (_, header_bytes) = self.header.to_bytes()?;
+        integrity::seal(header_bytes, &self.model)
+    }
+
+    /// The SHA-256 digest of the header and model. This is the digest that is recorded in the file when it is
+    /// written, so it identifies the contents of the model regardless of where it was loaded from. The digest read
+    /// from the file is reused while the header and model are unchanged, otherwise it is computed.
+    ///
+    /// # Returns
+    /// The digest, or an error with a `BadRequest` status if the header is too long to be written.
+    pub fn digest(&self) -> Result<Vec<u8>, SurrealError> {
+        if let Some(digest) = self
+            .unchanged_load()
+            .and_then(|loaded| loaded.digest.clone())
+        {
+            return Ok(digest);
+        }
+        let (_, header_bytes) = self.header.to_bytes()?;
+        Ok(integrity::compute_digest(&header_bytes, &self.model))
+    }
+
+    /// Signs the header and model with an Ed25519 key. The signature is written with the file and is invalidated by
+    /// any later change to the header or model.
+    ///
+    /// # Arguments
+    /// * `signing_key` - The private key to sign the file with.
+    ///
+    /// # Returns
+    /// An error with a `BadRequest` status if the header is too long to be written.
+    pub fn sign(&mut self, signing_key: &SigningKey) -> Result<(), SurrealError> {
+        let header_bytes = self.sealed_header()?;
+        self.signature = Some(ModelSignature::sign(
+            signing_key,
+            &header_bytes,
+            &self.model,
+        ));
+        Ok(())
+    }
+
+    /// Checks that the file is signed by one of the trusted keys and that the header and model have not been changed
+    /// since it was signed.
+    ///
+    /// # Arguments
+    /// * `trusted_keys` - The public keys that are allowed to sign models.
+    ///
+    /// # Returns
+    /// An error with an `Unauthorized` status if the file is unsigned or the signature is not valid.
+    pub fn verify(&self, trusted_keys: &[VerifyingKey]) -> Result<(), SurrealError> {
+        let signature = match &self.signature {
+            Some(signature) => signature,
+            None => {
+                return Err(SurrealError::new(
+                    "The surml file is not signed".to_string(),
+                    SurrealErrorStatus::Unauthorized,
+                ))
+            }
+        };
+        signature.verify(trusted_keys)?;
+
+        // a loaded signature covers the bytes that were read, which still describe the file while nothing has changed
+        if self.unchanged_load().is_some() {
+            return Ok(());
+        }
+        // otherwise the signature covers the bytes that were signed so the current header and model must still match
+        if integrity::compute_digest(&self.sealed_header()?, &self.model) != signature.message {
+            return Err(SurrealError::new(
+                "The surml file has been changed since it was signed".to_string(),
+                SurrealErrorStatus::Unauthorized,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Converts the header and the model to a vector of bytes. A digest over the header and model is recorded in the
+    /// header so corrupted or truncated files can be detected when they are loaded. If the file is signed the
+    /// signature is appended after the digest. The model is compressed with the codec recorded in the header. A
+    /// loaded file with a digest that has not been changed keeps the header section it was read with, so fields
+    /// from a newer writer and the signature over them are preserved.
     ///
     /// # Returns
     /// A vector of bytes representing the header and the model.
-    pub fn to_bytes(&self) -> Vec<u8> {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SurrealError> {
         // compile the header into bytes.
-        let (num, header_bytes) = self.header.to_bytes();
-        let num_bytes = i32::to_be_bytes(num).to_vec();
+        let header_bytes = match self.unchanged_load() {
+            Some(loaded) if loaded.digest.is_some() => loaded.raw.clone(),
+            _ => {
+                let header_bytes = self.sealed_header()?;
+                match &self.signature {
+                    Some(signature) => signature.append_to(header_bytes)?,
+                    None => header_bytes,
+                }
+            }
+        };
+        let num_bytes = match i32::try_from(header_bytes.len()) {
+            Ok(length) => length.to_be_bytes().to_vec(),
+            Err(_) => {
+                return Err(SurrealError::new(
+                    format!(
+                        "The header of {} bytes is too long to be written",
+                        header_bytes.len()
+                    ),
+                    SurrealErrorStatus::BadRequest,
+                ))
+            }
+        };
 
         // combine the bytes into a single vector
         let mut combined_vec: Vec<u8> = Vec::new();
         combined_vec.extend(num_bytes);
         combined_vec.extend(header_bytes);
-        combined_vec.extend(self.model.clone());
-        combined_vec
+        combined_vec.extend(self.header.compression.compress(&self.model)?);
+        Ok(combined_vec)
     }
 
     /// Writes the header and the model to a `surml` file.
@@ -153,22 +434,107 @@ impl SurMlFile {
     /// # Returns
     /// An `io::Result` indicating whether the write was successful.
     pub fn write(&self, file_path: &str) -> Result<(), SurrealError> {
-        let combined_vec = self.to_bytes();
+        let combined_vec = self.to_bytes()?;
 
         // write the bytes to a file
         let mut file = safe_eject_internal!(File::create(file_path));
         safe_eject_internal!(file.write(&combined_vec));
         Ok(())
     }
+
+    /// Checks that the header is consistent with itself and with the inputs of the model, for example that the number
+    /// of columns fits the input of the model and that every normaliser refers to a defined column.
+    ///
+    /// # Returns
+    /// A report of every error and warning found.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::fresh();
+        report.check_header(&self.header);
+        match get_session(&self.model) {
+            Ok(session) => report.check_session(&self.header, &session),
+            Err(error) => report.issues.push(Issue {
+                severity: Severity::Error,
+                location: Location::Model,
+                message: format!("Model could not be loaded: {}", error.message),
+            }),
+        }
+        report
+    }
+
+    /// Validates the file and writes it to a `surml` file only if there are no errors.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path to the `surml` file.
+    ///
+    /// # Returns
+    /// The report with any warnings, or an error with a `BadRequest` status listing the issues if the file is invalid.
+    pub fn write_validated(&self, file_path: &str) -> Result<ValidationReport, SurrealError> {
+        let report = self.validate();
+        if !report.is_valid() {
+            return Err(SurrealError::new(
+                format!("The surml file is invalid:\n{}", report),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        self.write(file_path)?;
+        Ok(report)
+    }
+
+    /// Writes the header and the model to a `surml` file with the model compressed. The codec is recorded in the
+    /// header of the written file so the model is decompressed when it is loaded, `self` is left unchanged. The
+    /// signature covers the codec so a signed file can only be written with the codec it was signed with.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path to the `surml` file.
+    /// * `codec` - The codec to compress the model with.
+    ///
+    /// # Returns
+    /// An error with a `BadRequest` status if the file is signed with a different codec, otherwise whether the
+    /// write was successful.
+    pub fn write_compressed(
+        &self,
+        file_path: &str,
+        codec: Compression,
+    ) -> Result<(), SurrealError> {
+        if codec == self.header.compression {
+            return self.write(file_path);
+        }
+        if self.signature.is_some() {
+            return Err(SurrealError::new(
+                format!(
+                    "The surml file is signed with the {} codec so it must be signed again to be written with {}",
+                    self.header.compression, codec
+                ),
+                SurrealErrorStatus::BadRequest,
+            ));
+        }
+        let mut header = self.header.clone();
+        header.compression = codec;
+        let compressed = SurMlFile {
+            header,
+            model: self.model.clone(),
+            signature: None,
+            loaded: None,
+        };
+        compressed.write(file_path)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::storage::header::encoding::tags;
+    use tempfile::{tempdir, TempDir};
+
+    /// Builds a path to a file in the temporary directory so tests do not rewrite the files in the stash.
+    fn temp_path(dir: &TempDir, name: &str) -> String {
+        dir.path().join(name).to_str().unwrap().to_string()
+    }
 
     #[test]
     fn test_write() {
+        let dir = tempdir().unwrap();
         let mut header = Header::fresh();
         header.add_column(String::from("squarefoot"));
         header.add_column(String::from("num_floors"));
@@ -180,13 +546,14 @@ mod tests {
         file.read_to_end(&mut model_bytes).unwrap();
 
         let surml_file = SurMlFile::new(header, model_bytes);
-        surml_file.write("./stash/test.surml").unwrap();
+        surml_file.write(&temp_path(&dir, "test.surml")).unwrap();
 
-        let _ = SurMlFile::from_file("./stash/test.surml").unwrap();
+        let _ = SurMlFile::from_file(&temp_path(&dir, "test.surml")).unwrap();
     }
 
     #[test]
     fn test_write_forrest() {
+        let dir = tempdir().unwrap();
         let header = Header::fresh();
 
         let mut file = File::open("./stash/forrest_test.onnx").unwrap();
@@ -195,9 +562,318 @@ mod tests {
         file.read_to_end(&mut model_bytes).unwrap();
 
         let surml_file = SurMlFile::new(header, model_bytes);
-        surml_file.write("./stash/forrest.surml").unwrap();
+        surml_file.write(&temp_path(&dir, "forrest.surml")).unwrap();
 
-        let _ = SurMlFile::from_file("./stash/forrest.surml").unwrap();
+        let _ = SurMlFile::from_file(&temp_path(&dir, "forrest.surml")).unwrap();
+    }
+
+    #[test]
+    fn test_from_file_mapped() {
+        let dir = tempdir().unwrap();
+        let mut file = File::open("./stash/linear_test.onnx").unwrap();
+        let mut model_bytes = Vec::new();
+        file.read_to_end(&mut model_bytes).unwrap();
+
+        let mut header = Header::fresh();
+        header.add_column(String::from("squarefoot"));
+        let surml_file = SurMlFile::new(header, model_bytes.clone());
+        surml_file
+            .write(&temp_path(&dir, "mapped_test.surml"))
+            .unwrap();
+
+        let mapped =
+            SurMlFile::from_file_mapped_strict(&temp_path(&dir, "mapped_test.surml")).unwrap();
+        assert_eq!(mapped.header, surml_file.header);
+        assert_eq!(mapped.model, model_bytes);
+
+        // the model of a shared buffer is a slice of that buffer
+        let shared = Bytes::from(surml_file.to_bytes().unwrap());
+        let loaded = SurMlFile::from_shared(shared.clone()).unwrap();
+        let offset = loaded.model.as_ptr() as usize - shared.as_ptr() as usize;
+        assert_eq!(offset + loaded.model.len(), shared.len());
+    }
+
+    #[test]
+    fn test_read_header_only() {
+        let dir = tempdir().unwrap();
+        let mut header = Header::fresh();
+        header.add_column(String::from("squarefoot"));
+        header.add_name(String::from("house-price-prediction"));
+        let surml_file = SurMlFile::new(header, vec![7u8; 1024]);
+        surml_file
+            .write(&temp_path(&dir, "header_only_test.surml"))
+            .unwrap();
+
+        let header = Header::read_from_path(&temp_path(&dir, "header_only_test.surml")).unwrap();
+        assert_eq!(header, surml_file.header);
+
+        // the model bytes after the header are left unread
+        let bytes = surml_file.to_bytes().unwrap();
+        let mut reader = std::io::Cursor::new(&bytes);
+        let header = Header::read_from_reader(&mut reader).unwrap();
+        assert_eq!(header, surml_file.header);
+        assert_eq!(bytes.len() - reader.position() as usize, 1024);
+
+        assert!(Header::read_from_path(&temp_path(&dir, "missing.surml")).is_err());
+    }
+
+    #[test]
+    fn test_read_legacy_file() {
+        let legacy = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+        assert_eq!(legacy.header.keys.store, vec!["squarefoot", "num_floors"]);
+        assert_eq!(legacy.header.name.to_string(), "linear");
+        assert_eq!(
+            legacy.header.outputs.first().unwrap().name,
+            Some("house_price".to_string())
+        );
+
+        // the legacy file is rewritten with the binary header and reads back the same
+        let converted = SurMlFile::from_bytes(legacy.to_bytes().unwrap()).unwrap();
+        assert_eq!(converted.header, legacy.header);
+        assert_eq!(converted.model, legacy.model);
+    }
+
+    #[test]
+    fn test_digest() {
+        let mut header = Header::fresh();
+        header.add_column(String::from("squarefoot"));
+        let surml_file = SurMlFile::new(header, vec![1, 2, 3, 4, 5]);
+        let bytes = surml_file.to_bytes().unwrap();
+
+        let mut loaded = SurMlFile::from_bytes_strict(bytes.clone()).unwrap();
+        assert!(loaded.loaded.as_ref().unwrap().digest.is_some());
+        assert_eq!(loaded.digest().unwrap(), surml_file.digest().unwrap());
+
+        // the recorded digest is not reused once the header or model changes
+        loaded.header.add_name(String::from("renamed"));
+        let (_, header_bytes) = loaded.header.to_bytes().unwrap();
+        assert_eq!(
+            loaded.digest().unwrap(),
+            integrity::compute_digest(&header_bytes, &loaded.model)
+        );
+        assert_ne!(loaded.digest().unwrap(), surml_file.digest().unwrap());
+        loaded.header = Header::from_bytes(surml_file.header.to_bytes().unwrap().1).unwrap();
+        loaded.model = Bytes::from(vec![5, 4, 3, 2, 1]);
+        assert_ne!(loaded.digest().unwrap(), surml_file.digest().unwrap());
+
+        // a partial upload is missing the end of the model
+        let mut truncated = bytes.clone();
+        truncated.truncate(bytes.len() - 2);
+        match SurMlFile::from_bytes(truncated) {
+            Ok(_) => panic!("should have error with a truncated model"),
+            Err(error) => assert_eq!(error.status, SurrealErrorStatus::BadRequest),
+        }
+
+        // a flipped bit in the model
+        let mut corrupted = bytes.clone();
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 1;
+        assert!(SurMlFile::from_bytes(corrupted).is_err());
+    }
+
+    #[test]
+    fn test_strict_requires_digest() {
+        let _ = SurMlFile::from_file("./model_stash/sklearn/surml/linear.surml").unwrap();
+        match SurMlFile::from_file_strict("./model_stash/sklearn/surml/linear.surml") {
+            Ok(_) => panic!("should have error with a legacy file in strict mode"),
+            Err(error) => assert_eq!(error.status, SurrealErrorStatus::BadRequest),
+        }
+    }
+
+    #[cfg(feature = "onnx-tests")]
+    #[test]
+    fn test_validate() {
+        let dir = tempdir().unwrap();
+        crate::execution::session::set_environment().unwrap();
+        let mut surml_file = SurMlFile::from_file("./model_stash/onnx/surml/linear.surml").unwrap();
+        let report = surml_file.validate();
+        assert!(report.is_valid(), "{}", report);
+
+        surml_file.header.add_column(String::from("extra"));
+        let report = surml_file.validate();
+        assert!(!report.is_valid());
+        assert!(matches!(
+            report.errors().next().unwrap().location,
+            Location::OnnxInput(_)
+        ));
+        assert!(surml_file
+            .write_validated(&temp_path(&dir, "invalid.surml"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_write_compressed() {
+        let dir = tempdir().unwrap();
+        let mut file = File::open("./stash/forrest_test.onnx").unwrap();
+        let mut model_bytes = Vec::new();
+        file.read_to_end(&mut model_bytes).unwrap();
+
+        for (codec, name) in [
+            (Compression::Zstd, "forrest_zstd.surml"),
+            (Compression::Deflate, "forrest_deflate.surml"),
+        ] {
+            let path = &temp_path(&dir, name);
+            let surml_file = SurMlFile::new(Header::fresh(), model_bytes.clone());
+            surml_file.write_compressed(path, codec).unwrap();
+            assert_eq!(surml_file.header.compression, Compression::None);
+            assert!(std::fs::metadata(path).unwrap().len() < model_bytes.len() as u64);
+
+            let loaded = SurMlFile::from_file_strict(path).unwrap();
+            assert_eq!(loaded.header.compression, codec);
+            assert_eq!(loaded.model, model_bytes);
+        }
+
+        // a corrupted compressed model is rejected
+        let mut surml_file = SurMlFile::new(Header::fresh(), model_bytes.clone());
+        surml_file.header.compression = Compression::Zstd;
+        let mut bytes = surml_file.to_bytes().unwrap();
+        let middle = bytes.len() - 100;
+        bytes[middle] ^= 0xff;
+        assert!(SurMlFile::from_bytes(bytes).is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+        let trusted = vec![signing_key.verifying_key()];
+
+        let mut header = Header::fresh();
+        header.add_column(String::from("squarefoot"));
+        let mut surml_file = SurMlFile::new(header, vec![1, 2, 3, 4, 5]);
+        match surml_file.verify(&trusted) {
+            Ok(_) => panic!("should have error with an unsigned file"),
+            Err(error) => assert_eq!(error.status, SurrealErrorStatus::Unauthorized),
+        }
+
+        surml_file.sign(&signing_key).unwrap();
+        surml_file.verify(&trusted).unwrap();
+
+        let loaded = SurMlFile::from_bytes_strict(surml_file.to_bytes().unwrap()).unwrap();
+        loaded.verify(&trusted).unwrap();
+        assert_eq!(loaded.header, surml_file.header);
+
+        let other_key = SigningKey::from_bytes(&[4u8; 32]);
+        assert!(loaded.verify(&[other_key.verifying_key()]).is_err());
+
+        // changing the header after signing invalidates the signature
+        let mut changed = SurMlFile::from_bytes(surml_file.to_bytes().unwrap()).unwrap();
+        changed.header.add_column(String::from("num_floors"));
+        assert!(changed.verify(&trusted).is_err());
+        let reloaded = SurMlFile::from_bytes(changed.to_bytes().unwrap()).unwrap();
+        assert!(reloaded.verify(&trusted).is_err());
+    }
+
+    #[test]
+    fn test_verify_signed_file_with_unknown_field() {
+        let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+        let trusted = vec![signing_key.verifying_key()];
+        let model = vec![1, 2, 3, 4, 5];
+
+        // a newer writer signs a header with a field this version does not know
+        let mut header = Header::fresh();
+        header.add_column(String::from("squarefoot"));
+        let (_, mut header_bytes) = header.to_bytes().unwrap();
+        header_bytes.extend_from_slice(&500u16.to_be_bytes());
+        header_bytes.extend_from_slice(&3u32.to_be_bytes());
+        header_bytes.extend_from_slice(&[1, 2, 3]);
+        let sealed = integrity::seal(header_bytes, &model).unwrap();
+        let signed = ModelSignature::sign(&signing_key, &sealed, &model)
+            .append_to(sealed)
+            .unwrap();
+        let mut bytes = (signed.len() as i32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(&signed);
+        bytes.extend_from_slice(&model);
+
+        let loaded = SurMlFile::from_bytes_strict(bytes.clone()).unwrap();
+        assert_eq!(loaded.header, header);
+        loaded.verify(&trusted).unwrap();
+
+        // writing the unchanged file keeps the field so the signature still verifies
+        assert_eq!(loaded.to_bytes().unwrap(), bytes);
+        let reloaded = SurMlFile::from_bytes_strict(loaded.to_bytes().unwrap()).unwrap();
+        reloaded.verify(&trusted).unwrap();
+
+        let mut changed = reloaded;
+        changed.header.add_name(String::from("renamed"));
+        assert_eq!(
+            changed.verify(&trusted).unwrap_err().message,
+            "The surml file has been changed since it was signed"
+        );
+    }
+
+    #[test]
+    fn test_field_after_signature_is_rejected() {
+        let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+        let trusted = vec![signing_key.verifying_key()];
+
+        let mut header = Header::fresh();
+        header.add_column(String::from("a"));
+        header.add_column(String::from("b"));
+        let mut surml_file = SurMlFile::new(header, vec![1, 2, 3, 4, 5]);
+        surml_file.sign(&signing_key).unwrap();
+        let bytes = surml_file.to_bytes().unwrap();
+        SurMlFile::from_bytes_strict(bytes.clone())
+            .unwrap()
+            .verify(&trusted)
+            .unwrap();
+
+        // reordered keys appended after the signature are not covered by it so the file is refused
+        let mut keys = Header::fresh();
+        keys.add_column(String::from("b"));
+        keys.add_column(String::from("a"));
+        let mut payload = keys.keys.to_field().unwrap();
+        let mut field = (tags::KEYS | tags::CRITICAL).to_be_bytes().to_vec();
+        field.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        field.append(&mut payload);
+
+        let header_length = u32::from_be_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        let mut tampered = ((header_length + field.len()) as u32)
+            .to_be_bytes()
+            .to_vec();
+        tampered.extend_from_slice(&bytes[4..4 + header_length]);
+        tampered.extend_from_slice(&field);
+        tampered.extend_from_slice(&bytes[4 + header_length..]);
+
+        let error = match SurMlFile::from_bytes_strict(tampered.clone()) {
+            Ok(_) => panic!("should have error with a field after the signature"),
+            Err(error) => error,
+        };
+        assert_eq!(error.status, SurrealErrorStatus::BadRequest);
+        assert!(SurMlFile::from_bytes(tampered.clone()).is_err());
+        assert!(ModelSignature::from_header_bytes(
+            &tampered[4..4 + header_length + field.len()],
+            &[1, 2, 3, 4, 5]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_sign_then_write_compressed() {
+        let dir = tempdir().unwrap();
+        let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+        let trusted = vec![signing_key.verifying_key()];
+
+        let mut surml_file = SurMlFile::new(Header::fresh(), vec![1, 2, 3, 4, 5]);
+        surml_file.sign(&signing_key).unwrap();
+
+        // changing the codec would break the signature so the write is refused and the file is unchanged
+        let path = &temp_path(&dir, "signed_zstd.surml");
+        match surml_file.write_compressed(path, Compression::Zstd) {
+            Ok(_) => panic!("should have error when changing the codec of a signed file"),
+            Err(error) => assert_eq!(error.status, SurrealErrorStatus::BadRequest),
+        }
+        assert_eq!(surml_file.header.compression, Compression::None);
+        surml_file.verify(&trusted).unwrap();
+
+        // signing after choosing the codec keeps the signature valid
+        surml_file.header.compression = Compression::Zstd;
+        surml_file.sign(&signing_key).unwrap();
+        surml_file
+            .write_compressed(path, Compression::Zstd)
+            .unwrap();
+        let loaded = SurMlFile::from_file_strict(path).unwrap();
+        loaded.verify(&trusted).unwrap();
+        assert_eq!(loaded.header.compression, Compression::Zstd);
     }
 
     #[test]
@@ -0,0 +1,347 @@
+//! Defines the checks that the header of a surml file is consistent with itself and with its model.
+use std::fmt;
+
+use ort::session::Session;
+
+use crate::storage::header::input_dims::InputDims;
+use crate::storage::header::Header;
+
+/// How serious an issue is.
+///
+/// # Fields
+/// * `Error` - The model will fail at inference time.
+/// * `Warning` - The model can run but something is likely to be wrong or missing.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// What an issue refers to.
+///
+/// # Fields
+/// * `HeaderField` - A field of the header such as `keys`, `normalisers` or `output`.
+/// * `OnnxInput` - A named input of the ONNX graph.
+/// * `Model` - The model as a whole, for example when it can not be loaded.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Location {
+    HeaderField(String),
+    OnnxInput(String),
+    Model,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::HeaderField(field) => write!(f, "header.{}", field),
+            Location::OnnxInput(name) => write!(f, "onnx input {}", name),
+            Location::Model => write!(f, "model"),
+        }
+    }
+}
+
+/// A single inconsistency found in a surml file.
+///
+/// # Fields
+/// * `severity` - How serious the issue is.
+/// * `location` - What the issue refers to.
+/// * `message` - A description of the issue.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Issue {
+    pub severity: Severity,
+    pub location: Location,
+    pub message: String,
+}
+
+/// The outcome of validating a surml file.
+///
+/// # Fields
+/// * `issues` - Every issue found in the order the checks were run.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    /// Creates a new report with no issues.
+    ///
+    /// # Returns
+    /// A new empty `ValidationReport`.
+    pub fn fresh() -> Self {
+        ValidationReport { issues: Vec::new() }
+    }
+
+    /// Returns `true` if there are no errors. Warnings do not make a file invalid.
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// The issues that will make the model fail at inference time.
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// The issues that are likely to be wrong but do not stop the model from running.
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// Adds an error to the report.
+    fn error(&mut self, location: Location, message: String) {
+        self.issues.push(Issue {
+            severity: Severity::Error,
+            location,
+            message,
+        });
+    }
+
+    /// Adds a warning to the report.
+    fn warning(&mut self, location: Location, message: String) {
+        self.issues.push(Issue {
+            severity: Severity::Warning,
+            location,
+            message,
+        });
+    }
+
+    /// Checks that the fields of a header agree with each other.
+    ///
+    /// # Arguments
+    /// * `header` - The header to check.
+    pub fn check_header(&mut self, header: &Header) {
+        let field = |name: &str| Location::HeaderField(name.to_string());
+
+        if header.keys.store.is_empty() {
+            self.warning(
+                field("keys"),
+                "No columns are defined so buffered computations can not be run".to_string(),
+            );
+        }
+        for column in &header.normalisers.store_ref {
+            if !header.keys.reference.contains_key(column) {
+                self.error(
+                    field("normalisers"),
+                    format!(
+                        "Normaliser refers to column {} which is not defined",
+                        column
+                    ),
+                );
+            }
+        }
+        if header.outputs.is_empty() {
+            self.warning(field("output"), "Output name is missing".to_string());
+        }
+        for output in &header.outputs.store {
+            match &output.name {
+                None if output.normaliser.is_some() => self.error(
+                    field("output"),
+                    "Output has a normaliser but no name".to_string(),
+                ),
+                None => self.warning(field("output"), "Output name is missing".to_string()),
+                Some(_) => {}
+            }
+        }
+        if !header.inputs.is_empty() {
+            for binding in &header.inputs.store {
+                for column in &binding.columns {
+                    if !header.keys.reference.contains_key(column) {
+                        self.error(
+                            field("inputs"),
+                            format!(
+                                "Input {} is bound to column {} which is not defined",
+                                binding.name, column
+                            ),
+                        );
+                    }
+                }
+            }
+            for column in &header.keys.store {
+                let bound = header
+                    .inputs
+                    .store
+                    .iter()
+                    .any(|binding| binding.columns.contains(column));
+                if !bound {
+                    self.warning(
+                        field("inputs"),
+                        format!("Column {} is not bound to any input", column),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks that a header agrees with the inputs of the ONNX graph of its model.
+    ///
+    /// # Arguments
+    /// * `header` - The header to check.
+    /// * `session` - The session built from the model.
+    pub fn check_session(&mut self, header: &Header, session: &Session) {
+        let model_inputs: Vec<(String, Option<InputDims>)> = session
+            .inputs
+            .iter()
+            .map(|input| {
+                let dims = input
+                    .input_type
+                    .tensor_dimensions()
+                    .map(|dims| InputDims::from_model_dims(dims));
+                (input.name.clone(), dims)
+            })
+            .collect();
+
+        if header.inputs.is_empty() {
+            let (name, dims) = match model_inputs.first() {
+                Some(input) => input,
+                None => {
+                    self.error(Location::Model, "Model has no inputs".to_string());
+                    return;
+                }
+            };
+            if model_inputs.len() > 1 {
+                self.error(
+                    Location::HeaderField("inputs".to_string()),
+                    format!(
+                        "Model has {} inputs but no columns are bound to them",
+                        model_inputs.len()
+                    ),
+                );
+            }
+            let columns = header.input_width();
+            if columns == 0 {
+                return;
+            }
+            if !header.input_dims.is_empty() {
+                if let Err(error) = header.input_dims.resolve(columns) {
+                    self.error(
+                        Location::HeaderField("input_dims".to_string()),
+                        error.message,
+                    );
+                }
+            } else if let Some(dims) = dims {
+                self.check_width(name, dims, columns);
+            }
+            return;
+        }
+
+        for binding in &header.inputs.store {
+            match model_inputs.iter().find(|(name, _)| *name == binding.name) {
+                Some((name, Some(dims))) => {
+                    let width = binding
+                        .columns
+                        .iter()
+                        .map(|column| {
+                            header
+                                .encoders
+                                .get_encoder(column)
+                                .map_or(1, |encoder| encoder.width())
+                        })
+                        .sum();
+                    self.check_width(name, dims, width)
+                }
+                Some((_, None)) => {}
+                None => self.error(
+                    Location::OnnxInput(binding.name.clone()),
+                    format!("Model has no input named {}", binding.name),
+                ),
+            }
+        }
+        for (name, _) in &model_inputs {
+            if header.inputs.get(name).is_none() {
+                self.error(
+                    Location::OnnxInput(name.clone()),
+                    format!("No columns are bound to input {}", name),
+                );
+            }
+        }
+    }
+
+    /// Checks that a number of columns fits the declared dimensions of an ONNX input.
+    fn check_width(&mut self, name: &str, dims: &InputDims, columns: usize) {
+        if dims.resolve(columns).is_err() {
+            self.error(
+                Location::OnnxInput(name.to_string()),
+                format!(
+                    "{} columns do not fit the input dimensions {} of the model",
+                    columns, dims
+                ),
+            );
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self
+            .issues
+            .iter()
+            .map(|issue| format!("{} [{}]: {}", issue.severity, issue.location, issue.message))
+            .collect::<Vec<String>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::storage::header::normalisers::{
+        linear_scaling::LinearScaling, wrapper::NormaliserType,
+    };
+
+    #[test]
+    fn test_check_header() {
+        let mut header = Header::fresh();
+        let mut report = ValidationReport::fresh();
+        report.check_header(&header);
+        assert!(report.is_valid());
+        assert_eq!(report.warnings().count(), 2);
+
+        header.add_column("squarefoot".to_string());
+        header.add_column("num_floors".to_string());
+        header.add_output(
+            "house_price".to_string(),
+            Some(NormaliserType::LinearScaling(LinearScaling {
+                min: 0.0,
+                max: 1.0,
+            })),
+        );
+        header
+            .add_input_binding("squarefoot".to_string(), "numeric".to_string())
+            .unwrap();
+        header.normalisers.store_ref.push("missing".to_string());
+
+        let mut report = ValidationReport::fresh();
+        report.check_header(&header);
+        assert!(!report.is_valid());
+        let errors: Vec<&Issue> = report.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].location,
+            Location::HeaderField("normalisers".to_string())
+        );
+        let warnings: Vec<&Issue> = report.warnings().collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "Column num_floors is not bound to any input"
+        );
+        assert_eq!(
+            report.to_string(),
+            "error [header.normalisers]: Normaliser refers to column missing which is not defined\n\
+             warning [header.inputs]: Column num_floors is not bound to any input"
+        );
+    }
+}